@@ -12,10 +12,15 @@ use quote::ToTokens;
 use syn::parse_macro_input;
 use syn::parse_quote;
 use syn::spanned::Spanned;
-use syn::{Data, DataStruct, DeriveInput, Fields, Ident, Type};
+use syn::{
+    Data, DataStruct, DeriveInput, Fields, GenericArgument, Ident, Meta, NestedMeta, PathArguments,
+    Type,
+};
 
 pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
-    if !attr.is_empty() {
+    // `#[derive_readable(soa)]` also generates the struct of arrays.
+    let soa = attr.to_string() == "soa";
+    if !attr.is_empty() && !soa {
         let mut attr = attr.into_iter();
         let start = attr
             .next()
@@ -28,7 +33,7 @@ pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
             .span();
         let end = attr.fold(start, |_, item| item.span());
         let compile_error = crate::compile_error_at(
-            quote!("no extra attribute is suppported."),
+            quote!("unknown attribute; only `soa` is supported."),
             Span2::from(start),
             Span2::from(end),
         );
@@ -38,18 +43,55 @@ pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     let mut ast = parse_macro_input!(input as DeriveInput);
 
+    // `#[readable(...)]` is consumed here since it is not a real attribute
+    let order = match take_order(&mut ast) {
+        Ok(order) => order,
+        Err(error) => return error,
+    };
+
+    if let Err(error) = check_types(&ast) {
+        return error;
+    }
+
+    // every type parameter must be readable since the fields are translated through it
+    add_readable_bounds(&mut ast);
+
     // derive actually Readable
-    let derive = match derive_readable_impl(&ast) {
+    let derive = match derive_readable_impl(&ast, order.as_deref()) {
         Ok(derive) => derive,
         Err(error) => return error,
     };
 
+    let soa = if soa {
+        match derive_soa(&ast, order.as_deref()) {
+            Ok(soa) => soa,
+            Err(error) => return error,
+        }
+    } else {
+        TokenStream2::new()
+    };
+
     // modify AST to use actual Readable::Output type
     if let Err(error) = replace_type(&mut ast) {
         return error;
     }
 
-    quote!(#ast #derive).into()
+    quote!(#ast #derive #soa).into()
+}
+
+fn add_readable_bounds(ast: &mut DeriveInput) {
+    let params: Vec<Ident> = ast
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+
+    let where_clause = ast.generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::proconio::source::Readable));
+    }
 }
 
 fn replace_type(ast: &mut DeriveInput) -> Result<(), TokenStream> {
@@ -62,7 +104,7 @@ fn replace_type(ast: &mut DeriveInput) -> Result<(), TokenStream> {
         };
 
         let new_ty: Type = {
-            let ty = field.ty.clone().into_token_stream();
+            let ty = readable_type(&field.ty).into_token_stream();
             parse_quote!(<#ty as ::proconio::source::Readable>::Output)
         };
 
@@ -74,18 +116,311 @@ fn replace_type(ast: &mut DeriveInput) -> Result<(), TokenStream> {
     Ok(())
 }
 
-fn derive_readable_impl(ast: &DeriveInput) -> Result<TokenStream2, TokenStream> {
+// fixed-size arrays and smart pointers can't implement `Readable` directly, so they are read
+// through `ArrayOf`, `BoxOf`, `RcOf` and `ArcOf`.
+fn readable_type(ty: &Type) -> Type {
+    match ty {
+        Type::Array(array) => {
+            let elem = readable_type(&array.elem);
+            let len = &array.len;
+            parse_quote!(::proconio::marker::ArrayOf<#elem, #len>)
+        }
+        Type::Paren(paren) => readable_type(&paren.elem),
+        Type::Group(group) => readable_type(&group.elem),
+        _ => match smart_pointer(ty) {
+            Some((marker, inner)) => {
+                let inner = readable_type(inner);
+                parse_quote!(::proconio::marker::#marker<#inner>)
+            }
+            None => ty.clone(),
+        },
+    }
+}
+
+// recognizes `Box<T>`, `Rc<T>` and `Arc<T>` (possibly with a path) and returns the marker to read
+// it and `T`.
+fn smart_pointer(ty: &Type) -> Option<(Ident, &Type)> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    let marker = match last.ident.to_string().as_str() {
+        "Box" => "BoxOf",
+        "Rc" => "RcOf",
+        "Arc" => "ArcOf",
+        _ => return None,
+    };
+    let args = match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => args,
+        _ => return None,
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some((Ident::new(marker, last.ident.span()), inner)),
+        _ => None,
+    }
+}
+
+// recognizes `Option<T>`, possibly with a path.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            matches!(path.path.segments.last(), Some(last) if last.ident == "Option")
+        }
+        _ => false,
+    }
+}
+
+// finds the field types which can never be read and reports them with the reason, instead of the
+// unsatisfied trait bound.
+fn check_types(ast: &DeriveInput) -> Result<(), TokenStream> {
+    let fields = &get_data(ast)?.fields;
+    for field in fields {
+        if let Some((ty, reason)) = unsupported_type(&field.ty) {
+            let (start, end) = crate::get_span_range(ty.into_token_stream().into());
+            let message = format!("this field can't be read: {}.", reason);
+            return Err(crate::compile_error_at(
+                quote!(#message),
+                Span2::from(start),
+                Span2::from(end),
+            )
+            .into_token_stream()
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+fn unsupported_type(ty: &Type) -> Option<(&Type, &'static str)> {
+    let reason = match ty {
+        Type::Array(array) => return unsupported_type(&array.elem),
+        Type::Paren(paren) => return unsupported_type(&paren.elem),
+        Type::Group(group) => return unsupported_type(&group.elem),
+        Type::Reference(_) => {
+            "references are not supported; use an owned type such as `String` or `Vec<T>`"
+        }
+        Type::Slice(_) => "slices are not supported; use `Vec<T>`",
+        Type::Ptr(_) => "raw pointers are not supported",
+        Type::BareFn(_) => "function pointers are not supported",
+        Type::TraitObject(_) | Type::ImplTrait(_) => {
+            "trait objects are not supported; use a concrete type"
+        }
+        Type::Never(_) => "`!` is not supported",
+        Type::Tuple(tuple) => return tuple.elems.iter().find_map(unsupported_type),
+        _ if is_option(ty) => {
+            "`Option<T>` is not supported, since the input has no way to tell a missing value; \
+             read the flag or the count and then the value"
+        }
+        _ => return smart_pointer(ty).and_then(|(_, inner)| unsupported_type(inner)),
+    };
+
+    Some((ty, reason))
+}
+
+// parses `#[readable(order(a, b, ...))]` and removes it from the struct.
+fn take_order(ast: &mut DeriveInput) -> Result<Option<Vec<Ident>>, TokenStream> {
+    let (attrs, rest) = ast
+        .attrs
+        .drain(..)
+        .partition(|attr| attr.path.is_ident("readable"));
+    ast.attrs = rest;
+
+    let mut order = None;
+    for attr in attrs {
+        let error = |message: &str| -> TokenStream {
+            let span = attr.span();
+            crate::compile_error_at(quote!(#message), span, span)
+                .into_token_stream()
+                .into()
+        };
+
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => return Err(error("expected `#[readable(order(field, ...))]`.")),
+        };
+        for nested in list.nested {
+            let order_list = match nested {
+                NestedMeta::Meta(Meta::List(order_list)) if order_list.path.is_ident("order") => {
+                    order_list
+                }
+                _ => return Err(error("unknown readable attribute; expected `order(...)`.")),
+            };
+            if order.is_some() {
+                return Err(error("`order` is specified more than once."));
+            }
+
+            let mut idents = Vec::new();
+            for field in order_list.nested {
+                match field {
+                    NestedMeta::Meta(Meta::Path(path)) if path.get_ident().is_some() => {
+                        idents.push(path.get_ident().cloned().unwrap());
+                    }
+                    _ => return Err(error("`order` must be a list of field names.")),
+                }
+            }
+            order = Some(idents);
+        }
+    }
+
+    Ok(order)
+}
+
+// sorts the reads in the order specified by `#[readable(order(...))]`.
+fn sort_reads<'a>(
+    fields: &Fields,
+    field_info: &'a [FieldInfo],
+    order: &[Ident],
+) -> Result<Vec<&'a TokenStream2>, TokenStream> {
+    let error = |message: String, span: Span2| -> TokenStream {
+        crate::compile_error_at(quote!(#message), span, span)
+            .into_token_stream()
+            .into()
+    };
+
+    if let Fields::Unnamed(_) = fields {
+        return Err(error(
+            "`order` is only supported for structs with named fields.".to_string(),
+            fields.span(),
+        ));
+    }
+
+    let mut reads = Vec::new();
+    for (idx, ident) in order.iter().enumerate() {
+        if order[..idx].contains(ident) {
+            return Err(error(
+                format!("field `{}` appears more than once in `order`.", ident),
+                ident.span(),
+            ));
+        }
+        match field_info.iter().find(|f| f.ident == *ident) {
+            Some(info) => reads.push(&info.read),
+            None => {
+                return Err(error(
+                    format!("no field `{}` in this struct.", ident),
+                    ident.span(),
+                ))
+            }
+        }
+    }
+
+    if let Some(missing) = field_info.iter().find(|f| !order.contains(&f.ident)) {
+        return Err(error(
+            format!("field `{}` is missing in `order`.", missing.ident),
+            missing.ident.span(),
+        ));
+    }
+
+    Ok(reads)
+}
+
+fn ordered_reads<'a>(
+    fields: &Fields,
+    field_info: &'a [FieldInfo],
+    order: Option<&[Ident]>,
+) -> Result<Vec<&'a TokenStream2>, TokenStream> {
+    match order {
+        Some(order) => sort_reads(fields, field_info, order),
+        None => Ok(field_info.iter().map(|f| &f.read).collect()),
+    }
+}
+
+// generates `{name}SoA`, which has a `Vec` of the output type for each field, and reads the
+// records into it by `read_n()`.
+fn derive_soa(ast: &DeriveInput, order: Option<&[Ident]>) -> Result<TokenStream2, TokenStream> {
+    let name = get_name(ast);
+    let fields = &get_data(ast)?.fields;
+    if let Fields::Unnamed(_) | Fields::Unit = fields {
+        let span = ast.ident.span();
+        let message = "`soa` is only supported for structs with named fields.";
+        return Err(crate::compile_error_at(quote!(#message), span, span)
+            .into_token_stream()
+            .into());
+    }
+
+    let field_info = field_info(fields);
+    let reads = ordered_reads(fields, &field_info, order)?;
+    let idents: Vec<&Ident> = field_info.iter().map(|f| &f.ident).collect();
+    let columns = fields.iter().map(|field| {
+        let vis = &field.vis;
+        let ident = &field.ident;
+        let ty = readable_type(&field.ty);
+        quote!(#vis #ident: ::proconio::__Vec<<#ty as ::proconio::source::Readable>::Output>)
+    });
+
+    let vis = &ast.vis;
+    let soa = Ident::new(&format!("{}SoA", name), name.span());
+    let len = match idents.first() {
+        Some(first) => quote!(self.#first.len()),
+        None => quote!(0),
+    };
+    let doc = format!(
+        "The struct of arrays of `{}`, generated by `#[derive_readable(soa)]`.",
+        name
+    );
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #soa #generics #where_clause {
+            #(#columns,)*
+        }
+
+        impl #impl_generics #soa #ty_generics #where_clause {
+            /// Reads `n` records into the arrays.
+            pub fn read_n<R: ::proconio::source::BufRead, S: ::proconio::source::Source<R>>(source: &mut S, n: usize) -> Self {
+                let mut __soa = #soa {
+                    #(#idents: ::proconio::__Vec::with_capacity(n),)*
+                };
+                for _ in 0..n {
+                    #(#reads)*
+                    #(__soa.#idents.push(#idents);)*
+                }
+                __soa
+            }
+
+            /// Gets the number of the records.
+            pub fn len(&self) -> usize {
+                #len
+            }
+
+            /// Checks if there are no records.
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+        }
+
+        impl #impl_generics ::core::iter::FromIterator<#name #ty_generics> for #soa #ty_generics #where_clause {
+            fn from_iter<I: ::core::iter::IntoIterator<Item = #name #ty_generics>>(iter: I) -> Self {
+                let mut __soa = #soa {
+                    #(#idents: ::proconio::__Vec::new(),)*
+                };
+                for __record in iter {
+                    #(__soa.#idents.push(__record.#idents);)*
+                }
+                __soa
+            }
+        }
+    })
+}
+
+fn derive_readable_impl(
+    ast: &DeriveInput,
+    order: Option<&[Ident]>,
+) -> Result<TokenStream2, TokenStream> {
     let name = get_name(ast);
     let fields = &get_data(ast)?.fields;
 
     let field_info = field_info(fields);
     let generate = generate(fields, &name, &field_info);
-    let reads = field_info.iter().map(|f| &f.read);
+    let reads = ordered_reads(fields, &field_info, order)?;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
 
     let res = quote! {
-        impl ::proconio::source::Readable for #name {
-            type Output = #name;
-            fn read<R: ::std::io::BufRead, S: ::proconio::source::Source<R>>(source: &mut S) -> #name {
+        impl #impl_generics ::proconio::source::Readable for #name #ty_generics #where_clause {
+            type Output = #name #ty_generics;
+            fn read<R: ::proconio::source::BufRead, S: ::proconio::source::Source<R>>(source: &mut S) -> #name #ty_generics {
                 #(#reads)*
                 #generate
             }
@@ -158,7 +493,7 @@ fn field_named(fields: &Fields) -> Vec<FieldInfo> {
             "Please report this issue from ",
             "<https://github.com/statiolake/proconio-rs/issues>."
         ));
-        let ty = field.ty.clone();
+        let ty = readable_type(&field.ty);
         let read = quote! {
             let #ident = <#ty as ::proconio::source::Readable>::read(source);
         };
@@ -175,7 +510,7 @@ fn field_unnamed(fields: &Fields) -> Vec<FieldInfo> {
     for (idx, field) in fields.iter().enumerate() {
         let ident = format!("field{}", idx);
         let ident = Ident::new(&ident, Span2::call_site());
-        let ty = field.ty.clone();
+        let ty = readable_type(&field.ty);
         let read = quote! {
             let #ident = <#ty as ::proconio::source::Readable>::read(source);
         };
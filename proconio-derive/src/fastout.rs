@@ -40,7 +40,7 @@ pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
     itemfn.into_token_stream().into()
 }
 
-fn error_for_print_macros_in_closures(block: &Block) -> std::result::Result<(), Vec<Stmt>> {
+pub fn error_for_print_macros_in_closures(block: &Block) -> std::result::Result<(), Vec<Stmt>> {
     let mut visitor = BlockVisitor::default();
     visitor.visit_block(block);
     return if visitor.compile_errors.is_empty() {
@@ -116,7 +116,7 @@ fn error_for_print_macros_in_closures(block: &Block) -> std::result::Result<(),
     }
 }
 
-fn insert_new_print_macros(block: &Block) -> Block {
+pub fn insert_new_print_macros(block: &Block) -> Block {
     parse_quote! {{
         let __proconio_stdout = ::std::io::stdout();
         let mut __proconio_stdout = ::std::io::BufWriter::new(__proconio_stdout.lock());
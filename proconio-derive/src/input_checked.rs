@@ -0,0 +1,281 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proc_macro::TokenStream;
+use proc_macro2::{
+    Delimiter, Ident, Spacing, Span as Span2, TokenStream as TokenStream2, TokenTree,
+};
+use quote::{quote, quote_spanned};
+
+pub fn main(input: TokenStream) -> TokenStream {
+    let input = TokenStream2::from(input);
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let entries = parse_entries(&tokens);
+
+    let errors: Vec<TokenStream2> = check(&entries)
+        .into_iter()
+        .map(|(message, span)| quote_spanned!(span=> compile_error!(#message);))
+        .collect();
+
+    if errors.is_empty() {
+        quote!(::proconio::input! { #input }).into()
+    } else {
+        quote!(#(#errors)*).into()
+    }
+}
+
+enum Entry<'a> {
+    // `from source`, `with { ... }` and such, which are not checked.
+    Setting,
+    Variable {
+        pattern: &'a [TokenTree],
+        colon: Option<&'a TokenTree>,
+        kind: &'a [TokenTree],
+    },
+}
+
+fn parse_entries(tokens: &[TokenTree]) -> Vec<Entry<'_>> {
+    split_top_level(tokens, |token| is_punct(token, ','))
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if is_setting(segment) {
+                return Entry::Setting;
+            }
+
+            let colon = segment
+                .iter()
+                .enumerate()
+                .position(|(idx, token)| is_single_colon(segment, idx, token));
+            let (pattern, colon, rest) = match colon {
+                Some(idx) => (&segment[..idx], Some(&segment[idx]), &segment[idx + 1..]),
+                None => (segment, None, &segment[segment.len()..]),
+            };
+            let kind_end = rest
+                .iter()
+                .position(|token| is_ident(token, "as"))
+                .unwrap_or(rest.len());
+
+            Entry::Variable {
+                pattern,
+                colon,
+                kind: &rest[..kind_end],
+            }
+        })
+        .collect()
+}
+
+fn is_setting(segment: &[TokenTree]) -> bool {
+    match segment {
+        [first, ..] if is_ident(first, "from") || is_ident(first, "in") => true,
+        [first, TokenTree::Group(group)] if is_ident(first, "with") => {
+            group.delimiter() == Delimiter::Brace
+        }
+        _ => false,
+    }
+}
+
+fn check(entries: &[Entry<'_>]) -> Vec<(String, Span2)> {
+    let names: Vec<Vec<Ident>> = entries
+        .iter()
+        .map(|entry| match entry {
+            Entry::Variable { pattern, .. } => variable_names(pattern),
+            Entry::Setting => Vec::new(),
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    for (idx, entry) in entries.iter().enumerate() {
+        let (pattern, colon, kind) = match entry {
+            Entry::Variable {
+                pattern,
+                colon,
+                kind,
+            } => (pattern, colon, kind),
+            Entry::Setting => continue,
+        };
+
+        let colon = match colon {
+            Some(colon) => colon,
+            None => {
+                errors.push((
+                    "expected `variable: type`; the `:` and the type are missing".to_string(),
+                    span_of(pattern),
+                ));
+                continue;
+            }
+        };
+        if kind.is_empty() {
+            errors.push(("expected a type after `:`".to_string(), colon.span()));
+            continue;
+        }
+
+        // the length may be a variable outside of the macro, so only the variable which is read
+        // later in this invocation is reported.
+        // `input!` splits the type at `,` even in `<...>`.
+        if let Some(comma) = kind.iter().find(|token| is_punct(token, ',')) {
+            errors.push((
+                "the type containing `,` must be wrapped in braces, like `{HashMapOf<K, V>}`"
+                    .to_string(),
+                comma.span(),
+            ));
+            continue;
+        }
+
+        let is_read_before = |name: &Ident| names[..=idx].iter().flatten().any(|n| n == name);
+        let is_read_after = |name: &Ident| names[idx + 1..].iter().flatten().any(|n| n == name);
+        let kind_errors = check_kind(kind, &mut |length| {
+            if let [TokenTree::Ident(name)] = length {
+                if !is_read_before(name) && is_read_after(name) {
+                    return Some(format!(
+                        "`{}` is used in the array length before it is read; read `{}` first",
+                        name, name
+                    ));
+                }
+            }
+            None
+        });
+        errors.extend(kind_errors);
+    }
+
+    errors
+}
+
+// checks the arrays in the kind.  `check_length` reports the errors of the length.
+fn check_kind(
+    kind: &[TokenTree],
+    check_length: &mut dyn FnMut(&[TokenTree]) -> Option<String>,
+) -> Vec<(String, Span2)> {
+    let mut errors = Vec::new();
+    for token in kind {
+        let group = match token {
+            TokenTree::Group(group) => group,
+            _ => continue,
+        };
+        let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+
+        match group.delimiter() {
+            Delimiter::Parenthesis => errors.extend(check_kind(&inner, check_length)),
+            Delimiter::Bracket => {
+                let semi = inner.iter().position(|token| is_punct(token, ';'));
+                let (elem, length) = match semi {
+                    Some(semi) => (&inner[..semi], &inner[semi + 1..]),
+                    // `[type]` reads the length from the input.
+                    None => {
+                        errors.extend(check_kind(&inner, check_length));
+                        continue;
+                    }
+                };
+
+                if elem.is_empty() {
+                    errors.push((
+                        "expected the type of the elements before `;`".to_string(),
+                        group.span(),
+                    ));
+                }
+                if length.is_empty() {
+                    errors.push((
+                        "expected the length of the array after `;`".to_string(),
+                        group.span(),
+                    ));
+                } else if let Some(message) = check_length(length) {
+                    errors.push((message, span_of(length)));
+                }
+                errors.extend(check_kind(elem, check_length));
+            }
+            // `{type}` accepts any type.
+            Delimiter::Brace | Delimiter::None => {}
+        }
+    }
+
+    errors
+}
+
+// the variables bound by the pattern, such as `a` and `b` of `(a, mut b)`.
+fn variable_names(pattern: &[TokenTree]) -> Vec<Ident> {
+    let mut names = Vec::new();
+    for token in pattern {
+        match token {
+            TokenTree::Ident(ident) if ident != "mut" && ident != "_" => names.push(ident.clone()),
+            TokenTree::Group(group) => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                names.extend(variable_names(&inner));
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+// splits the tokens at the separators, which are not in `<...>`.
+fn split_top_level(
+    tokens: &[TokenTree],
+    is_separator: impl Fn(&TokenTree) -> bool,
+) -> Vec<&[TokenTree]> {
+    let mut segments = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        if is_punct(token, '<') {
+            depth += 1;
+        } else if is_punct(token, '>') && !is_arrow(tokens, idx) {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0 && is_separator(token) {
+            segments.push(&tokens[start..idx]);
+            start = idx + 1;
+        }
+    }
+    segments.push(&tokens[start..]);
+
+    segments
+}
+
+// `:` which is not a part of `::`.
+fn is_single_colon(tokens: &[TokenTree], idx: usize, token: &TokenTree) -> bool {
+    let joint_colon = |token: &TokenTree| match token {
+        TokenTree::Punct(punct) => punct.as_char() == ':' && punct.spacing() == Spacing::Joint,
+        _ => false,
+    };
+
+    is_punct(token, ':')
+        && !(joint_colon(token) && matches!(tokens.get(idx + 1), Some(next) if is_punct(next, ':')))
+        && !(idx > 0 && joint_colon(&tokens[idx - 1]))
+}
+
+// `>` of `->`.
+fn is_arrow(tokens: &[TokenTree], idx: usize) -> bool {
+    idx > 0
+        && match &tokens[idx - 1] {
+            TokenTree::Punct(punct) => punct.as_char() == '-' && punct.spacing() == Spacing::Joint,
+            _ => false,
+        }
+}
+
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+    match token {
+        TokenTree::Punct(punct) => punct.as_char() == ch,
+        _ => false,
+    }
+}
+
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+    match token {
+        TokenTree::Ident(ident) => ident == name,
+        _ => false,
+    }
+}
+
+fn span_of(tokens: &[TokenTree]) -> Span2 {
+    let start = match tokens.first() {
+        Some(first) => first.span(),
+        None => return Span2::call_site(),
+    };
+    let end = tokens.last().map(TokenTree::span).unwrap_or(start);
+
+    start.join(end).unwrap_or(start)
+}
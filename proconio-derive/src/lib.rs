@@ -10,7 +10,8 @@
 
 //! Macros to easily derive `Readable` and make stdout faster.
 //!
-//! proconio_derive provides two procedural macros (attributes): `derive_readable` and `fastout`.
+//! proconio_derive provides three procedural macros (attributes): `derive_readable`, `fastout`
+//! and `main`.
 //!
 //! # Examples for `#[derive_readable]`
 //!
@@ -77,6 +78,8 @@ use syn::Stmt;
 
 mod derive_readable;
 mod fastout;
+mod input_checked;
+mod main_attr;
 
 /// Derives `Readable` for your own type.
 ///
@@ -86,11 +89,130 @@ mod fastout;
 /// the output type of the read.  For example, if you have `Usize1` in your struct, it will
 /// actually be defined as `usize`.  Of course the `Usize1`'s  `Readable` implementation is used to
 /// read.
+///
+/// Tuple structs are read field by field in the same way, so `struct Query(u8, Usize1, Usize1);`
+/// is read from three tokens and has the fields of type `(u8, usize, usize)`.  Generic structs are
+/// also supported: every type parameter is required to be `Readable`, and the fields of the type
+/// parameter have its output type.  For example, `Pair<Usize1>` below has two `usize` fields.
+/// Fixed-size array fields like `[Usize1; 3]` are read element by element through
+/// `proconio::marker::ArrayOf`, and become `[usize; 3]`.  Similarly, `Box<T>`, `Rc<T>` and
+/// `Arc<T>` fields read `T` and wrap it, so `Box<Usize1>` becomes `Box<usize>`.  Fields which can
+/// never be read, such as references, trait objects and `Option<T>`, are reported as compile
+/// errors.
+///
+/// ```
+/// # extern crate proconio;
+/// # extern crate proconio_derive;
+/// # use proconio::input;
+/// # use proconio::marker::Usize1;
+/// # use proconio::source::auto::AutoSource;
+/// # use proconio_derive::derive_readable;
+/// #[derive_readable]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// # let source = AutoSource::from("1 2");
+/// input! {
+/// #   from source,
+///     pair: Pair<Usize1>,
+/// }
+///
+/// let (first, second): (usize, usize) = (pair.first, pair.second);
+/// assert_eq!((first, second), (0, 1));
+/// ```
+///
+/// The fields are read in the order of the definition.  To read them in another order, add
+/// `#[readable(order(...))]` listing all the field names below `#[derive_readable]`.  The layout of
+/// the struct is unchanged.  This is only for structs with named fields.
+///
+/// ```
+/// # extern crate proconio;
+/// # extern crate proconio_derive;
+/// # use proconio::input;
+/// # use proconio::marker::Usize1;
+/// # use proconio::source::auto::AutoSource;
+/// # use proconio_derive::derive_readable;
+/// #[derive_readable]
+/// #[readable(order(to, from, weight))]
+/// struct Edge {
+///     from: Usize1,
+///     to: Usize1,
+///     weight: u64,
+/// }
+///
+/// # let source = AutoSource::from("2 1 10");
+/// input! {
+/// #   from source,
+///     edge: Edge,
+/// }
+///
+/// assert_eq!((edge.from, edge.to, edge.weight), (0, 1, 10));
+/// ```
+///
+/// With `#[derive_readable(soa)]`, the struct of arrays `{Name}SoA` is also generated, which has a
+/// `Vec` for each field with the same name and visibility.  `read_n(source, n)` reads `n` records
+/// directly into the arrays, and an array of the records can be collected into it by
+/// `proconio::modifier::collect()`.  This is only for structs with named fields.
+///
+/// ```
+/// # extern crate proconio;
+/// # extern crate proconio_derive;
+/// # use proconio::input;
+/// # use proconio::marker::Usize1;
+/// # use proconio::modifier::collect;
+/// # use proconio::source::auto::AutoSource;
+/// # use proconio_derive::derive_readable;
+/// #[derive_readable(soa)]
+/// struct Edge {
+///     from: Usize1,
+///     to: Usize1,
+///     weight: u64,
+/// }
+///
+/// # let mut source = AutoSource::from("1 2 10  2 3 20  1 3 30");
+/// let edges = EdgeSoA::read_n(&mut source, 2);
+/// assert_eq!(edges.to, [1, 2]);
+///
+/// input! {
+/// #   from source,
+///     rest: [Edge; 1] as collect::<EdgeSoA>(),
+/// }
+/// assert_eq!(rest.weight, [30]);
+/// ```
 #[proc_macro_attribute]
 pub fn derive_readable(attr: TokenStream, input: TokenStream) -> TokenStream {
     derive_readable::main(attr, input)
 }
 
+/// `input!` with the checks of the syntax.
+///
+/// This accepts the same syntax as `proconio::input!` and expands to it, but checks the syntax
+/// beforehand to report the mistakes at the exact location, instead of the opaque errors from the
+/// inside of `input!`.  For example, the following is reported as "`n` is used in the array
+/// length before it is read; read `n` first" at `n` in the array.
+///
+/// ```compile_fail
+/// # extern crate proconio;
+/// # extern crate proconio_derive;
+/// # use proconio::source::auto::AutoSource;
+/// # use proconio_derive::input_checked;
+/// # let source = AutoSource::from("3 1 2 3");
+/// input_checked! {
+/// #   from source,
+///     a: [i64; n],
+///     n: usize,
+/// }
+/// ```
+///
+/// The missing type like `a:`, the missing length like `[i64;]` and the type containing `,` not
+/// wrapped in braces like `HashMapOf<K, V>` are reported as well.
+#[proc_macro]
+pub fn input_checked(input: TokenStream) -> TokenStream {
+    input_checked::main(input)
+}
+
 /// Enables buffering for stdout.
 ///
 /// You cannot create a closure containing `print!` or `println!` in `#[fastout]` function.  This
@@ -158,6 +280,44 @@ pub fn fastout(attr: TokenStream, input: TokenStream) -> TokenStream {
     fastout::main(attr, input)
 }
 
+/// Sets up your `main()` for competitive programming.
+///
+/// This attribute bundles the following:
+///
+/// - `print!` and `println!` are buffered as `#[fastout]` does.
+/// - The output of `proconio::output!` and `proconio::outputln!` is flushed at the end of the
+///   function and before panics, as `proconio::flush_on_exit()` does.
+///
+/// You can specify the following options:
+///
+/// - `interactive`: `input!` reads stdin line by line, as `input_interactive!` does.  The output
+///   buffered by `output!` is flushed before reading, and `print!` and `println!` are not buffered,
+///   so that the communication with the judge works.
+/// - `stack = "256MB"`: runs the function in a new thread with the specified size of stack.  This
+///   is useful for deep recursions.  The size can be an integer (in bytes) or a string with a unit
+///   `KB`, `MB` or `GB` (binary units: `1KB` is 1024 bytes).  The return value of the function must
+///   be `Send`.
+///
+/// ```
+/// # extern crate proconio;
+/// # extern crate proconio_derive;
+/// use proconio::outputln;
+///
+/// #[proconio_derive::main(stack = "64MB")]
+/// fn main() {
+///     fn depth(n: u32) -> u32 {
+///         if n == 0 { 0 } else { depth(n - 1) + 1 }
+///     }
+///
+///     outputln!("{}", depth(100_000));
+///     println!("hello");
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
+    main_attr::main(attr, input)
+}
+
 fn compile_error_at(args: TokenStream2, start: Span2, end: Span2) -> Stmt {
     let start = start.unwrap();
     let end = end.unwrap();
@@ -0,0 +1,158 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use quote::ToTokens;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote};
+use syn::{Block, ItemFn, Lit, Meta, NestedMeta, Stmt, Token};
+
+#[derive(Default)]
+struct Options {
+    interactive: bool,
+    stack: Option<usize>,
+}
+
+pub fn main(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let mut itemfn: ItemFn = parse_macro_input!(input as ItemFn);
+
+    let options = match parse_options(attr) {
+        Ok(options) => options,
+        Err(compile_errors) => {
+            itemfn.block.stmts = compile_errors;
+            return itemfn.into_token_stream().into();
+        }
+    };
+
+    if options.stack.is_some() && !itemfn.sig.inputs.is_empty() {
+        itemfn.block.stmts = vec![crate::compile_error_at(
+            quote!("`stack` option can only be used for functions without arguments."),
+            itemfn.sig.inputs.span(),
+            itemfn.sig.inputs.span(),
+        )];
+        return itemfn.into_token_stream().into();
+    }
+
+    let block = if options.interactive {
+        // Buffering `print!` and `println!` breaks the communication with the judge, so they are
+        // left as is in interactive mode.
+        (*itemfn.block).clone()
+    } else {
+        if let Err(compile_errors) =
+            crate::fastout::error_for_print_macros_in_closures(&itemfn.block)
+        {
+            itemfn.block.stmts = compile_errors;
+            return itemfn.into_token_stream().into();
+        }
+
+        crate::fastout::insert_new_print_macros(&itemfn.block)
+    };
+
+    let init_stdin = if options.interactive {
+        quote!(::proconio::__init_stdin_interactive();)
+    } else {
+        quote!()
+    };
+
+    let body = match options.stack {
+        Some(size) => run_in_thread(&itemfn, &block, size),
+        None => block.into_token_stream(),
+    };
+
+    itemfn.block = Box::new(parse_quote! {{
+        let __proconio_guard = ::proconio::flush_on_exit();
+        #init_stdin
+        #body
+    }});
+
+    itemfn.into_token_stream().into()
+}
+
+fn run_in_thread(itemfn: &ItemFn, block: &Block, size: usize) -> TokenStream2 {
+    let output = &itemfn.sig.output;
+    quote! {
+        fn __proconio_main() #output #block
+
+        ::proconio::run_with_stack(#size, __proconio_main)
+    }
+}
+
+fn parse_options(attr: TokenStream) -> Result<Options, Vec<Stmt>> {
+    let mut options = Options::default();
+    let (start, end) = crate::get_span_range(attr.clone());
+    let metas = Punctuated::<NestedMeta, Token![,]>::parse_terminated
+        .parse(attr)
+        .map_err(|_| {
+            vec![crate::compile_error_at(
+                quote!("failed to parse the options for #[proconio::main]."),
+                start.into(),
+                end.into(),
+            )]
+        })?;
+
+    for meta in metas {
+        match meta {
+            NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("interactive") => {
+                options.interactive = true;
+            }
+            NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("stack") => {
+                let size = match &nv.lit {
+                    Lit::Str(size) => parse_size(&size.value()),
+                    Lit::Int(size) => size.base10_parse().ok(),
+                    _ => None,
+                };
+
+                let size = size.ok_or_else(|| {
+                    vec![crate::compile_error_at(
+                        quote!(
+                            "invalid stack size; specify the number of bytes like \
+                            `stack = \"256MB\"` or `stack = 268435456`."
+                        ),
+                        nv.lit.span(),
+                        nv.lit.span(),
+                    )]
+                })?;
+                options.stack = Some(size);
+            }
+            other => {
+                return Err(vec![crate::compile_error_at(
+                    quote!(
+                        "unknown option for #[proconio::main]; \
+                        available options are `interactive` and `stack = \"...\"`."
+                    ),
+                    other.span(),
+                    other.span(),
+                )]);
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+// Parses the size like "256MB".  Units are binary, so "1KB" is 1024 bytes.
+fn parse_size(size: &str) -> Option<usize> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (num, unit) = size.split_at(split);
+    let num: usize = num.parse().ok()?;
+    let unit: usize = match &*unit.trim().to_ascii_uppercase() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return None,
+    };
+
+    num.checked_mul(unit)
+}
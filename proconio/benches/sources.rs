@@ -0,0 +1,51 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use proconio::bench::{self, read_n};
+use proconio::marker::{Bytes, Chars};
+use proconio::source::bytes::ByteSource;
+use proconio::source::line::LineSource;
+use proconio::source::once::OnceSource;
+use proconio::source::Readable;
+
+fn compare<T: Readable>(c: &mut Criterion, group: &str, input: &str, n: usize) {
+    let mut group = c.benchmark_group(group);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.sample_size(20);
+    group.bench_function(BenchmarkId::new("LineSource", n), |b| {
+        b.iter(|| read_n::<T, _, _>(LineSource::from(input), n))
+    });
+    group.bench_function(BenchmarkId::new("OnceSource", n), |b| {
+        b.iter(|| read_n::<T, _, _>(OnceSource::from(input), n))
+    });
+    group.bench_function(BenchmarkId::new("ByteSource", n), |b| {
+        b.iter(|| read_n::<T, _, _>(ByteSource::from(input), n))
+    });
+    group.finish();
+}
+
+fn ints(c: &mut Criterion) {
+    let n = 1_000_000;
+    compare::<i64>(c, "ints", &bench::ints(n), n);
+}
+
+fn strings(c: &mut Criterion) {
+    let n = 100_000;
+    let input = bench::words(n, 10);
+    compare::<Chars>(c, "strings/Chars", &input, n);
+    compare::<Bytes>(c, "strings/Bytes", &input, n);
+}
+
+fn matrix(c: &mut Criterion) {
+    let (h, w) = (1000, 1000);
+    // the leading `h w` is read as a part of the matrix, so the values are read as `u32`.
+    compare::<u32>(c, "matrix", &bench::matrix(h, w), h * w);
+}
+
+criterion_group!(benches, ints, strings, matrix);
+criterion_main!(benches);
@@ -0,0 +1,280 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Helpers for heuristic contests such as AtCoder Heuristic Contest.
+//!
+//! `Snapshot` keeps the entire input, so that a solution trying several starts can read the same
+//! input again from the beginning.  `replay()` runs a closure where `input!` without `from` reads
+//! the snapshot, and `source()` creates a source to be given by `from`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::ahc::Snapshot;
+//! use proconio::input;
+//!
+//! let snapshot = Snapshot::from("3\n1 2 3\n"); // usually `Snapshot::stdin()`.
+//! for _ in 0..2 {
+//!     let sum = snapshot.replay(|| {
+//!         input! {
+//!             n: usize,
+//!             a: [u64; n],
+//!         }
+//!         a.iter().sum::<u64>()
+//!     });
+//!     assert_eq!(sum, 6);
+//! }
+//! ```
+//!
+//! `Scorer` computes the score of an output for an input, as the local tester of the contest does.
+//! `run()` runs the solution over the inputs, capturing its output as `test_samples!`, and scores
+//! each of them.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::ahc;
+//! use proconio::{input, outputln};
+//!
+//! // the score is the sum of the chosen numbers, which must be in the input.
+//! fn score(input: &str, output: &str) -> Result<i64, String> {
+//!     let mut score = 0;
+//!     for token in output.split_whitespace() {
+//!         let x: i64 = token.parse().map_err(|_| format!("`{}` is not a number", token))?;
+//!         if !input.split_whitespace().skip(1).any(|t| t == token) {
+//!             return Err(format!("{} is not in the input", x));
+//!         }
+//!         score += x;
+//!     }
+//!     Ok(score)
+//! }
+//!
+//! fn solve() {
+//!     input! {
+//!         n: usize,
+//!         a: [i64; n],
+//!     }
+//!     outputln!("{}", a.iter().max().unwrap());
+//! }
+//!
+//! let report = ahc::run(&score, &["2\n1 5\n", "3\n4 2 3\n"], solve);
+//! assert_eq!(report.total(), 9);
+//! assert_eq!(report.failures(), 0);
+//! println!("{}", report);
+//! ```
+
+use crate::harness;
+use crate::source::auto::AutoSource;
+use crate::source::once::OnceSource;
+use crate::source::Source;
+use crate::stress;
+use std::fmt;
+use std::io::{BufReader, Cursor};
+use std::time::{Duration, Instant};
+
+/// The entire input kept to be read again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    input: String,
+}
+
+impl Snapshot {
+    /// Reads the entire stdin.
+    ///
+    /// The stdin is read through the source of `input!`, so the input given by `PROCONIO_INPUT` or
+    /// `PROCONIO_INPUT_TEXT` is read instead, and the input is recorded by `PROCONIO_RECORD` as
+    /// well.  Call this before reading the stdin by `input!`; the lines already read by `input!`
+    /// are not in the snapshot.
+    pub fn stdin() -> Snapshot {
+        let mut stdin = crate::__lock_stdin_for_input();
+        let mut input = String::new();
+        while let Some(line) = stdin.next_line() {
+            input.push_str(line);
+            input.push('\n');
+        }
+        Snapshot { input }
+    }
+
+    /// Gets the input.
+    pub fn as_str(&self) -> &str {
+        &self.input
+    }
+
+    /// Creates a source reading the input from the beginning.
+    pub fn source(&self) -> OnceSource<BufReader<&[u8]>> {
+        OnceSource::from(self.input.as_str())
+    }
+
+    /// Runs `f`, where `input!` without `from` reads the input from the beginning.
+    ///
+    /// See `harness::with_source()` for details.
+    pub fn replay<F: FnOnce() -> T, T>(&self, f: F) -> T {
+        let input = self.input.clone().into_bytes();
+        harness::with_source(AutoSource::new(BufReader::new(Cursor::new(input))), f)
+    }
+}
+
+impl From<String> for Snapshot {
+    fn from(input: String) -> Snapshot {
+        Snapshot { input }
+    }
+}
+
+impl<'a> From<&'a str> for Snapshot {
+    fn from(input: &'a str) -> Snapshot {
+        Snapshot {
+            input: input.to_string(),
+        }
+    }
+}
+
+/// A trait computing the score of an output, as the local tester of the contest.
+///
+/// This is implemented for closures (and functions) of the same signature as `score()`.
+pub trait Scorer {
+    /// Computes the score of `output` for `input`, or returns the reason why the output is
+    /// invalid.
+    fn score(&self, input: &str, output: &str) -> Result<i64, String>;
+}
+
+impl<F: Fn(&str, &str) -> Result<i64, String>> Scorer for F {
+    fn score(&self, input: &str, output: &str) -> Result<i64, String> {
+        self(input, output)
+    }
+}
+
+/// The result of a case of `run()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaseResult {
+    /// The score, or the reason why the output is invalid, including the panic of the solution.
+    pub score: Result<i64, String>,
+    /// The time taken by the solution, excluding the scoring.
+    pub elapsed: Duration,
+}
+
+/// The results of `run()`, in the order of the inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// The result of each case.
+    pub cases: Vec<CaseResult>,
+}
+
+impl Report {
+    /// Gets the sum of the scores of the valid outputs.
+    pub fn total(&self) -> i64 {
+        self.cases
+            .iter()
+            .filter_map(|case| case.score.as_ref().ok())
+            .sum()
+    }
+
+    /// Gets the number of the cases whose output is invalid.
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|case| case.score.is_err()).count()
+    }
+
+    /// Gets the longest time taken by the solution.
+    pub fn max_elapsed(&self) -> Duration {
+        self.cases
+            .iter()
+            .map(|case| case.elapsed)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, case) in self.cases.iter().enumerate() {
+            let ms = case.elapsed.as_secs_f64() * 1000.0;
+            match &case.score {
+                Ok(score) => writeln!(f, "case {}: score {} ({:.0} ms)", i, score, ms)?,
+                Err(reason) => writeln!(f, "case {}: invalid: {} ({:.0} ms)", i, reason, ms)?,
+            }
+        }
+        write!(
+            f,
+            "total: {} ({} invalid, max {:.0} ms)",
+            self.total(),
+            self.failures(),
+            self.max_elapsed().as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Runs `solve` over the inputs and scores each output by `scorer`.
+///
+/// As `test_samples!`, `input!` without `from` reads each input and the output of `output!` and
+/// `outputln!` is captured.  A panic of the solution makes the case invalid.
+pub fn run<S, I, F>(scorer: &S, inputs: I, mut solve: F) -> Report
+where
+    S: Scorer + ?Sized,
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+    F: FnMut(),
+{
+    let cases = inputs
+        .into_iter()
+        .map(|input| {
+            let input = input.as_ref();
+            let start = Instant::now();
+            let output = stress::run_solution(&mut solve, input);
+            let elapsed = start.elapsed();
+            let score = output
+                .map_err(|message| format!("the solution panicked: {}", message))
+                .and_then(|output| scorer.score(input, &output));
+            CaseResult { score, elapsed }
+        })
+        .collect();
+
+    Report { cases }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input, outputln};
+
+    fn length(_input: &str, output: &str) -> Result<i64, String> {
+        match output.trim().len() {
+            0 => Err("empty output".to_string()),
+            len => Ok(len as i64),
+        }
+    }
+
+    #[test]
+    fn run_reports_invalid_cases() {
+        let report = run(&length, &["ab", "0", "abcd"], || {
+            input! {
+                s: String,
+            }
+            assert_ne!(s, "0", "oops");
+            outputln!("{}", s);
+        });
+
+        assert_eq!(report.total(), 6);
+        assert_eq!(report.failures(), 1);
+        let reason = report.cases[1].score.as_ref().unwrap_err();
+        assert!(reason.starts_with("the solution panicked"), "{}", reason);
+    }
+
+    #[test]
+    fn snapshot_stdin() {
+        let snapshot = harness::with_source(OnceSource::from("2\n1 2\r\n\n"), Snapshot::stdin);
+        assert_eq!(snapshot.as_str(), "2\n1 2\n\n");
+    }
+
+    #[test]
+    fn snapshot_source() {
+        let snapshot = Snapshot::from("1 2");
+        for _ in 0..2 {
+            input! {
+                from snapshot.source(),
+                a: (u8, u8),
+            }
+            assert_eq!(a, (1, 2));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Helpers for measuring how fast the input is read.
+//!
+//! Under tight time limits, the choice of the source matters.  This module generates typical large
+//! inputs and reads them with each source, so that you can compare the sources for your own
+//! `Readable` types.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::bench;
+//!
+//! let input = bench::ints(1000);
+//! for timing in bench::compare_sources::<i64>(&input, 1000) {
+//!     println!("{}: {:?}", timing.name, timing.elapsed);
+//! }
+//! ```
+//!
+//! The inputs are generated from a fixed seed, so they are the same on every run.  For precise
+//! measurements, use `read_n()` with a benchmark harness such as `criterion`; the benchmarks of
+//! this crate in `benches/` are written in this way.
+
+use crate::gen::Rng;
+use crate::source::bytes::ByteSource;
+use crate::source::line::LineSource;
+use crate::source::once::OnceSource;
+use crate::source::{Readable, Source};
+use std::fmt::Write;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+const SEED: u64 = 0x5eed;
+
+/// Generates `n` integers in `-10^9..=10^9`, one line per 10 integers.
+pub fn ints(n: usize) -> String {
+    let mut rng = Rng::new(SEED);
+    let mut res = String::new();
+    for i in 0..n {
+        let sep = if i % 10 == 9 || i + 1 == n { '\n' } else { ' ' };
+        write!(
+            res,
+            "{}{}",
+            rng.gen_range_inclusive(-1_000_000_000, 1_000_000_000),
+            sep
+        )
+        .unwrap();
+    }
+    res
+}
+
+/// Generates `n` words of lowercase letters of length `len`, one word per line.
+pub fn words(n: usize, len: usize) -> String {
+    let mut rng = Rng::new(SEED);
+    let mut res = String::with_capacity(n * (len + 1));
+    for _ in 0..n {
+        for _ in 0..len {
+            res.push((b'a' + rng.gen_range_inclusive(0, 25) as u8) as char);
+        }
+        res.push('\n');
+    }
+    res
+}
+
+/// Generates a `h` by `w` matrix of digits `0..=9`, preceded by the line `h w`.
+pub fn matrix(h: usize, w: usize) -> String {
+    let mut rng = Rng::new(SEED);
+    let mut res = String::with_capacity((h + 1) * 2 * w);
+    writeln!(res, "{} {}", h, w).unwrap();
+    for _ in 0..h {
+        for j in 0..w {
+            let sep = if j + 1 == w { '\n' } else { ' ' };
+            write!(res, "{}{}", rng.gen_range_inclusive(0, 9), sep).unwrap();
+        }
+    }
+    res
+}
+
+/// Reads `n` values of `T` from the source.
+pub fn read_n<T: Readable, R: BufRead, S: Source<R>>(mut source: S, n: usize) -> Vec<T::Output> {
+    (0..n).map(|_| T::read(&mut source)).collect()
+}
+
+/// The time taken to read the input with a source.  Returned by `compare_sources()`.
+#[derive(Debug, Clone)]
+pub struct Timing {
+    /// The name of the source, such as `"OnceSource"`.
+    pub name: &'static str,
+
+    /// The time taken to create the source and read the values.
+    pub elapsed: Duration,
+}
+
+/// Reads `n` values of `T` from the input with each source, and returns the time taken.
+///
+/// The sources are `LineSource`, `OnceSource` and `ByteSource`.  Creating the source is included
+/// in the time, since `OnceSource` and `ByteSource` read the whole input at that point.
+pub fn compare_sources<T: Readable>(input: &str, n: usize) -> Vec<Timing> {
+    vec![
+        measure("LineSource", || {
+            read_n::<T, _, _>(LineSource::from(input), n)
+        }),
+        measure("OnceSource", || {
+            read_n::<T, _, _>(OnceSource::from(input), n)
+        }),
+        measure("ByteSource", || {
+            read_n::<T, _, _>(ByteSource::from(input), n)
+        }),
+    ]
+}
+
+fn measure<T, F: FnOnce() -> T>(name: &'static str, f: F) -> Timing {
+    let start = Instant::now();
+    let res = f();
+    let elapsed = start.elapsed();
+    drop(res);
+    Timing { name, elapsed }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Chars;
+
+    #[test]
+    fn workloads() {
+        let input = matrix(3, 4);
+        let source = OnceSource::from(&*input);
+        input! {
+            from source,
+            h: usize,
+            w: usize,
+            a: [[u8; w]; h],
+        }
+        assert!(a.iter().flatten().all(|&x| x <= 9));
+
+        let input = words(5, 3);
+        let source = OnceSource::from(&*input);
+        input! {
+            from source,
+            s: [Chars; 5],
+        }
+        assert!(s.iter().all(|s| s.len() == 3));
+
+        assert_eq!(ints(25), ints(25));
+        assert_eq!(ints(25).lines().count(), 3);
+    }
+
+    #[test]
+    fn same_values() {
+        let input = ints(100);
+        let values = read_n::<i64, _, _>(LineSource::from(&*input), 100);
+        assert_eq!(read_n::<i64, _, _>(ByteSource::from(&*input), 100), values);
+
+        let names: Vec<_> = compare_sources::<i64>(&input, 100)
+            .iter()
+            .map(|timing| timing.name)
+            .collect();
+        assert_eq!(names, ["LineSource", "OnceSource", "ByteSource"]);
+    }
+}
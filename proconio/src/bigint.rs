@@ -0,0 +1,59 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Re-exports arbitrary precision integers of `num-bigint`.
+//!
+//! This module requires `bigint` feature enabled.  `BigInt` and `BigUint` implement `FromStr`, so
+//! they are `Readable` as they are; integers beyond 128 bits can be read directly.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::bigint::BigUint;
+//! use proconio::input;
+//! # let source = AutoSource::from("340282366920938463463374607431768211456");
+//!
+//! input! {
+//! #   from source,
+//!     x: BigUint,
+//! }
+//!
+//! assert_eq!(x, BigUint::from(u128::MAX) + 1u32);
+//! ```
+
+pub use num_bigint::{BigInt, BigUint, Sign};
+
+#[cfg(test)]
+mod tests {
+    use super::{BigInt, BigUint};
+    use crate::input;
+    use crate::source::auto::AutoSource;
+
+    #[test]
+    fn input_bigint() {
+        let source = AutoSource::from("-123456789012345678901234567890123456789012 2 0 00");
+        input! {
+            from source,
+            x: BigInt,
+            n: usize,
+            a: [BigUint; n],
+        }
+
+        assert_eq!(x.to_string(), "-123456789012345678901234567890123456789012");
+        assert_eq!(a, [BigUint::from(0u32), BigUint::from(0u32)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `-1` to the value of type")]
+    fn input_err_biguint_negative() {
+        let source = AutoSource::from("-1");
+        input! {
+            from source,
+            _x: BigUint,
+        }
+    }
+}
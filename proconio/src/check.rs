@@ -0,0 +1,263 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Helpers to compare the output of a solution with the expected one.
+//!
+//! These are the comparisons the checkers of the judges usually do, for your own checker or local
+//! test harness.  Each function reads the expected output and the actual output from two sources
+//! and returns the first mismatch.
+//!
+//! - `tokens_equal()` compares the tokens, ignoring the difference of whitespaces.
+//! - `floats_close()` compares the tokens, accepting a small error of the floating point numbers.
+//! - `lines_equal_ignoring_trailing_ws()` compares the lines, ignoring the trailing whitespaces
+//!   of each line and the trailing empty lines.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::check;
+//! use proconio::source::once::OnceSource;
+//!
+//! let mut expected = OnceSource::from("0.5 yes\n");
+//! let mut actual = OnceSource::from("0.5000001\nyes");
+//! assert_eq!(check::floats_close(&mut expected, &mut actual, 1e-6), Ok(()));
+//!
+//! let mut expected = OnceSource::from("1 2 3\n");
+//! let mut actual = OnceSource::from("1 2 4\n");
+//! let mismatch = check::tokens_equal(&mut expected, &mut actual).unwrap_err();
+//! assert_eq!(mismatch.to_string(), "token #2 differs: expected `3`, found `4`");
+//! ```
+//!
+//! The line comparison needs a source keeping the lines, such as `OnceSource` and `LineSource`.
+
+use crate::source::{BufRead, Source};
+use std::fmt;
+
+/// The unit compared by the functions in this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    /// A whitespace-separated token.
+    Token,
+    /// A line.
+    Line,
+}
+
+/// The first token or line where two outputs differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Whether a token or a line differs.
+    pub unit: Unit,
+
+    /// 0-indexed position of the differing token or line.
+    pub position: usize,
+
+    /// The token or line in the expected output.  `None` if the expected output is shorter.
+    pub expected: Option<String>,
+
+    /// The token or line in the actual output.  `None` if the actual output is shorter.
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |found: &Option<String>| match found {
+            Some(found) => format!("`{}`", found),
+            None => "end of output".to_string(),
+        };
+        let unit = match self.unit {
+            Unit::Token => "token",
+            Unit::Line => "line",
+        };
+
+        write!(
+            f,
+            "{} #{} differs: expected {}, found {}",
+            unit,
+            self.position,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Compares the tokens of the two outputs exactly.
+pub fn tokens_equal<R1, S1, R2, S2>(expected: &mut S1, actual: &mut S2) -> Result<(), Mismatch>
+where
+    R1: BufRead,
+    S1: Source<R1>,
+    R2: BufRead,
+    S2: Source<R2>,
+{
+    compare_tokens(expected, actual, |expected, actual| expected == actual)
+}
+
+/// Compares the tokens of the two outputs, accepting the absolute or relative error up to `eps`.
+///
+/// If the expected token is a number, the actual token `a` is accepted when it is a number with
+/// `|a - e| <= eps * max(1, |e|)` for the expected value `e`.  The other tokens are compared
+/// exactly.
+pub fn floats_close<R1, S1, R2, S2>(
+    expected: &mut S1,
+    actual: &mut S2,
+    eps: f64,
+) -> Result<(), Mismatch>
+where
+    R1: BufRead,
+    S1: Source<R1>,
+    R2: BufRead,
+    S2: Source<R2>,
+{
+    compare_tokens(expected, actual, |expected, actual| {
+        if expected == actual {
+            return true;
+        }
+        match (expected.parse::<f64>(), actual.parse::<f64>()) {
+            (Ok(e), Ok(a)) => (a - e).abs() <= eps * e.abs().max(1.0),
+            _ => false,
+        }
+    })
+}
+
+/// Compares the lines of the two outputs, ignoring the trailing whitespaces of each line and the
+/// trailing empty lines.
+///
+/// The leading whitespaces and the spaces between the tokens must be the same.  Both sources must
+/// support `Source::next_line()`.
+pub fn lines_equal_ignoring_trailing_ws<R1, S1, R2, S2>(
+    expected: &mut S1,
+    actual: &mut S2,
+) -> Result<(), Mismatch>
+where
+    R1: BufRead,
+    S1: Source<R1>,
+    R2: BufRead,
+    S2: Source<R2>,
+{
+    let mut position = 0;
+    loop {
+        let e = expected.next_line().map(str::trim_end);
+        let a = actual.next_line().map(str::trim_end);
+        match (e, a) {
+            (None, None) => return Ok(()),
+            (Some(e), Some(a)) if e == a => {}
+            // the trailing empty lines.
+            (Some(""), None) | (None, Some("")) => {}
+            (e, a) => return Err(mismatch(Unit::Line, position, e, a)),
+        }
+        position += 1;
+    }
+}
+
+fn compare_tokens<R1, S1, R2, S2, F>(
+    expected: &mut S1,
+    actual: &mut S2,
+    mut same: F,
+) -> Result<(), Mismatch>
+where
+    R1: BufRead,
+    S1: Source<R1>,
+    R2: BufRead,
+    S2: Source<R2>,
+    F: FnMut(&str, &str) -> bool,
+{
+    let mut position = 0;
+    loop {
+        match (expected.next_token(), actual.next_token()) {
+            (None, None) => return Ok(()),
+            (Some(e), Some(a)) if same(e, a) => position += 1,
+            (e, a) => return Err(mismatch(Unit::Token, position, e, a)),
+        }
+    }
+}
+
+fn mismatch(unit: Unit, position: usize, expected: Option<&str>, actual: Option<&str>) -> Mismatch {
+    Mismatch {
+        unit,
+        position,
+        expected: expected.map(str::to_string),
+        actual: actual.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::once::OnceSource;
+
+    fn tokens(expected: &str, actual: &str) -> Result<(), String> {
+        let mut expected = OnceSource::from(expected);
+        let mut actual = OnceSource::from(actual);
+        tokens_equal(&mut expected, &mut actual).map_err(|m| m.to_string())
+    }
+
+    fn floats(expected: &str, actual: &str) -> Result<(), String> {
+        let mut expected = OnceSource::from(expected);
+        let mut actual = OnceSource::from(actual);
+        floats_close(&mut expected, &mut actual, 1e-6).map_err(|m| m.to_string())
+    }
+
+    fn lines(expected: &str, actual: &str) -> Result<(), String> {
+        let mut expected = OnceSource::from(expected);
+        let mut actual = OnceSource::from(actual);
+        lines_equal_ignoring_trailing_ws(&mut expected, &mut actual).map_err(|m| m.to_string())
+    }
+
+    #[test]
+    fn exact_tokens() {
+        assert_eq!(tokens("1 2\n3\n", "1\n2 3"), Ok(()));
+        assert_eq!(
+            tokens("1 2 3", "1 2"),
+            Err("token #2 differs: expected `3`, found end of output".into())
+        );
+        assert_eq!(
+            tokens("Yes", "Yes No"),
+            Err("token #1 differs: expected end of output, found `No`".into())
+        );
+        assert_eq!(
+            tokens("1.0", "1"),
+            Err("token #0 differs: expected `1.0`, found `1`".into())
+        );
+    }
+
+    #[test]
+    fn close_floats() {
+        assert_eq!(floats("1.0 Yes", "1 Yes"), Ok(()));
+        assert_eq!(floats("0.1", "0.1000009"), Ok(()));
+        assert_eq!(floats("1000000", "1000000.9"), Ok(()));
+        assert_eq!(
+            floats("0.1", "0.100002"),
+            Err("token #0 differs: expected `0.1`, found `0.100002`".into())
+        );
+        assert_eq!(
+            floats("1", "NaN"),
+            Err("token #0 differs: expected `1`, found `NaN`".into())
+        );
+        assert_eq!(
+            floats("Yes", "yes"),
+            Err("token #0 differs: expected `Yes`, found `yes`".into())
+        );
+    }
+
+    #[test]
+    fn trailing_whitespaces() {
+        assert_eq!(lines("#.#\n...\n", "#.# \r\n...\n\n"), Ok(()));
+        assert_eq!(lines("1 2\n\n", "1 2"), Ok(()));
+        assert_eq!(
+            lines("1 2\n3\n", "1 2 3\n"),
+            Err("line #0 differs: expected `1 2`, found `1 2 3`".into())
+        );
+        assert_eq!(
+            lines("a\n\nb\n", "a\nb\n"),
+            Err("line #1 differs: expected ``, found `b`".into())
+        );
+        assert_eq!(
+            lines("a\nb\n", "a\n"),
+            Err("line #1 differs: expected `b`, found end of output".into())
+        );
+    }
+}
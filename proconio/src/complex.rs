@@ -0,0 +1,71 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Re-exports complex numbers of `num-complex`.
+//!
+//! This module requires `complex` feature enabled.  A complex number is written in one of two
+//! ways, so read it through the marker of the format:
+//!
+//! - `marker::ComplexPair<T>` reads the real part and the imaginary part from two tokens, like a
+//!   point `3 -4`.
+//! - `marker::ComplexToken<T>` reads a single token like `3-4i`, as `Complex<T>` itself does.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::complex::Complex64;
+//! use proconio::input;
+//! use proconio::marker::{ComplexPair, ComplexToken};
+//! # let source = AutoSource::from("3 -4  1.5+2i");
+//!
+//! input! {
+//! #   from source,
+//!     p: ComplexPair<f64>,
+//!     z: ComplexToken<f64>,
+//! }
+//!
+//! assert_eq!(p.norm(), 5.0);
+//! assert_eq!(z, Complex64::new(1.5, 2.0));
+//! ```
+
+pub use num_complex::{Complex, Complex32, Complex64};
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+    use crate::input;
+    use crate::marker::{ComplexPair, ComplexToken, Usize1};
+    use crate::source::auto::AutoSource;
+
+    #[test]
+    fn input_complex() {
+        let source = AutoSource::from("2 1 -1 0 3  1 2  i -2.5j 7");
+        input! {
+            from source,
+            n: usize,
+            points: [ComplexPair<i64>; n],
+            indices: ComplexPair<Usize1>,
+            z: [ComplexToken<f64>; 2],
+            w: Complex<i32>,
+        }
+
+        assert_eq!(points, [Complex::new(1, -1), Complex::new(0, 3)]);
+        assert_eq!(indices, Complex::new(0, 1));
+        assert_eq!(z, [Complex::new(0.0, 1.0), Complex::new(0.0, -2.5)]);
+        assert_eq!(w, Complex::new(7, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the complex number `1+`")]
+    fn input_err_complex_token() {
+        let source = AutoSource::from("1+");
+        input! {
+            from source,
+            _z: ComplexToken<f64>,
+        }
+    }
+}
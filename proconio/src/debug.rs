@@ -0,0 +1,120 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Debug prints enabled only in your local environment.
+//!
+//! The debug prints left in the submission may cause WA (if written to the stdout) or TLE (if
+//! printed many times).  `dbg_local!` prints the values to the stderr only if the debug prints are
+//! enabled, which is when:
+//!
+//! - `local` feature of `proconio` is enabled, or
+//! - the environment variable `PROCONIO_DEBUG` is set.
+//!
+//! Neither is the case on the judge, where `dbg_local!` does nothing: the values are not even
+//! evaluated.  Enable `local` feature only in your local environment, for example by
+//! `cargo run --features proconio/local`, or just set `PROCONIO_DEBUG=1`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::dbg_local;
+//!
+//! let a = vec![1, 2, 3];
+//! let sum: i32 = a.iter().sum();
+//! dbg_local!(a, sum); // prints `[src/main.rs:5] a = [1, 2, 3]` and `[src/main.rs:5] sum = 6`.
+//! ```
+//!
+//! The output buffered by `output!` and `outputln!` is flushed before printing, so the debug prints
+//! appear in the right place among the output in the terminal.  They are written through the
+//! stderr buffered by `eoutput!`, and flushed immediately.  Unlike `dbg!`, the values are
+//! borrowed, not moved.
+//!
+//! When enabled, the guard of `flush_on_exit()` also warns to the stderr at the end of the program
+//! if some input is left unread on the stdin, which usually means a wrong input format.  See
+//! `assert_input_consumed!` to check it explicitly.
+
+use once_cell::sync::OnceCell;
+use std::env;
+use std::fmt::{self, Write as _};
+use std::io::Write as _;
+
+/// The environment variable enabling the debug prints.
+pub const DEBUG_VAR: &str = "PROCONIO_DEBUG";
+
+/// Check if the debug prints by `dbg_local!` are enabled.
+///
+/// The environment variable is checked only once, at the first call.
+pub fn enabled() -> bool {
+    if cfg!(feature = "local") {
+        return true;
+    }
+
+    static ENABLED: OnceCell<bool> = OnceCell::new();
+    *ENABLED.get_or_init(|| env::var_os(DEBUG_VAR).is_some())
+}
+
+/// print the values to the stderr if the debug prints are enabled.
+///
+/// basic syntax is:
+/// ```text
+/// dbg_local!(expr, ...);
+/// ```
+/// Each value is printed by `{:?}` with the location and the expression.  See the document of
+/// [`debug`](debug/index.html) module for details.
+#[macro_export]
+macro_rules! dbg_local {
+    ($($value:expr),* $(,)?) => {
+        if $crate::debug::enabled() {
+            $crate::debug::__print(
+                ::std::file!(),
+                ::std::line!(),
+                &[$((::std::stringify!($value), &$value as &dyn ::std::fmt::Debug)),*],
+            );
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn __print(file: &str, line: u32, values: &[(&str, &dyn fmt::Debug)]) {
+    crate::try_flush_output();
+    let mut stderr = crate::stderr_writer();
+    let _ = stderr.write_all(format(file, line, values).as_bytes());
+    let _ = stderr.flush();
+}
+
+fn format(file: &str, line: u32, values: &[(&str, &dyn fmt::Debug)]) -> String {
+    let mut res = String::new();
+    if values.is_empty() {
+        writeln!(res, "[{}:{}]", file, line).unwrap();
+    }
+    for (expr, value) in values {
+        writeln!(res, "[{}:{}] {} = {:?}", file, line, expr, value).unwrap();
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_values() {
+        let a = vec![1, 2];
+        assert_eq!(
+            format("src/main.rs", 3, &[("a", &a), ("a.len() + 1", &3)]),
+            "[src/main.rs:3] a = [1, 2]\n[src/main.rs:3] a.len() + 1 = 3\n"
+        );
+        assert_eq!(format("src/main.rs", 4, &[]), "[src/main.rs:4]\n");
+    }
+
+    #[test]
+    fn borrowed_values() {
+        let s = String::from("moved?");
+        dbg_local!(s, s.len(),);
+        dbg_local!();
+        assert_eq!(s, "moved?");
+    }
+}
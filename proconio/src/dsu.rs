@@ -0,0 +1,230 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines `Dsu`, a union-find built from the edges while reading.
+//!
+//! This module requires `dsu` feature enabled.  For simple connectivity problems, reading the
+//! edges and merging their endpoints is all you need.  `Dsu` is a modifier merging the edges of
+//! the array into the union-find with `n` vertices:
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::dsu::Dsu;
+//! use proconio::input;
+//! use proconio::marker::Usize1;
+//! # let source = AutoSource::from("4 2  1 2  3 2");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     m: usize,
+//!     mut uf: [(Usize1, Usize1); m] as Dsu::new(n),
+//! }
+//!
+//! assert!(uf.same(0, 2));
+//! assert!(!uf.same(0, 3));
+//! assert_eq!(uf.count(), 2);
+//! ```
+//!
+//! If the input is exactly `n m` followed by `m` edges of 1-indexed vertices, `DsuFromEdges` reads
+//! them all at once.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::dsu::DsuFromEdges;
+//! use proconio::input;
+//! # let source = AutoSource::from("3 1  1 3");
+//!
+//! input! {
+//! #   from source,
+//!     mut uf: DsuFromEdges,
+//! }
+//!
+//! assert_eq!(uf.len(), 3);
+//! assert_eq!(uf.size(2), 2);
+//! ```
+
+use crate::marker::Usize1;
+use crate::modifier::Modifier;
+use crate::source::{BufRead, Readable, Source};
+use alloc::vec::Vec;
+use core::mem;
+
+/// Union-find (disjoint set union) over the vertices `0..n`.
+///
+/// `find()` compresses the path, and `union()` merges the smaller set into the larger one.
+///
+/// As a modifier, `Dsu` merges the edges `(usize, usize)` of the array into itself.  Output of
+/// reading has type `Dsu`.  Vertices out of `0..n` cause panic.
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl Dsu {
+    /// Creates `Dsu` where each of the `n` vertices is in its own set.
+    pub fn new(n: usize) -> Dsu {
+        Dsu {
+            parent: (0..n).collect(),
+            size: alloc::vec![1; n],
+            count: n,
+        }
+    }
+
+    /// Gets the number of the vertices.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Check if there is no vertex.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Gets the number of the sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Gets the representative of the set containing `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut x = x;
+        while x != root {
+            x = mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`.  Returns `false` if they are already the same.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.count -= 1;
+        true
+    }
+
+    /// Check if `a` and `b` are in the same set.
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Gets the size of the set containing `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Gets the sets.  Each set is sorted, and the sets are sorted by their smallest vertices.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut index = alloc::vec![usize::MAX; n];
+        let mut res: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        for x in 0..n {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = res.len();
+                res.push(Vec::new());
+            }
+            res[index[root]].push(x);
+        }
+        res
+    }
+}
+
+impl Modifier<(usize, usize)> for Dsu {
+    type Output = Dsu;
+    fn modify<I: Iterator<Item = (usize, usize)>>(mut self, items: I) -> Dsu {
+        let n = self.len();
+        for (u, v) in items {
+            assert!(
+                u < n && v < n,
+                "failed to merge the edge ({}, {}): the vertices must be in `0..{}`",
+                u,
+                v,
+                n
+            );
+            self.union(u, v);
+        }
+        self
+    }
+}
+
+/// DsuFromEdges: `n m` followed by `m` edges of 1-indexed vertices.  Output of reading has type
+/// `Dsu` with `n` vertices where the edges are merged.
+pub enum DsuFromEdges {}
+
+impl Readable for DsuFromEdges {
+    type Output = Dsu;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Dsu {
+        let n = usize::read(source);
+        let m = usize::read(source);
+        Dsu::new(n).modify((0..m).map(|_| (Usize1::read(source), Usize1::read(source))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::source::slice::SliceSource;
+    use alloc::vec;
+
+    #[test]
+    fn union_find() {
+        let mut uf = Dsu::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(4, 1));
+        assert!(!uf.union(0, 4));
+        assert!(uf.union(3, 5));
+
+        assert_eq!(uf.count(), 3);
+        assert_eq!(uf.size(4), 3);
+        assert_eq!(uf.find(0), uf.find(4));
+        assert_eq!(uf.groups(), [vec![0, 1, 4], vec![2], vec![3, 5]]);
+    }
+
+    #[test]
+    fn read_edges() {
+        let source = SliceSource::from("5 3  1 2  2 3  4 5  1 1  2 2");
+        input! {
+            from source,
+            mut uf: DsuFromEdges,
+            mut loops: [(Usize1, Usize1); 2] as Dsu::new(2),
+        }
+
+        assert_eq!(uf.groups(), [vec![0, 1, 2], vec![3, 4]]);
+        assert_eq!(loops.count(), 2);
+        assert!(!loops.same(0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to merge the edge (0, 3): the vertices must be in `0..3`")]
+    fn vertex_out_of_range() {
+        let source = SliceSource::from("3 1  1 4");
+        input! {
+            from source,
+            _uf: DsuFromEdges,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Prints the best answer so far when the program is about to be stopped.
+//!
+//! In heuristic contests, a program which is still improving its answer at the time limit should
+//! print the best answer found so far rather than nothing.  `register()` sets the "emergency
+//! output" closure, which is called on SIGTERM or SIGINT (on Unix), or at the deadline set by
+//! `set_deadline()`.  After the closure, the output buffered by `output!` is flushed and the
+//! process exits with the status 0.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::emergency;
+//! use proconio::outputln;
+//! use std::sync::{Arc, Mutex};
+//! use std::time::Duration;
+//!
+//! let best = Arc::new(Mutex::new(0u64));
+//! {
+//!     let best = Arc::clone(&best);
+//!     emergency::register(move || outputln!("{}", best.lock().unwrap()));
+//! }
+//! emergency::set_deadline(Duration::from_millis(1900));
+//!
+//! for score in 0.. {
+//!     // improve the answer.
+//!     *best.lock().unwrap() = score;
+//! }
+//! ```
+//!
+//! The closure runs on a thread of this module while the program keeps running, so share the
+//! answer through `Mutex` or atomics as above.  If the program finishes normally, call `cancel()`
+//! before printing the final answer, so that the answer is not printed twice.
+//!
+//! The output is written through the buffered stdout, so the closure waits while another thread
+//! holds the handle of `stdout_writer()`.
+//!
+//! This module requires `emergency` feature.
+
+use std::io::Write;
+use std::process;
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+type Closure = Box<dyn FnOnce() + Send>;
+
+static EMERGENCY: Mutex<Option<Closure>> = Mutex::new(None);
+
+/// Registers the closure printing the answer so far, replacing the previous one.
+///
+/// On Unix, this also starts handling SIGTERM and SIGINT at the first call.  Once handled, these
+/// signals don't terminate the process by themselves; the process exits after the closure.  After
+/// `cancel()`, they flush the output and exit with the status `128 + signal`.
+pub fn register<F: FnOnce() + Send + 'static>(closure: F) {
+    *lock() = Some(Box::new(closure));
+
+    #[cfg(unix)]
+    handle_signals();
+}
+
+/// Calls the registered closure after `timeout` from now, unless it is cancelled.
+pub fn set_deadline(timeout: Duration) {
+    thread::spawn(move || {
+        thread::sleep(timeout);
+        trigger(None);
+    });
+}
+
+/// Cancels the registered closure.
+///
+/// If the closure is running, this waits until the process exits.
+pub fn cancel() {
+    lock().take();
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<Closure>> {
+    EMERGENCY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Calls the closure and exits.  `signal` is the signal received, which exits the process even
+// without the closure.
+fn trigger(signal: Option<i32>) {
+    // keeps the lock until the exit so that `cancel()` can't return while the closure runs.
+    let mut lock = lock();
+    let status = match (lock.take(), signal) {
+        (Some(closure), _) => {
+            closure();
+            0
+        }
+        (None, Some(signal)) => 128 + signal,
+        (None, None) => return,
+    };
+
+    // waits for the other threads writing to the stdout, unlike the flush on panic.
+    let _ = crate::stdout_writer().flush();
+    process::exit(status);
+}
+
+#[cfg(unix)]
+fn handle_signals() {
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::iterator::Signals;
+    use std::sync::Once;
+
+    static HANDLE: Once = Once::new();
+    HANDLE.call_once(|| {
+        let mut signals = Signals::new([SIGTERM, SIGINT])
+            .unwrap_or_else(|e| panic!("failed to handle the signals: {}", e));
+        thread::spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                trigger(Some(signal));
+            }
+        });
+    });
+}
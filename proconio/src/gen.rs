@@ -0,0 +1,875 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Generates random inputs for stress testing.
+//!
+//! `gen_input!` has the same syntax as `input!`, except that each value is annotated with the
+//! range (or other spec) after `in`.  Instead of reading, `GenSource` produces a random token
+//! according to the spec, and the token is read into the variable through `Readable` just like
+//! `input!`.  The generated tokens are also stored as the text of the input, so that the same
+//! input can be fed to your solution.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::gen::GenSource;
+//! use proconio::gen_input;
+//! use proconio::marker::Usize1;
+//!
+//! let mut generator = GenSource::new(42);
+//! gen_input! {
+//!     from &mut generator,
+//!     n: usize in 2..=5,
+//!     a: [i64 in -10..=10; n],
+//!     edges: [(Usize1 in 1..=n, Usize1 in 1..=n); n - 1],
+//! }
+//!
+//! assert!(a.iter().all(|&x| -10 <= x && x <= 10));
+//! assert!(edges.iter().all(|&(u, v)| u < n && v < n));
+//!
+//! // "n\na_1 ... a_n\nu_1 v_1\n...\n"
+//! let text = generator.text();
+//! assert_eq!(text.lines().count(), 1 + 1 + (n - 1));
+//! assert!(text.starts_with(&format!("{}\n", n)));
+//! ```
+//!
+//! Each top-level variable is placed on its own line.  The elements of an array are separated by a
+//! space, except that each element of an array of arrays or tuples is placed on its own line.
+//!
+//! Without `from`, a `GenSource` with a random seed is used and the generated text is written by
+//! `output!`.  Combined with `capture_output()`, you can get the text as a `String`.
+//!
+//! The spec is a value implementing `Spec`.  Integer ranges (`1..=n`, `0..n`), float ranges and
+//! `word()` are available.  The type of the value is independent of the spec, so `Usize1 in 1..=n`
+//! generates a 1-indexed token and reads it as a 0-indexed `usize`.
+//!
+//! To declare the input format once, use `input_spec!`.  It declares a struct of the values which
+//! is read with the specs checked, and generated by `gen_input!` with the same specs.  The struct
+//! also validates the test cases strictly by `validate::run()`.
+
+use crate::source::{BufRead, Checkpoint, Position, Source};
+use crate::write::Writable;
+use std::collections::hash_map::RandomState;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Range, RangeInclusive};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A small pseudo random number generator (xorshift64*).
+///
+/// This is not cryptographically secure, but good enough for generating test cases.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates `Rng` with the specified seed.
+    pub fn new(seed: u64) -> Rng {
+        // scramble the seed by splitmix64, since xorshift doesn't work with the zero state.
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Rng {
+            state: if z == 0 { 1 } else { z },
+        }
+    }
+
+    /// Generates a random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Generates a random integer in `low..=high`.
+    pub fn gen_range_inclusive(&mut self, low: i128, high: i128) -> i128 {
+        assert!(
+            low <= high,
+            "failed to generate a value: the range {}..={} is empty",
+            low,
+            high
+        );
+
+        let span = (high - low) as u128 + 1;
+        let random = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+        low + (random % span) as i128
+    }
+
+    /// Generates a random float in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// A spec of a generated token.
+pub trait Spec {
+    /// Generates a token.
+    fn generate(&self, rng: &mut Rng) -> String;
+
+    /// Checks that the token satisfies the spec, returning the reason if not.
+    ///
+    /// This is used by the types declared by `input_spec!` to validate the input.  The default
+    /// implementation accepts any token.
+    fn check(&self, token: &str) -> Result<(), String> {
+        let _ = token;
+        Ok(())
+    }
+}
+
+impl<P: Spec + ?Sized> Spec for &P {
+    fn generate(&self, rng: &mut Rng) -> String {
+        (**self).generate(rng)
+    }
+
+    fn check(&self, token: &str) -> Result<(), String> {
+        (**self).check(token)
+    }
+}
+
+fn check_range<T: PartialOrd + std::str::FromStr, P: std::ops::RangeBounds<T> + Debug>(
+    range: &P,
+    token: &str,
+) -> Result<(), String> {
+    match token.parse::<T>() {
+        Ok(value) if range.contains(&value) => Ok(()),
+        _ => Err(format!("`{}` is not in {:?}", token, range)),
+    }
+}
+
+macro_rules! impl_spec_for_integers {
+    ($($ty:ty)*) => {
+        $(
+            impl Spec for RangeInclusive<$ty> {
+                fn generate(&self, rng: &mut Rng) -> String {
+                    rng.gen_range_inclusive(*self.start() as i128, *self.end() as i128)
+                        .to_string()
+                }
+
+                fn check(&self, token: &str) -> Result<(), String> {
+                    check_range::<$ty, _>(self, token)
+                }
+            }
+
+            impl Spec for Range<$ty> {
+                fn generate(&self, rng: &mut Rng) -> String {
+                    rng.gen_range_inclusive(self.start as i128, self.end as i128 - 1)
+                        .to_string()
+                }
+
+                fn check(&self, token: &str) -> Result<(), String> {
+                    check_range::<$ty, _>(self, token)
+                }
+            }
+        )*
+    };
+}
+
+impl_spec_for_integers!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+impl Spec for Range<f64> {
+    fn generate(&self, rng: &mut Rng) -> String {
+        assert!(
+            self.start < self.end,
+            "failed to generate a value: the range {}..{} is empty",
+            self.start,
+            self.end
+        );
+        (self.start + (self.end - self.start) * rng.next_f64()).to_string()
+    }
+
+    fn check(&self, token: &str) -> Result<(), String> {
+        check_range::<f64, _>(self, token)
+    }
+}
+
+/// Word: a string made of the characters in the alphabet.
+///
+/// This is usually created by `word()`.
+#[derive(Debug, Clone)]
+pub struct Word {
+    alphabet: Vec<char>,
+    len: RangeInclusive<usize>,
+}
+
+/// Creates `Word` spec generating a string of the characters in `alphabet` whose length is in
+/// `len`.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::gen::{word, GenSource};
+/// use proconio::gen_input;
+/// use proconio::marker::Chars;
+///
+/// let mut generator = GenSource::new(0);
+/// gen_input! {
+///     from &mut generator,
+///     h: usize in 1..=3,
+///     w: usize in 1..=3,
+///     grid: [Chars in word(".#", w..=w); h],
+/// }
+///
+/// assert!(grid.iter().all(|row| row.len() == w));
+/// ```
+pub fn word(alphabet: &str, len: RangeInclusive<usize>) -> Word {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    assert!(
+        !alphabet.is_empty(),
+        "failed to create a word spec: the alphabet is empty"
+    );
+
+    Word { alphabet, len }
+}
+
+impl Spec for Word {
+    fn generate(&self, rng: &mut Rng) -> String {
+        let len = rng.gen_range_inclusive(*self.len.start() as i128, *self.len.end() as i128);
+        (0..len)
+            .map(|_| {
+                let idx = rng.gen_range_inclusive(0, self.alphabet.len() as i128 - 1);
+                self.alphabet[idx as usize]
+            })
+            .collect()
+    }
+
+    fn check(&self, token: &str) -> Result<(), String> {
+        let len = token.chars().count();
+        if !self.len.contains(&len) {
+            return Err(format!(
+                "the length of `{}` is not in {:?}",
+                token, self.len
+            ));
+        }
+        match token.chars().find(|c| !self.alphabet.contains(c)) {
+            Some(c) => Err(format!("`{}` in `{}` is not in the alphabet", c, token)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Source producing random tokens instead of reading.
+///
+/// Tokens are generated by `push()` in advance, and then read through `Source`.  `gen_input!` does
+/// this for each value.
+#[derive(Debug, Clone)]
+pub struct GenSource {
+    rng: Rng,
+    seed: u64,
+    tokens: VecDeque<String>,
+    current: String,
+    text: String,
+}
+
+impl GenSource {
+    /// Creates `GenSource` with the specified seed.  The same seed generates the same input.
+    pub fn new(seed: u64) -> GenSource {
+        GenSource {
+            rng: Rng::new(seed),
+            seed,
+            tokens: VecDeque::new(),
+            current: String::new(),
+            text: String::new(),
+        }
+    }
+
+    /// Creates `GenSource` with a random seed.
+    pub fn random() -> GenSource {
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
+        GenSource::new(hasher.finish())
+    }
+
+    /// Gets the seed.  Pass it to `new()` to generate the same input again.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Gets the random number generator.
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
+    /// Generates a token according to the spec.  The token is read by the next `next_token()`.
+    pub fn push<P: Spec + ?Sized>(&mut self, spec: &P) {
+        let token = spec.generate(&mut self.rng);
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push(' ');
+        }
+        self.text.push_str(&token);
+        self.tokens.push_back(token);
+    }
+
+    /// Writes `value` of `T` into the input, instead of generating a token by a spec.
+    ///
+    /// This is for the values made by your own code, such as a random tree.  The text written by
+    /// `Writable::write()` is also read through `Source`, splitted by whitespaces.
+    pub fn push_value<T: Writable + ?Sized>(&mut self, value: &T::Output) {
+        let start = self.text.len();
+        T::write(value, &mut self.text);
+        let tokens = self.text[start..].split_whitespace().map(String::from);
+        self.tokens.extend(tokens);
+    }
+
+    /// Ends the current line of the text.
+    pub fn new_line(&mut self) {
+        if !self.text.is_empty() && !self.text.ends_with('\n') {
+            self.text.push('\n');
+        }
+    }
+
+    /// Gets the text of the generated input.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Takes the text of the generated input, leaving it empty.
+    pub fn take_text(&mut self) -> String {
+        mem::take(&mut self.text)
+    }
+}
+
+impl Source<io::Empty> for GenSource {
+    fn next_token(&mut self) -> Option<&str> {
+        self.current = self.tokens.pop_front()?;
+        Some(&self.current)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.front().map(String::as_str)
+    }
+}
+
+/// Source checking the tokens against the specs while reading another source.
+///
+/// This has `push()` and `new_line()` as `GenSource` does, so that `gen_input!` can read the input
+/// with the specs: `push()` checks the next token instead of generating it.  The types declared by
+/// `input_spec!` read the input through this.
+pub struct SpecReader<'a, R, S> {
+    inner: &'a mut S,
+    _read: PhantomData<fn() -> R>,
+}
+
+impl<'a, R: BufRead, S: Source<R>> SpecReader<'a, R, S> {
+    /// Creates `SpecReader` reading `source`.
+    pub fn new(source: &'a mut S) -> SpecReader<'a, R, S> {
+        SpecReader {
+            inner: source,
+            _read: PhantomData,
+        }
+    }
+
+    /// Checks that the next token satisfies the spec.  Panics if not.
+    pub fn push<P: Spec + ?Sized>(&mut self, spec: &P) {
+        let token = match self.inner.peek_token() {
+            Some(token) => token,
+            // reading the token reports it.
+            None => return,
+        };
+        if let Err(reason) = spec.check(token) {
+            panic!("failed to read the input: {}", reason);
+        }
+    }
+
+    /// Does nothing, since the lines are not checked.
+    pub fn new_line(&mut self) {}
+}
+
+impl<R: BufRead, S: Source<R>> Source<R> for SpecReader<'_, R, S> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.inner.next_token_bytes()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn end_line(&mut self) {
+        self.inner.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.inner.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.inner.rewind(checkpoint)
+    }
+}
+
+/// Declares a struct of the input format, which both reads and generates the input.
+///
+/// The fields are written with the syntax of `gen_input!`.  `input_spec!` declares the struct
+/// having the fields of the values, and implements:
+///
+/// - `Readable` reading the input like `input!`, and checking each token against its spec.  The
+///   struct can be read by `input!` as is.
+/// - `generate()` generating a random input by `gen_input!` into the `GenSource`.
+/// - `Validate` checking the whitespaces and the end of the input as well, used by
+///   `validate::run()`.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::gen::GenSource;
+/// use proconio::input;
+/// use proconio::input_spec;
+/// use proconio::source::auto::AutoSource;
+///
+/// input_spec! {
+///     #[derive(Debug)]
+///     pub struct Input {
+///         n: usize in 1..=100_000,
+///         a: [i64 in -1_000_000_000..=1_000_000_000; n],
+///     }
+/// }
+///
+/// let source = AutoSource::from("3\n1 -2 3\n");
+/// input! {
+///     from source,
+///     input: Input,
+/// }
+/// assert_eq!(input.a, [1, -2, 3]);
+///
+/// let mut generator = GenSource::new(42);
+/// let input = Input::generate(&mut generator);
+/// assert_eq!(input.a.len(), input.n);
+/// assert!(generator.text().starts_with(&input.n.to_string()));
+/// ```
+///
+/// Reading a value out of its spec panics, such as `0` for `n` above.  The checks need
+/// `Source::peek_token()`, which most sources support.  In `stress::run()`, generate the input by
+/// `|generator| { Input::generate(generator); }`.
+///
+/// The fields have the same visibility as the struct.
+#[macro_export]
+macro_rules! input_spec {
+    // parse fields
+    (@fields [$($done:tt)*] @rest) => {
+        $crate::input_spec!(@done $($done)*);
+    };
+    (@fields [$($done:tt)*] @rest $field:ident: $($rest:tt)*) => {
+        $crate::input_spec!(@field [$($done)*] $field [] $($rest)*);
+    };
+    (@field [$($done:tt)*] $field:ident [$($kind:tt)*]) => {
+        $crate::input_spec!(@fields [$($done)* ($field [$($kind)*])] @rest);
+    };
+    (@field [$($done:tt)*] $field:ident [$($kind:tt)*] , $($rest:tt)*) => {
+        $crate::input_spec!(@fields [$($done)* ($field [$($kind)*])] @rest $($rest)*);
+    };
+    (@field [$($done:tt)*] $field:ident [$($kind:tt)*] $tt:tt $($rest:tt)*) => {
+        $crate::input_spec!(@field [$($done)*] $field [$($kind)* $tt] $($rest)*);
+    };
+
+    // declare the struct
+    (
+        @done
+        [$(#[$attr:meta])*] [$vis:vis] $name:ident
+        $(($field:ident [$($kind:tt)*]))*
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($vis $field: $crate::input_spec!(@type [$($kind)*]),)*
+        }
+
+        impl $name {
+            /// Generates a random input into `generator`, and returns its values.
+            #[allow(dead_code)]
+            $vis fn generate(generator: &mut $crate::gen::GenSource) -> $name {
+                $crate::gen_input! {
+                    from generator,
+                    $($field: $($kind)*,)*
+                }
+                $name { $($field),* }
+            }
+        }
+
+        impl $crate::__Readable for $name {
+            type Output = $name;
+            fn read<R: $crate::source::BufRead, S: $crate::source::Source<R>>(
+                source: &mut S,
+            ) -> $name {
+                let mut reader = $crate::gen::SpecReader::new(source);
+                $crate::gen_input! {
+                    from &mut reader,
+                    $($field: $($kind)*,)*
+                }
+                $name { $($field),* }
+            }
+        }
+
+        impl $crate::validate::Validate for $name {
+            fn validate(validator: &mut $crate::validate::Validator) {
+                $crate::gen_input! {
+                    from validator,
+                    $($field: $($kind)*,)*
+                }
+            }
+        }
+    };
+
+    // the type of the value
+    (@type [[$($kind:tt)*]]) => {
+        $crate::input_spec!(@array_type [] $($kind)*)
+    };
+    (@type [($($kind:tt)*)]) => {
+        $crate::input_spec!(@tuple_type [] [] $($kind)*)
+    };
+    (@type [$($kind:tt)*]) => {
+        $crate::input_spec!(@scalar_type [] $($kind)*)
+    };
+    (@array_type [$($kind:tt)*] ; $len:expr) => {
+        ::std::vec::Vec<$crate::input_spec!(@type [$($kind)*])>
+    };
+    (@array_type [$($kind:tt)*] $tt:tt $($rest:tt)*) => {
+        $crate::input_spec!(@array_type [$($kind)* $tt] $($rest)*)
+    };
+    (@tuple_type [$($done:tt)*] [$($kind:tt)+]) => {
+        $crate::input_spec!(@tuple_type [$($done)*] [$($kind)*] ,)
+    };
+    (@tuple_type [$($done:tt)*] []) => {
+        ($($done)*)
+    };
+    (@tuple_type [$($done:tt)*] [$($kind:tt)+] , $($rest:tt)*) => {
+        $crate::input_spec!(
+            @tuple_type [$($done)* $crate::input_spec!(@type [$($kind)*]),] [] $($rest)*
+        )
+    };
+    (@tuple_type [$($done:tt)*] [$($kind:tt)*] $tt:tt $($rest:tt)*) => {
+        $crate::input_spec!(@tuple_type [$($done)*] [$($kind)* $tt] $($rest)*)
+    };
+    (@scalar_type [$($ty:tt)*] in $spec:expr) => {
+        <$($ty)* as $crate::__Readable>::Output
+    };
+    (@scalar_type [$($ty:tt)*] $tt:tt $($rest:tt)*) => {
+        $crate::input_spec!(@scalar_type [$($ty)* $tt] $($rest)*)
+    };
+
+    ($(#[$attr:meta])* $vis:vis struct $name:ident { $($fields:tt)* }) => {
+        $crate::input_spec!(@fields [[$(#[$attr])*] [$vis] $name] @rest $($fields)*);
+    };
+}
+
+/// Generates random input with the syntax of `input!`.
+///
+/// basic syntax is:
+/// ```text
+/// gen_input! {
+///     from generator,               // optional: if you omitted, the text is written by output!.
+///     (mut) variable: type in spec, // the token is generated by `spec` and read as `type`.
+///     (mut) array: [type in spec; len],
+///     (mut) tuple: (type in spec, type in spec),
+///     ...
+/// }
+/// ```
+///
+/// See the document of [`gen`](gen/index.html) module for details.
+#[macro_export]
+macro_rules! gen_input {
+    // terminator
+    (@from [$gen:expr] @rest) => {};
+
+    // parse mutability
+    (@from [$gen:expr] @rest mut $($rest:tt)*) => {
+        $crate::gen_input! {
+            @from [$gen]
+            @mut [mut]
+            @rest $($rest)*
+        }
+    };
+    (@from [$gen:expr] @rest $($rest:tt)*) => {
+        $crate::gen_input! {
+            @from [$gen]
+            @mut []
+            @rest $($rest)*
+        }
+    };
+
+    // parse variable pattern
+    (@from [$gen:expr] @mut [$($mut:tt)?] @rest $var:tt: $($rest:tt)*) => {
+        $crate::gen_input! {
+            @from [$gen]
+            @mut [$($mut)*]
+            @var $var
+            @kind []
+            @rest $($rest)*
+        }
+    };
+
+    // parse kind
+    (@from [$gen:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest) => {
+        // the variables are often used only for generating the text.
+        #[allow(unused_variables)]
+        let $($mut)* $var = $crate::gen_input!(@gen [$gen] @kind [$($kind)*]);
+        $gen.new_line();
+    };
+    (@from [$gen:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest, $($rest:tt)*) => {
+        $crate::gen_input!(@from [$gen] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest);
+        $crate::gen_input!(@from [$gen] @rest $($rest)*);
+    };
+    (@from [$gen:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::gen_input!(@from [$gen] @mut [$($mut)*] @var $var @kind [$($kind)* $tt] @rest $($rest)*);
+    };
+
+    // generate a value
+    (@gen [$gen:expr] @kind [[$($kind:tt)*]]) => {
+        $crate::gen_input!(@array [$gen] @kind [] @rest $($kind)*)
+    };
+    (@gen [$gen:expr] @kind [($($kind:tt)*)]) => {
+        $crate::gen_input!(@tuple [$gen] @done [] @kind [] @rest $($kind)*)
+    };
+    (@gen [$gen:expr] @kind [$($kind:tt)*]) => {
+        $crate::gen_input!(@scalar [$gen] @ty [] @rest $($kind)*)
+    };
+
+    // array
+    (@array [$gen:expr] @kind [$($kind:tt)*] @rest ; $len:expr) => {{
+        let len: usize = $len;
+        (0..len)
+            .map(|_| {
+                let value = $crate::gen_input!(@gen [$gen] @kind [$($kind)*]);
+                $crate::gen_input!(@row [$gen] @kind [$($kind)*]);
+                value
+            })
+            .collect::<Vec<_>>()
+    }};
+    (@array [$gen:expr] @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::gen_input!(@array [$gen] @kind [$($kind)* $tt] @rest $($rest)*)
+    };
+
+    // each element of an array of arrays or tuples is placed on its own line.
+    (@row [$gen:expr] @kind [[$($kind:tt)*]]) => { $gen.new_line() };
+    (@row [$gen:expr] @kind [($($kind:tt)*)]) => { $gen.new_line() };
+    (@row [$gen:expr] @kind [$($kind:tt)*]) => { () };
+
+    // tuple
+    (@tuple [$gen:expr] @done [$($done:tt)*] @kind [$($kind:tt)+] @rest) => {
+        $crate::gen_input!(@tuple [$gen] @done [$($done)*] @kind [$($kind)*] @rest ,)
+    };
+    (@tuple [$gen:expr] @done [$($done:tt)*] @kind [] @rest) => {
+        ($($done)*)
+    };
+    (@tuple [$gen:expr] @done [$($done:tt)*] @kind [$($kind:tt)+] @rest , $($rest:tt)*) => {
+        $crate::gen_input!(
+            @tuple [$gen]
+            @done [$($done)* $crate::gen_input!(@gen [$gen] @kind [$($kind)*]),]
+            @kind []
+            @rest $($rest)*
+        )
+    };
+    (@tuple [$gen:expr] @done [$($done:tt)*] @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::gen_input!(@tuple [$gen] @done [$($done)*] @kind [$($kind)* $tt] @rest $($rest)*)
+    };
+
+    // scalar
+    (@scalar [$gen:expr] @ty [$($ty:tt)*] @rest in $spec:expr) => {{
+        $gen.push(&$spec);
+        <$($ty)* as $crate::__Readable>::read($gen)
+    }};
+    (@scalar [$gen:expr] @ty [$($ty:tt)*] @rest) => {
+        compile_error!(concat!(
+            "the spec of the generated value is missing: write like `",
+            stringify!($($ty)*),
+            " in 1..=10`"
+        ))
+    };
+    (@scalar [$gen:expr] @ty [$($ty:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::gen_input!(@scalar [$gen] @ty [$($ty)* $tt] @rest $($rest)*)
+    };
+
+    (from $gen:expr, $($rest:tt)*) => {
+        #[allow(unused_variables, unused_mut)]
+        let mut s = $gen;
+        $crate::gen_input! {
+            @from [&mut *s]
+            @rest $($rest)*
+        }
+    };
+    ($($rest:tt)*) => {
+        let mut s = $crate::gen::GenSource::random();
+        $crate::gen_input! {
+            @from [&mut s]
+            @rest $($rest)*
+        }
+        $crate::output!("{}", s.take_text());
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::{Chars, LenPrefixed, Usize1};
+    use crate::source::auto::AutoSource;
+    use crate::source::Readable;
+    use crate::{capture_output, input};
+
+    input_spec! {
+        #[derive(Debug, PartialEq)]
+        struct Graph {
+            n: usize in 2..=5,
+            name: Chars in word("ab", 1..=3),
+            edges: [(Usize1 in 1..=n, Usize1 in 1..=n); n - 1],
+        }
+    }
+
+    #[test]
+    fn input_spec_read() {
+        let source = AutoSource::from("3 ab\n1 2\n3 1\n");
+        input! {
+            from source,
+            graph: Graph,
+        }
+
+        assert_eq!(
+            graph,
+            Graph {
+                n: 3,
+                name: vec!['a', 'b'],
+                edges: vec![(0, 1), (2, 0)],
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the input: `4` is not in 1..=3")]
+    fn input_spec_out_of_range() {
+        let mut source = AutoSource::from("3 ab\n1 2\n4 1\n");
+        Graph::read(&mut source);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the input: `c` in `ac` is not in the alphabet")]
+    fn input_spec_out_of_alphabet() {
+        let mut source = AutoSource::from("2 ac\n1 2\n");
+        Graph::read(&mut source);
+    }
+
+    #[test]
+    fn input_spec_generate() {
+        for seed in 0..20 {
+            let mut generator = GenSource::new(seed);
+            let graph = Graph::generate(&mut generator);
+            let mut source = AutoSource::from(generator.text());
+            assert_eq!(Graph::read(&mut source), graph);
+        }
+    }
+
+    #[test]
+    fn push_value() {
+        let mut generator = GenSource::new(0);
+        generator.push(&(1..=1));
+        generator.push_value::<LenPrefixed<Usize1>>(&vec![0, 2]);
+        generator.new_line();
+        generator.push_value::<Chars>(&vec!['a', 'b']);
+        assert_eq!(generator.text(), "1 2 1 3\nab");
+
+        input! {
+            from &mut generator,
+            x: u32,
+            a: [Usize1],
+            s: Chars,
+        }
+        assert_eq!((x, a, s), (1, vec![0, 2], vec!['a', 'b']));
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        let generate = |seed| {
+            let mut generator = GenSource::new(seed);
+            gen_input! {
+                from &mut generator,
+                n: usize in 1..=10,
+                a: [u64 in 0..1_000_000_000; n],
+            }
+            assert_eq!(a.len(), n);
+            generator.take_text()
+        };
+
+        assert_eq!(generate(1), generate(1));
+        assert_ne!(generate(1), generate(2));
+    }
+
+    #[test]
+    fn layout() {
+        let mut generator = GenSource::new(0);
+        gen_input! {
+            from &mut generator,
+            n: usize in 3..=3,
+            grid: [[i32 in 7..=7; 2]; n],
+            s: Chars in word("a", 4..=4),
+            p: (Usize1 in 1..=1, f64 in 0.5..0.75),
+        }
+
+        assert_eq!(grid, vec![vec![7, 7]; 3]);
+        assert_eq!(s, ['a'; 4]);
+        assert_eq!(p.0, 0);
+        assert!(0.5 <= p.1 && p.1 < 0.75);
+
+        let mut lines = generator.text().lines();
+        assert_eq!(lines.next(), Some("3"));
+        assert_eq!(lines.next(), Some("7 7"));
+        assert_eq!(lines.next(), Some("7 7"));
+        assert_eq!(lines.next(), Some("7 7"));
+        assert_eq!(lines.next(), Some("aaaa"));
+        assert!(lines.next().unwrap().starts_with("1 0."));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn output_without_from() {
+        let output = capture_output(|| {
+            gen_input! {
+                n: i64 in -1..=-1,
+                mut a: [u8 in 5..6; 3],
+            }
+            a.push(0);
+            assert_eq!((n, a), (-1, vec![5, 5, 5, 0]));
+        });
+
+        assert_eq!(output, "-1\n5 5 5\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "the range 1..=0 is empty")]
+    fn empty_range() {
+        let mut generator = GenSource::new(0);
+        gen_input! {
+            from &mut generator,
+            n: usize in 1..1,
+        }
+        let _ = n;
+    }
+}
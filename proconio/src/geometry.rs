@@ -0,0 +1,159 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines `Point`, a readable coordinate pair.
+//!
+//! This module requires `geometry` feature enabled.  `Point<T>` is read from two tokens `x y`,
+//! and has basic vector operations so that you can use the read points right away.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::geometry::Point;
+//! use proconio::input;
+//! # let source = AutoSource::from("3  0 0  4 0  0 3");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     pts: [Point<i64>; n],
+//! }
+//!
+//! // twice the area of the triangle
+//! assert_eq!((pts[1] - pts[0]).cross(pts[2] - pts[0]), 12);
+//! ```
+
+use crate::source::{BufRead, Readable, Source};
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point (or a vector) on the plane.
+///
+/// As a `Readable`, `Point<T>` reads `x` and `y` by `T`.  Output of reading has type
+/// `Point<T::Output>`, so `Point<Usize1>` is read as `Point<usize>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    /// Creates a point.
+    pub fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Point<T> {
+    /// Dot product.
+    pub fn dot(self, other: Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Squared norm.  This is the same with `self.dot(self)`.
+    pub fn norm2(self) -> T {
+        self.dot(self)
+    }
+}
+
+impl<T: Copy + Sub<Output = T> + Mul<Output = T>> Point<T> {
+    /// Cross product, the z-component of the 3D cross product.  It is positive if `other` is in
+    /// the counterclockwise direction from `self`.
+    pub fn cross(self, other: Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl<T: Readable> Readable for Point<T> {
+    type Output = Point<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Point<T::Output> {
+        let x = T::read(source);
+        let y = T::read(source);
+        Point { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+    fn mul(self, k: T) -> Point<T> {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Point<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Point<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Point;
+    use crate::input;
+    use crate::marker::Usize1;
+    use crate::source::slice::SliceSource;
+
+    #[test]
+    fn input_points() {
+        let source = SliceSource::from("1 2 -3 4  5 6");
+        input! {
+            from source,
+            pts: [Point<i64>; 2],
+            index: Point<Usize1>,
+        }
+
+        assert_eq!(pts, [Point::new(1, 2), Point::new(-3, 4)]);
+        assert_eq!(index, Point::new(4, 5));
+    }
+
+    #[test]
+    fn operations() {
+        let a = Point::new(1, 2);
+        let mut b = Point::new(-3, 4);
+
+        assert_eq!(a + b, Point::new(-2, 6));
+        assert_eq!(a - b, Point::new(4, -2));
+        assert_eq!(-a, Point::new(-1, -2));
+        assert_eq!(a * 3, Point::new(3, 6));
+        assert_eq!(a.dot(b), 5);
+        assert_eq!(a.cross(b), 10);
+        assert_eq!(b.norm2(), 25);
+
+        b += a;
+        assert_eq!(b, Point::new(-2, 6));
+        b -= a;
+        assert_eq!(b, Point::new(-3, 4));
+    }
+}
@@ -0,0 +1,398 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Builds graphs from the edges while reading.
+//!
+//! This module requires `graph` feature enabled.  Most graph problems start with `m` edges given
+//! as pairs of 1-indexed vertices.  `Graph` is a modifier building the adjacency list from the
+//! array of edges, so that the graph is ready right after `input!`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::graph::Graph;
+//! use proconio::input;
+//! use proconio::marker::Usize1;
+//! # let source = AutoSource::from("3 2  1 2  2 3");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     m: usize,
+//!     g: [(Usize1, Usize1); m] as Graph::undirected(n),
+//! }
+//!
+//! assert_eq!(g, [vec![1], vec![0, 2], vec![1]]);
+//! ```
+//!
+//! The edges with weights, `(u, v, w)`, build the adjacency list of `(v, w)`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::graph::Graph;
+//! use proconio::input;
+//! use proconio::marker::Usize1;
+//! # let source = AutoSource::from("3 2  1 2 10  1 3 20");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     m: usize,
+//!     g: [(Usize1, Usize1, i64); m] as Graph::directed(n),
+//! }
+//!
+//! assert_eq!(g, [vec![(1, 10), (2, 20)], vec![], vec![]]);
+//! ```
+//!
+//! A rooted tree is often given by the parents `p_2 ... p_n` of the vertices other than the root
+//! `1`.  The array of the parents builds the tree in the same way; `directed` builds the lists of
+//! the children.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::graph::Graph;
+//! use proconio::input;
+//! use proconio::marker::Usize1;
+//! # let source = AutoSource::from("4  1 1 2");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     children: [Usize1; n - 1] as Graph::directed(n),
+//! }
+//!
+//! assert_eq!(children, [vec![1, 2], vec![3], vec![], vec![]]);
+//! ```
+//!
+//! For large graphs, `Graph::csr()` builds `GraphCsr` instead, which stores all edges in a single
+//! array in the compressed sparse row format.  Each adjacency list is a slice of it.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::graph::Graph;
+//! use proconio::input;
+//! use proconio::marker::Usize1;
+//! # let source = AutoSource::from("3 2  1 2  2 3");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     m: usize,
+//!     g: [(Usize1, Usize1); m] as Graph::undirected(n).csr(),
+//! }
+//!
+//! assert_eq!(g.len(), 3);
+//! assert_eq!(g[1], [0, 2]);
+//! ```
+
+use crate::modifier::Modifier;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Index;
+
+/// Graph: build the adjacency list of the graph with `n` vertices from the edges.  Output of
+/// reading has type `Vec<Vec<usize>>` for the edges `(usize, usize)`, `Vec<Vec<(usize, W)>>` for
+/// the weighted edges `(usize, usize, W)`.
+///
+/// The vertices are 0-indexed; read them by `Usize1` for the 1-indexed input.  For an undirected
+/// graph, each edge is added in both directions.  Vertices out of `0..n` cause panic.
+///
+/// The array of `usize` is taken as the parents of the vertices `1, 2, ..., n - 1` of a tree, and
+/// builds `Vec<Vec<usize>>`.  For a directed graph, the edges are from the parents to the
+/// children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Graph {
+    n: usize,
+    directed: bool,
+}
+
+impl Graph {
+    /// Creates `Graph` modifier building the directed graph with `n` vertices.
+    pub fn directed(n: usize) -> Graph {
+        Graph { n, directed: true }
+    }
+
+    /// Creates `Graph` modifier building the undirected graph with `n` vertices.
+    pub fn undirected(n: usize) -> Graph {
+        Graph { n, directed: false }
+    }
+
+    /// Creates `Csr` modifier building the same graph as `GraphCsr`.
+    pub fn csr(self) -> Csr {
+        Csr(self)
+    }
+
+    fn check(self, u: usize, v: usize) {
+        assert!(
+            u < self.n && v < self.n,
+            "failed to add the edge ({}, {}): the vertices must be in `0..{}`",
+            u,
+            v,
+            self.n
+        );
+    }
+
+    // the edges from the parents, where the `i`-th parent is of the vertex `i + 1`.
+    fn parent_edges<I: Iterator<Item = usize>>(
+        self,
+        parents: I,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        parents.enumerate().map(|(i, p)| (p, i + 1))
+    }
+
+    // adds the arcs `(u, target(v))` of the edge, and `(v, target(u))` for an undirected graph.
+    fn add_arcs<T, F: FnMut(usize) -> T>(
+        self,
+        arcs: &mut Vec<(usize, T)>,
+        u: usize,
+        v: usize,
+        mut target: F,
+    ) {
+        self.check(u, v);
+        if !self.directed {
+            arcs.push((v, target(u)));
+        }
+        arcs.push((u, target(v)));
+    }
+}
+
+impl Modifier<(usize, usize)> for Graph {
+    type Output = Vec<Vec<usize>>;
+    fn modify<I: Iterator<Item = (usize, usize)>>(self, items: I) -> Vec<Vec<usize>> {
+        let mut res = vec![Vec::new(); self.n];
+        for (u, v) in items {
+            self.check(u, v);
+            res[u].push(v);
+            if !self.directed {
+                res[v].push(u);
+            }
+        }
+        res
+    }
+}
+
+impl<W: Clone> Modifier<(usize, usize, W)> for Graph {
+    type Output = Vec<Vec<(usize, W)>>;
+    fn modify<I: Iterator<Item = (usize, usize, W)>>(self, items: I) -> Vec<Vec<(usize, W)>> {
+        let mut res = vec![Vec::new(); self.n];
+        for (u, v, w) in items {
+            self.check(u, v);
+            if !self.directed {
+                res[v].push((u, w.clone()));
+            }
+            res[u].push((v, w));
+        }
+        res
+    }
+}
+
+impl Modifier<usize> for Graph {
+    type Output = Vec<Vec<usize>>;
+    fn modify<I: Iterator<Item = usize>>(self, items: I) -> Vec<Vec<usize>> {
+        self.modify(self.parent_edges(items))
+    }
+}
+
+/// Csr: build the graph as `GraphCsr`.  Output of reading has type `GraphCsr<usize>` for the edges
+/// `(usize, usize)` and the parents `usize`, `GraphCsr<(usize, W)>` for the weighted edges
+/// `(usize, usize, W)`.
+///
+/// This is created by `Graph::csr()`.  See `Graph` for how the edges are added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Csr(Graph);
+
+impl Modifier<(usize, usize)> for Csr {
+    type Output = GraphCsr<usize>;
+    fn modify<I: Iterator<Item = (usize, usize)>>(self, items: I) -> GraphCsr<usize> {
+        let mut arcs = Vec::new();
+        for (u, v) in items {
+            self.0.add_arcs(&mut arcs, u, v, |x| x);
+        }
+        GraphCsr::new(self.0.n, arcs)
+    }
+}
+
+impl<W: Clone> Modifier<(usize, usize, W)> for Csr {
+    type Output = GraphCsr<(usize, W)>;
+    fn modify<I: Iterator<Item = (usize, usize, W)>>(self, items: I) -> GraphCsr<(usize, W)> {
+        let mut arcs = Vec::new();
+        for (u, v, w) in items {
+            self.0.add_arcs(&mut arcs, u, v, |x| (x, w.clone()));
+        }
+        GraphCsr::new(self.0.n, arcs)
+    }
+}
+
+impl Modifier<usize> for Csr {
+    type Output = GraphCsr<usize>;
+    fn modify<I: Iterator<Item = usize>>(self, items: I) -> GraphCsr<usize> {
+        self.modify(self.0.parent_edges(items))
+    }
+}
+
+/// A graph in the compressed sparse row format.
+///
+/// The adjacency lists are stored in a single array, and `g[u]` is the slice of the list of `u`.
+/// This is built by `Graph::csr()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphCsr<T> {
+    start: Vec<usize>,
+    targets: Vec<T>,
+}
+
+impl<T> GraphCsr<T> {
+    // builds from the arcs `(u, t)`, keeping the order of the arcs in each list.
+    fn new(n: usize, arcs: Vec<(usize, T)>) -> GraphCsr<T> {
+        let mut start = vec![0; n + 1];
+        for &(u, _) in &arcs {
+            start[u + 1] += 1;
+        }
+        for u in 0..n {
+            start[u + 1] += start[u];
+        }
+
+        let mut next = start.clone();
+        let mut placed: Vec<Option<T>> = (0..arcs.len()).map(|_| None).collect();
+        for (u, t) in arcs {
+            placed[next[u]] = Some(t);
+            next[u] += 1;
+        }
+        let targets = placed
+            .into_iter()
+            .map(|t| t.expect("every slot is filled by an arc"))
+            .collect();
+
+        GraphCsr { start, targets }
+    }
+
+    /// Gets the number of the vertices.
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    /// Check if there is no vertex.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of the arcs.  An undirected edge is counted twice, once for each direction.
+    pub fn num_arcs(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Gets the adjacency list of `u`.  This is the same with `&g[u]`.
+    pub fn neighbors(&self, u: usize) -> &[T] {
+        &self.targets[self.start[u]..self.start[u + 1]]
+    }
+}
+
+impl<T> Index<usize> for GraphCsr<T> {
+    type Output = [T];
+    fn index(&self, u: usize) -> &[T] {
+        self.neighbors(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Usize1;
+    use crate::source::slice::SliceSource;
+
+    #[test]
+    fn undirected_weighted() {
+        let source = SliceSource::from("3 3  1 2 a  2 2 b  3 1 c");
+        input! {
+            from source,
+            n: usize,
+            m: usize,
+            g: [(Usize1, Usize1, char); m] as Graph::undirected(n),
+        }
+
+        assert_eq!(
+            g,
+            [
+                vec![(1, 'a'), (2, 'c')],
+                vec![(0, 'a'), (1, 'b'), (1, 'b')],
+                vec![(0, 'c')],
+            ]
+        );
+    }
+
+    #[test]
+    fn directed() {
+        let source = SliceSource::from("0 0 0 1");
+        input! {
+            from source,
+            empty: [(usize, usize); 0] as Graph::directed(2),
+            g: [(usize, usize); 2] as Graph::directed(2),
+        }
+
+        assert_eq!(empty.len(), 2);
+        assert!(empty.iter().all(Vec::is_empty));
+        assert_eq!(g, [vec![0, 1], vec![]]);
+    }
+
+    #[test]
+    fn parents() {
+        let source = SliceSource::from("5  1 1 3 3");
+        input! {
+            from source,
+            n: usize,
+            tree: [Usize1; n - 1] as Graph::undirected(n),
+        }
+
+        assert_eq!(tree, [vec![1, 2], vec![0], vec![0, 3, 4], vec![2], vec![2]]);
+    }
+
+    #[test]
+    fn csr() {
+        let source = SliceSource::from("4 4  1 2 5  3 1 7  2 3 9  1 4 0  3  1 1");
+        input! {
+            from source,
+            n: usize,
+            m: usize,
+            edges: [(Usize1, Usize1, u8); m],
+            k: usize,
+            tree: [Usize1; k - 1] as Graph::directed(k).csr(),
+        }
+
+        let g = Graph::undirected(n).csr().modify(edges.iter().copied());
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.num_arcs(), 8);
+        assert_eq!(g[0], [(1, 5), (2, 7), (3, 0)]);
+        assert_eq!(g.neighbors(2), [(0, 7), (1, 9)]);
+
+        let lists = Graph::undirected(n).modify(edges.into_iter());
+        assert!((0..n).all(|u| g[u] == *lists[u]));
+
+        assert_eq!(tree.num_arcs(), 2);
+        assert_eq!(
+            (&tree[0], &tree[1], &tree[2]),
+            (&[1, 2][..], &[][..], &[][..])
+        );
+
+        let empty = Graph::directed(0)
+            .csr()
+            .modify(Vec::<(usize, usize)>::new().into_iter());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to add the edge (0, 2): the vertices must be in `0..2`")]
+    fn vertex_out_of_range() {
+        let source = SliceSource::from("1 3");
+        input! {
+            from source,
+            _g: [(Usize1, Usize1); 1] as Graph::undirected(2),
+        }
+    }
+}
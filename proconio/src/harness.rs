@@ -0,0 +1,294 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Helpers to run your solution against sample cases locally.
+//!
+//! `test_samples!` runs your solution over the pasted sample cases and checks its output.  During
+//! each run, `input!` without `from` reads the sample input instead of stdin, and the output
+//! written by `output!` and `outputln!` is captured instead of being printed.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::{input, outputln, test_samples};
+//!
+//! fn solve() {
+//!     input! {
+//!         n: usize,
+//!         a: [u64; n],
+//!     }
+//!     outputln!("{}", a.iter().sum::<u64>());
+//! }
+//!
+//! // usually this is written in `#[test]` function.
+//! test_samples! {
+//!     solve,
+//!     "3\n1 2 3\n" => "6\n",
+//!     "1\n100\n" => "100\n",
+//! }
+//! ```
+//!
+//! The outputs are compared token-wise by `memo::diff_tokens()`, so the difference of whitespaces
+//! is ignored.  If some of the samples fail, it panics with the input, the expected output and the
+//! actual output of the failed samples.
+//!
+//! The source and the sink are global, so the replacement is serialized among threads: tests using
+//! these helpers run one by one even if the test runner runs them in parallel.  Note that the
+//! output printed by `print!`, `println!` or `#[fastout]` is not captured.
+//!
+//! To check the output in your own way, `with_source()`, `with_sink()` and `capture_output()` are
+//! available, and `check` has the comparisons such as the one with the floating point error.  To
+//! run your solution against a local judge program, see `interactive`.
+
+use crate::memo;
+use crate::source::any::Erased;
+use crate::source::auto::AutoSource;
+use crate::source::Source;
+use crate::{stdin_source, stdout_sink, StdinSource, StdoutSink};
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::mem;
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::thread::{self, ThreadId};
+
+/// Replaces the source used by `input!` without `from` during `f`.
+///
+/// The original source (usually stdin) is restored after `f` returns or panics.  This can be
+/// nested, and the innermost source is used.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::harness::with_source;
+/// use proconio::input;
+/// use proconio::source::auto::AutoSource;
+///
+/// let sum = with_source(AutoSource::from("1 2"), || {
+///     input! {
+///         a: u32,
+///         b: u32,
+///     }
+///     a + b
+/// });
+/// assert_eq!(sum, 3);
+/// ```
+pub fn with_source<R, S, F, T>(source: S, f: F) -> T
+where
+    R: BufRead + 'static,
+    S: Source<R> + Send + 'static,
+    F: FnOnce() -> T,
+{
+    let _lock = HarnessLock::acquire();
+    let replaced = StdinSource::Replaced(Box::new(Erased::new(source)));
+    let previous = mem::replace(&mut *lock_stdin(), replaced);
+    let _restore = RestoreSource(Some(previous));
+
+    f()
+}
+
+// Captures the output of `output!` and `outputln!` during `f`.
+pub(crate) fn capture<F: FnOnce() -> T, T>(f: F) -> (T, String) {
+    let _lock = HarnessLock::acquire();
+    let previous = {
+        let mut sink = lock_stdout();
+        // write out the output so far, so that it doesn't get mixed with the captured one.
+        let _ = sink.flush();
+        mem::replace(&mut *sink, StdoutSink::Captured(Vec::new()))
+    };
+    let mut restore = RestoreSink(Some(previous));
+
+    let res = f();
+    let captured = match restore.restore() {
+        Some(StdoutSink::Captured(captured)) => captured,
+        _ => unreachable!(concat!(
+            "the captured stdout was replaced.  ",
+            "This is a bug in `proconio`.  ",
+            "Please report this issue from ",
+            "<https://github.com/statiolake/proconio-rs/issues>."
+        )),
+    };
+    (res, String::from_utf8_lossy(&captured).into_owned())
+}
+
+/// Replaces the sink used by `output!` and `outputln!` without `to` during `f`.
+///
+/// The output so far is flushed before the replacement.  The original sink (usually stdout) is
+/// restored after `f` returns or panics, and then `sink` is flushed and dropped.  `input!` without
+/// `from` flushes the sink before reading, as `input_interactive!` does.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::harness::with_sink;
+/// use proconio::outputln;
+/// use std::sync::{Arc, Mutex};
+///
+/// # struct Shared(Arc<Mutex<Vec<u8>>>);
+/// # impl std::io::Write for Shared {
+/// #     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+/// #         self.0.lock().unwrap().write(buf)
+/// #     }
+/// #     fn flush(&mut self) -> std::io::Result<()> {
+/// #         Ok(())
+/// #     }
+/// # }
+/// let buf = Arc::new(Mutex::new(Vec::new()));
+/// with_sink(Shared(buf.clone()), || {
+///     outputln!("{} {}", 1, 2);
+/// });
+/// assert_eq!(*buf.lock().unwrap(), b"1 2\n");
+/// ```
+pub fn with_sink<W, F, T>(sink: W, f: F) -> T
+where
+    W: Write + Send + 'static,
+    F: FnOnce() -> T,
+{
+    let _lock = HarnessLock::acquire();
+    let previous = {
+        let mut current = lock_stdout();
+        let _ = current.flush();
+        mem::replace(&mut *current, StdoutSink::Replaced(Box::new(sink)))
+    };
+    let _restore = RestoreSink(Some(previous));
+
+    f()
+}
+
+// Runs `solve` reading `input` and returns the output.
+pub(crate) fn run_with_input<F: FnMut()>(solve: &mut F, input: &str) -> String {
+    let source = AutoSource::new(BufReader::new(Cursor::new(input.as_bytes().to_vec())));
+    let ((), output) = with_source(source, || capture(solve));
+    output
+}
+
+#[doc(hidden)]
+pub fn __test_samples<F: FnMut()>(mut solve: F, samples: &[(&str, &str)]) {
+    let mut failures = Vec::new();
+    for (idx, &(input, expected)) in samples.iter().enumerate() {
+        let output = run_with_input(&mut solve, input);
+
+        if let Some(mismatch) = memo::diff_tokens(expected, &output) {
+            failures.push(format!(
+                concat!(
+                    "sample #{} failed: {} (expected -> actual)\n",
+                    "[input]\n{}\n[expected]\n{}\n[actual]\n{}"
+                ),
+                idx + 1,
+                mismatch,
+                input.trim_end(),
+                expected.trim_end(),
+                output.trim_end(),
+            ));
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "{} of {} samples failed\n\n{}",
+            failures.len(),
+            samples.len(),
+            failures.join("\n\n")
+        );
+    }
+}
+
+/// Runs the solution over the sample cases and checks the output.
+///
+/// basic syntax is:
+/// ```text
+/// test_samples! {
+///     solve,                 // the function (or closure) to run.
+///     "input" => "expected", // sample cases.
+///     ...
+/// }
+/// ```
+///
+/// See the document of [`harness`](harness/index.html) module for details.
+#[macro_export]
+macro_rules! test_samples {
+    ($solve:expr, $($input:expr => $expected:expr),+ $(,)?) => {
+        $crate::harness::__test_samples($solve, &[$(($input, $expected)),+])
+    };
+}
+
+// The source and the sink may be poisoned by a panic of the previous test case; that's fine since
+// they are replaced anyway.
+fn lock_stdin() -> std::sync::MutexGuard<'static, StdinSource<BufReader<std::io::Stdin>>> {
+    stdin_source()
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+fn lock_stdout() -> std::sync::MutexGuard<'static, StdoutSink> {
+    stdout_sink().lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct RestoreSource(Option<StdinSource<BufReader<std::io::Stdin>>>);
+
+impl Drop for RestoreSource {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            *lock_stdin() = previous;
+        }
+    }
+}
+
+struct RestoreSink(Option<StdoutSink>);
+
+impl RestoreSink {
+    // Restores the previous sink and returns the replaced one.
+    fn restore(&mut self) -> Option<StdoutSink> {
+        let previous = self.0.take()?;
+        Some(mem::replace(&mut *lock_stdout(), previous))
+    }
+}
+
+impl Drop for RestoreSink {
+    fn drop(&mut self) {
+        if let Some(mut replaced) = self.restore() {
+            let _ = replaced.flush();
+        }
+    }
+}
+
+// Serializes the replacement of the global source and sink among threads, since tests run in
+// parallel.  The same thread can acquire it again, so that the replacements can be nested.
+struct HarnessLock;
+
+static OWNER: Mutex<Option<(ThreadId, usize)>> = Mutex::new(None);
+static RELEASED: Condvar = Condvar::new();
+
+impl HarnessLock {
+    fn acquire() -> HarnessLock {
+        let current = thread::current().id();
+        let mut owner = OWNER.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            match &mut *owner {
+                Some((id, depth)) if *id == current => {
+                    *depth += 1;
+                    break;
+                }
+                Some(_) => owner = RELEASED.wait(owner).unwrap_or_else(PoisonError::into_inner),
+                None => {
+                    *owner = Some((current, 1));
+                    break;
+                }
+            }
+        }
+
+        HarnessLock
+    }
+}
+
+impl Drop for HarnessLock {
+    fn drop(&mut self) {
+        let mut owner = OWNER.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, depth)) = &mut *owner {
+            *depth -= 1;
+            if *depth == 0 {
+                *owner = None;
+                RELEASED.notify_one();
+            }
+        }
+    }
+}
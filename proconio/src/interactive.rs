@@ -0,0 +1,209 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Helpers to test interactive solutions against a local judge program.
+//!
+//! `spawn_judge()` spawns the judge and returns a pair of a source reading the stdout of the
+//! judge and a sink writing to its stdin, which can be used by `from` of `input!` and `to` of
+//! `output!` respectively.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::interactive;
+//! use proconio::{input, outputln};
+//! use std::process::Command;
+//!
+//! let (mut source, mut sink) = interactive::spawn_judge(&mut Command::new("./judge")).unwrap();
+//! input! {
+//!     from &mut source,
+//!     n: u32,
+//! }
+//! outputln!(to sink, "? {}", n / 2); // flushed at the newline.
+//! let status = sink.wait().unwrap();
+//! ```
+//!
+//! To run the solution written with `input!` and `output!` without `from` and `to` as is, use
+//! `with_judge()`.  It replaces the global source and sink during the closure, just like
+//! `harness::with_source()` and `harness::with_sink()`.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::interactive;
+//! use proconio::{input, outputln};
+//! use std::process::Command;
+//!
+//! fn solve() {
+//!     input! {
+//!         n: u32,
+//!     }
+//!     outputln!("? {}", n / 2);
+//! }
+//!
+//! let ((), status) = interactive::with_judge(&mut Command::new("./judge"), solve).unwrap();
+//! assert!(status.success());
+//! ```
+
+use crate::harness;
+use crate::source::line::LineSource;
+use crate::source::{Position, Source};
+use std::io::{self, BufReader, LineWriter, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
+
+/// Source reading the stdout of the judge line by line.
+pub struct ChildSource {
+    inner: LineSource<BufReader<ChildStdout>>,
+}
+
+impl ChildSource {
+    fn new(stdout: ChildStdout) -> ChildSource {
+        ChildSource {
+            inner: LineSource::new(BufReader::new(stdout)),
+        }
+    }
+}
+
+impl Source<BufReader<ChildStdout>> for ChildSource {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+}
+
+/// Sink writing to the stdin of the judge, flushing at every newline.
+///
+/// This owns the judge process.  Call `wait()` to close the stdin of the judge and wait for it to
+/// exit.
+pub struct ChildSink {
+    writer: LineWriter<ChildStdin>,
+    child: Child,
+}
+
+impl ChildSink {
+    /// Closes the stdin of the judge and waits for it to exit.
+    pub fn wait(self) -> io::Result<ExitStatus> {
+        let ChildSink { writer, mut child } = self;
+        // dropping the writer flushes the output and closes the pipe.
+        drop(writer);
+        child.wait()
+    }
+}
+
+impl Write for ChildSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Spawns the judge with piped stdin and stdout, and returns the source and the sink connected to
+/// it.
+///
+/// The stderr of the judge is inherited, so the debug output of the judge is shown as is.
+pub fn spawn_judge(cmd: &mut Command) -> io::Result<(ChildSource, ChildSink)> {
+    let mut child = cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+    let stdin = child.stdin.take().expect(concat!(
+        "the stdin of the judge is not piped.  ",
+        "This is a bug in `proconio`.  ",
+        "Please report this issue from ",
+        "<https://github.com/statiolake/proconio-rs/issues>."
+    ));
+    let stdout = child.stdout.take().expect(concat!(
+        "the stdout of the judge is not piped.  ",
+        "This is a bug in `proconio`.  ",
+        "Please report this issue from ",
+        "<https://github.com/statiolake/proconio-rs/issues>."
+    ));
+
+    let sink = ChildSink {
+        writer: LineWriter::new(stdin),
+        child,
+    };
+    Ok((ChildSource::new(stdout), sink))
+}
+
+/// Spawns the judge and runs `f` with `input!` and `output!` connected to it.
+///
+/// After `f` returns, the stdin of the judge is closed and the exit status of the judge is
+/// returned along with the result of `f`.  The output is flushed before `input!` reads the
+/// next token, so there is no need to flush it manually.
+pub fn with_judge<F, T>(cmd: &mut Command, f: F) -> io::Result<(T, ExitStatus)>
+where
+    F: FnOnce() -> T,
+{
+    let (source, ChildSink { writer, mut child }) = spawn_judge(cmd)?;
+
+    // the writer is dropped when `with_sink()` returns, which closes the stdin of the judge.
+    let res = harness::with_source(source, || harness::with_sink(writer, f));
+    let status = child.wait()?;
+    Ok((res, status))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{input, outputln};
+
+    const JUDGE: &str =
+        r#"echo 21; read x; if [ "$x" = 42 ]; then echo AC; else echo WA; exit 1; fi"#;
+
+    #[test]
+    fn interact_with_sink() {
+        let (mut source, mut sink) = spawn_judge(Command::new("sh").arg("-c").arg(JUDGE)).unwrap();
+        input! {
+            from &mut source,
+            n: u32,
+        }
+        outputln!(to sink, "{}", n * 2);
+        input! {
+            from &mut source,
+            verdict: String,
+        }
+
+        assert_eq!(verdict, "AC");
+        assert!(sink.wait().unwrap().success());
+    }
+
+    #[test]
+    fn interact_with_judge() {
+        let (verdict, status) = with_judge(Command::new("sh").arg("-c").arg(JUDGE), || {
+            input! {
+                n: u32,
+            }
+            outputln!("{}", n * 2);
+            input! {
+                verdict: String,
+            }
+            verdict
+        })
+        .unwrap();
+
+        assert_eq!(verdict, "AC");
+        assert!(status.success());
+    }
+}
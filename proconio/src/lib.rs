@@ -6,12 +6,15 @@
 // distributed except according to those terms.
 
 #![allow(clippy::needless_doctest_main, clippy::print_literal)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! Easy IO library for competitive programming.
 //!
 //! `proconio` provides an easy way to read values from stdin (or other source).  The main is
 //! `input!` macro.
 //!
+//! The minimum supported Rust version is 1.67.0.
+//!
 //! # Examples
 //!
 //! The macro's user interface is basically the same with [tanakh's input
@@ -58,6 +61,66 @@
 //! # assert_eq!(m, 86);
 //! ```
 //!
+//! A tuple can be destructured into separate variables by writing a tuple pattern on the left-hand
+//! side.  `mut` can be put on individual names.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! # let source = AutoSource::from("1 2 3 4");
+//!
+//! input! {
+//! #   from source,
+//!     (a, mut b, (c, d)): (i64, i64, (u8, u8)),
+//! }
+//!
+//! b += a; // OK: b is mutable
+//! # assert_eq!((a, b, c, d), (1, 3, 3, 4));
+//! ```
+//!
+//! Irrelevant values can be skipped by `_`.  The tokens are consumed, but no variable is declared.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! # let source = AutoSource::from("3 10 20 30 1 2 100");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     _: [i64; n],        // skip the whole array.
+//!     (u, v, _): (usize, usize, i64), // skip the weight.
+//! }
+//! # assert_eq!((u, v), (1, 2));
+//! ```
+//!
+//! If you don't need even the values, `skip(n)` skips the next `n` tokens without parsing them,
+//! and `skip_line` skips the next token and the rest of its line.  These are
+//! `Source::skip_tokens()` and `Source::skip_line()`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! # let source = AutoSource::from("3 1 2 3\nthis line can be ignored\n42");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     _: skip(n),
+//!     _: skip_line,
+//!     answer: u32,
+//! }
+//! # assert_eq!(answer, 42);
+//! ```
+//!
+//! Conversely, `end_line` checks that the values read since the previous `end_line` came from
+//! exactly one line and nothing is left in it.  This catches a wrong format which would make the
+//! values silently spill over the next line.  Only `LineSource` checks it, so `AutoSource` checks
+//! it in debug build and does nothing in release build.
+//!
 //! You can read an array or a matrix like this:
 //!
 //! ```
@@ -83,6 +146,9 @@
 //! # );
 //! ```
 //!
+//! The length can be any expression of the integer, such as `[[i32; 2 * n]; m - 1]` or
+//! `[i32; a.len()]`.
+//!
 //! If the first input is the length of the array, you can omit the length.  This is the only way
 //! to read jagged array (an array of arrays of which the member arrays can be of different sizes)
 //! at once.  (Of course you can use `input!` multiple times in for-loop to read such an array
@@ -111,6 +177,45 @@
 //! );
 //! ```
 //!
+//! If the lengths of the rows are given separately, write `i in n` in place of the length to name
+//! the index of the row.  The index can be used in the type of the row, such as its length.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! # let source = AutoSource::from("3 2 0 1  1 2  3\n1\n2 3");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     k: [usize; n],
+//!     a: [[i32; k[i]]; i in n],
+//!     b: [[i32; i + 1]; i in 2], // a triangle.
+//! }
+//! assert_eq!(a, vec![vec![1, 2], vec![], vec![3]]);
+//! assert_eq!(b, vec![vec![1], vec![2, 3]]);
+//! ```
+//!
+//! If the array is terminated by a sentinel value instead of its length, write `until: sentinel`
+//! in place of the length.  The values are read until the sentinel appears, which is consumed but
+//! not included in the array.  The `:` tells it from a length using a variable named `until`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! # let source = AutoSource::from("3 1 4 1 -1\n2 7 0 0");
+//!
+//! input! {
+//! #   from source,
+//!     a: [i64; until: -1],
+//!     b: [(u32, u32); until: (0, 0)], // the sentinel is compared with the read value.
+//! }
+//! assert_eq!(a, [3, 1, 4, 1]);
+//! assert_eq!(b, [(2, 7)]);
+//! ```
+//!
 //! Strings can be read as `Vec<u8>` or `Vec<char>`.  Use `Bytes` and `Chars` to do so:
 //!
 //! ```
@@ -133,6 +238,27 @@
 //! assert_eq!(bytes, b"bytes");
 //! ```
 //!
+//! These read a token, so the spaces are never included.  When the spaces are meaningful, as in a
+//! grid where a space is a cell, use `LineBytes` (or `Line` for `String`) to read an entire line
+//! as is.  After the tokens of a line are read, they read the next line:
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! use proconio::marker::LineBytes;
+//! # let source = AutoSource::from("2 3\n# #\n.. \n");
+//!
+//! input! {
+//! #     from source,
+//!     h: usize,
+//!     w: usize,
+//!     grid: [LineBytes; h],
+//! }
+//!
+//! assert_eq!(grid, [b"# #", b".. "]);
+//! ```
+//!
 //! You can read tuples:
 //!
 //! ```
@@ -232,6 +358,36 @@
 //! trait.  This trait doesn't require the output type to be the same with the implementor.
 //! `Usize1` is implementing `Readable` trait, and there the type of read value is defined as
 //! `usize`.  You can implement `Readable` for your own type to read values in customized way.
+//! The combinators in `read` module, such as `read_map()`, make such an implementation one line.
+//! Its counterpart `write::Writable` writes a value back to the input text, to check your
+//! implementation by `write::assert_roundtrip()`.
+//!
+//! A type containing commas, like a generic type with multiple parameters, cannot be written as
+//! is, since commas separate the variables.  Put such a type in braces: `x: {MyPair<u32, Usize1>}`.
+//! It can also be used as an element of arrays and tuples.  In braces, `[T; N]` with a constant
+//! length is read as a fixed-size array `[T; N]` rather than `Vec<T>`: `p: {[i64; 3]}`.
+//!
+//! An array can be followed by `as` and a modifier.  Modifiers receive the elements of the array
+//! as they are read and build the value stored in the variable.  For example, `Sorted` sorts the
+//! array so that you can declare the intent in `input!` itself.  See `modifier` module for the
+//! list of modifiers.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! use proconio::modifier::Sorted;
+//! # let mut source = AutoSource::from("5 3 1 4 1 5");
+//!
+//! input! {
+//! #   from &mut source,
+//!     n: usize,
+//!     a: [u32; n] as Sorted,
+//! }
+//!
+//! // if you enter "5 3 1 4 1 5", the sorted array is stored.
+//! assert_eq!(a, [1, 1, 3, 4, 5]);
+//! ```
 //!
 //! Finally, you can make your own types `Readable` using `#[derive_readable]` attribute.  Types
 //! used in the struct are automatically translated to their output types, so a member declared as
@@ -295,6 +451,75 @@
 //! # }
 //! ```
 //!
+//! ## Reading a file instead of stdin
+//!
+//! When the environment variable `PROCONIO_INPUT` is set, `input!` without `from` reads the file
+//! at the path instead of stdin.  `PROCONIO_INPUT_TEXT` gives the input itself in the same way.
+//! This is handy where the shell redirection is not available, like the run configuration of an
+//! IDE:
+//!
+//! ```sh
+//! PROCONIO_INPUT=sample-1.txt cargo run
+//! ```
+//!
+//! `PROCONIO_INPUT` takes precedence over `PROCONIO_INPUT_TEXT`.  The variables are checked once,
+//! when stdin is read for the first time.
+//!
+//! Conversely, `PROCONIO_RECORD=path` saves everything read from stdin into the file, so that a
+//! failing run, even an interactive one, can be replayed exactly by `PROCONIO_INPUT=path`.  See
+//! `record_stdin()` to do it in the code.  To keep the input and output of each run for comparing
+//! them between the versions of your solution, see `memo`.
+//!
+//! # `output!`
+//!
+//! `output!` and `outputln!` are the counterparts of `input!`.  They are used in the same way as
+//! `print!` and `println!`, but the output is buffered, which makes your output much faster.
+//! Since it is buffered, you must call `flush_output()` at the end of your program, or keep the
+//! guard returned by `flush_on_exit()` alive until the end of your `main()`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::{flush_output, output, outputln};
+//!
+//! fn main() {
+//!     output!("{}{}, ", 'h', "ello"); // "hello"       (no newline)
+//!     outputln!("{}!", "world");      // "world!\n"
+//!     outputln!(fixed(3), 1.0 / 3.0); // "0.333\n"
+//!     flush_output();
+//! }
+//! ```
+//!
+//! Unlike `#[fastout]`, these macros can be used anywhere, including closures and other functions.
+//! Like `from` of `input!`, `to sink,` at the beginning changes the destination; see `sink` module.
+//! Do not mix them with `print!` and `println!`; the order of the output may differ.
+//!
+//! # `#[proconio::main]`
+//!
+//! `#[proconio::main]` attribute bundles `#[fastout]` and `flush_on_exit()`, and optionally selects
+//! the interactive mode or runs your `main()` in a thread having extended size of stack.  See the
+//! document of `proconio_derive::main` for details.  This attribute requires `derive` feature
+//! enabled, too.
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::{input, outputln};
+//!
+//! #[proconio::main(stack = "256MB")]
+//! fn main() {
+//! #   let source = AutoSource::from("3");
+//!     input! {
+//! #       from source,
+//!         n: usize,
+//!     }
+//!
+//!     outputln!("{}", n);
+//! }
+//! # }
+//! ```
+//!
 //! # `#[fastout]`
 //!
 //! If you import `proconio::fastout`, you can use `#[fastout]` attribute.  Adding this attribute
@@ -509,61 +734,592 @@
 #[cfg(feature = "derive")]
 pub use proconio_derive::*;
 
+#[cfg(feature = "std")]
+pub mod ahc;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "bigint")]
+pub mod bigint;
+#[cfg(feature = "std")]
+pub mod check;
+#[cfg(feature = "complex")]
+pub mod complex;
+#[cfg(feature = "std")]
+pub mod debug;
+#[cfg(feature = "dsu")]
+pub mod dsu;
+#[cfg(feature = "emergency")]
+pub mod emergency;
+#[cfg(feature = "std")]
+pub mod gen;
+#[cfg(feature = "geometry")]
+pub mod geometry;
+#[cfg(feature = "graph")]
+pub mod graph;
+#[cfg(feature = "std")]
+pub mod harness;
+#[cfg(feature = "std")]
+pub mod interactive;
 pub mod marker;
+#[cfg(feature = "std")]
+pub mod memo;
+pub mod modifier;
+#[cfg(feature = "std")]
+pub mod out;
+#[cfg(feature = "parallel")]
+pub mod par;
+pub mod read;
+#[cfg(feature = "std")]
+pub mod sink;
 pub mod source;
+#[cfg(feature = "std")]
+pub mod stress;
+#[cfg(feature = "std")]
+pub mod timer;
+#[cfg(feature = "std")]
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod write;
 
-use crate::source::{auto::AutoSource, line::LineSource};
+#[cfg(feature = "std")]
+use crate::source::{auto::AutoSource, bytes::ByteSource, line::LineSource};
+#[cfg(feature = "std")]
 use once_cell::sync::OnceCell;
-use std::io::{BufReader, Stdin};
+#[cfg(feature = "std")]
+use std::io::{BufReader, BufWriter, Stderr, Stdin, Stdout, Write};
+#[cfg(feature = "std")]
 use std::{
     io::{self, BufRead},
-    sync::Mutex,
+    mem,
+    sync::atomic::{AtomicBool, Ordering},
+    sync::{Mutex, MutexGuard, Once, PoisonError, TryLockError},
 };
 
+extern crate alloc;
+
 // Prepares a short path to `Readable` to enables rust-analyzer to infer `Readable::Output`.
 #[doc(hidden)]
 pub use crate::source::Readable as __Readable;
 
+#[doc(hidden)]
+pub use crate::source::TryReadable as __TryReadable;
+
+// `Vec` is not in the prelude of `no_std` crates.
+#[doc(hidden)]
+pub use alloc::vec::Vec as __Vec;
+
+// Runs `read` returning `Result`, to use `?` in it, for the tuples of `try_input!`.
+#[doc(hidden)]
+pub fn __try_with<T, F>(read: F) -> Result<T, source::ReadError>
+where
+    F: FnOnce() -> Result<T, source::ReadError>,
+{
+    read()
+}
+
+// Reads `len` values by `read` for the arrays of `try_input!`.  The length may be broken, so the
+// vector grows as the values are read instead of being allocated for `len` at first.
+#[doc(hidden)]
+pub fn __try_vec<T, F>(len: usize, mut read: F) -> Result<__Vec<T>, source::ReadError>
+where
+    F: FnMut() -> Result<T, source::ReadError>,
+{
+    let mut values = __Vec::new();
+    for _ in 0..len {
+        values.push(read()?);
+    }
+    Ok(values)
+}
+
+// Reads the values by `read` until the sentinel, for `[T; until sentinel]`.
+#[doc(hidden)]
+pub fn __until<T, U, F>(sentinel: U, mut read: F) -> impl Iterator<Item = T>
+where
+    T: PartialEq<U>,
+    F: FnMut() -> T,
+{
+    core::iter::from_fn(move || {
+        let value = read();
+        if value == sentinel {
+            None
+        } else {
+            Some(value)
+        }
+    })
+}
+
+#[cfg(feature = "std")]
 pub enum StdinSource<R: BufRead> {
     Normal(AutoSource<R>),      // for input!
+    Bytes(ByteSource<R>),       // for input! after read_stdin_as_bytes()
     Interactive(LineSource<R>), // for for input_interactive!
     Unknown(LineSource<R>),     // for is_stdin_empty() without input! or input_interactive!
+    // for harness::with_source()
+    Replaced(Box<dyn source::any::ErasedSource + Send>),
+    // for the input given by `INPUT_VAR` or `INPUT_TEXT_VAR`, `from_args()` and the recorded
+    // stdin other than `input_interactive!`, which doesn't flush the output as `Replaced`.
+    Given(Box<dyn source::any::ErasedSource + Send>),
+    // for the stdin not opened yet, left by harness::with_source(); opened by the first reader.
+    Unopened,
 }
 
+#[cfg(feature = "std")]
 impl<R: BufRead> source::Source<R> for StdinSource<R> {
     fn next_token(&mut self) -> Option<&str> {
         match self {
             StdinSource::Normal(source) => source.next_token(),
-            StdinSource::Interactive(source) => source.next_token(),
+            StdinSource::Bytes(source) => source.next_token(),
+            StdinSource::Interactive(source) => {
+                try_flush_output();
+                source.next_token()
+            }
             StdinSource::Unknown(source) => source.next_token(),
+            StdinSource::Replaced(source) => {
+                // the replaced source may be an interactive judge.
+                try_flush_output();
+                source.next_token()
+            }
+            StdinSource::Given(source) => source.next_token(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        match self {
+            StdinSource::Bytes(source) => source.next_token_bytes(),
+            StdinSource::Replaced(source) => {
+                try_flush_output();
+                source.next_token_bytes()
+            }
+            StdinSource::Given(source) => source.next_token_bytes(),
+            _ => self.next_token().map(str::as_bytes),
         }
     }
 
     fn is_empty(&mut self) -> bool {
         match self {
             StdinSource::Normal(source) => source.is_empty(),
-            StdinSource::Interactive(source) => source.is_empty(),
+            StdinSource::Bytes(source) => source.is_empty(),
+            StdinSource::Interactive(source) => {
+                try_flush_output();
+                source.is_empty()
+            }
             StdinSource::Unknown(source) => source.is_empty(),
+            StdinSource::Replaced(source) => {
+                try_flush_output();
+                source.is_empty()
+            }
+            StdinSource::Given(source) => source.is_empty(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        match self {
+            StdinSource::Normal(source) => source.peek_token(),
+            StdinSource::Bytes(source) => source.peek_token(),
+            StdinSource::Interactive(source) => {
+                try_flush_output();
+                source.peek_token()
+            }
+            StdinSource::Unknown(source) => source.peek_token(),
+            StdinSource::Replaced(source) => {
+                try_flush_output();
+                source.peek_token()
+            }
+            StdinSource::Given(source) => source.peek_token(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn skip_line(&mut self) {
+        match self {
+            StdinSource::Normal(source) => source.skip_line(),
+            StdinSource::Bytes(source) => source.skip_line(),
+            StdinSource::Interactive(source) => {
+                try_flush_output();
+                source.skip_line()
+            }
+            StdinSource::Unknown(source) => source.skip_line(),
+            StdinSource::Replaced(source) => {
+                try_flush_output();
+                source.skip_line()
+            }
+            StdinSource::Given(source) => source.skip_line(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn end_line(&mut self) {
+        match self {
+            StdinSource::Normal(source) => source.end_line(),
+            StdinSource::Bytes(source) => source.end_line(),
+            StdinSource::Interactive(source) => source.end_line(),
+            StdinSource::Unknown(source) => source.end_line(),
+            StdinSource::Replaced(source) => source.end_line(),
+            StdinSource::Given(source) => source.end_line(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        match self {
+            StdinSource::Normal(source) => source.next_line(),
+            StdinSource::Bytes(source) => source.next_line(),
+            StdinSource::Interactive(source) => {
+                try_flush_output();
+                source.next_line()
+            }
+            StdinSource::Unknown(source) => source.next_line(),
+            StdinSource::Replaced(source) => {
+                try_flush_output();
+                source.next_line()
+            }
+            StdinSource::Given(source) => source.next_line(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn position(&self) -> Option<source::Position> {
+        match self {
+            StdinSource::Normal(source) => source.position(),
+            StdinSource::Bytes(source) => source.position(),
+            StdinSource::Interactive(source) => source.position(),
+            StdinSource::Unknown(source) => source.position(),
+            StdinSource::Replaced(source) => source.position(),
+            StdinSource::Given(source) => source.position(),
+            StdinSource::Unopened => None,
+        }
+    }
+
+    fn checkpoint(&mut self) -> source::Checkpoint {
+        match self {
+            StdinSource::Normal(source) => source.checkpoint(),
+            StdinSource::Bytes(source) => source.checkpoint(),
+            StdinSource::Interactive(source) => source.checkpoint(),
+            StdinSource::Unknown(source) => source.checkpoint(),
+            StdinSource::Replaced(source) => source.checkpoint(),
+            StdinSource::Given(source) => source.checkpoint(),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+
+    fn rewind(&mut self, checkpoint: source::Checkpoint) {
+        match self {
+            StdinSource::Normal(source) => source.rewind(checkpoint),
+            StdinSource::Bytes(source) => source.rewind(checkpoint),
+            StdinSource::Interactive(source) => source.rewind(checkpoint),
+            StdinSource::Unknown(source) => source.rewind(checkpoint),
+            StdinSource::Replaced(source) => source.rewind(checkpoint),
+            StdinSource::Given(source) => source.rewind(checkpoint),
+            StdinSource::Unopened => unopened(),
+        }
+    }
+}
+
+// `Unopened` is opened by `__InputLock` before it is read.
+#[cfg(feature = "std")]
+fn unopened() -> ! {
+    unreachable!("stdin is read before it is opened")
+}
+
+#[cfg(feature = "std")]
+impl<R: BufRead> StdinSource<R> {
+    // whether the whole input is read at first, so that the later reads never overwrite it.
+    fn keeps_input(&self) -> bool {
+        match self {
+            StdinSource::Normal(_) => !cfg!(debug_assertions),
+            StdinSource::Bytes(_) => true,
+            _ => false,
         }
     }
 }
 
 #[doc(hidden)]
+#[cfg(feature = "std")]
 pub static STDIN_SOURCE: OnceCell<Mutex<StdinSource<BufReader<Stdin>>>> = OnceCell::new();
 
+/// The environment variable of the path to the file read instead of stdin.
+#[cfg(feature = "std")]
+pub const INPUT_VAR: &str = "PROCONIO_INPUT";
+
+/// The environment variable of the input text read instead of stdin.
+#[cfg(feature = "std")]
+pub const INPUT_TEXT_VAR: &str = "PROCONIO_INPUT_TEXT";
+
+/// The environment variable of the path to the file recording the input read from stdin.
+#[cfg(feature = "std")]
+pub const RECORD_VAR: &str = "PROCONIO_RECORD";
+
+// Whether `read_stdin_as_bytes()` is called.
+#[cfg(feature = "std")]
+static BYTE_STDIN: AtomicBool = AtomicBool::new(false);
+
+// The file given by `record_stdin()`, taken when the stdin source is created.
+#[cfg(feature = "std")]
+static RECORD_FILE: Mutex<Option<std::fs::File>> = Mutex::new(None);
+
+// The kind of the stdin source to create, which depends on the first user of stdin.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy)]
+enum StdinKind {
+    Normal,
+    Interactive,
+    Unknown,
+}
+
+// Creates the stdin source of the kind, unless the input is given by `INPUT_VAR` or
+// `INPUT_TEXT_VAR`.  The input read from stdin is recorded if `RECORD_VAR` or `record_stdin()`
+// gives the file, or otherwise into the memo if `memo::ID_VAR` is set.
+#[cfg(feature = "std")]
+fn init_stdin_source(kind: StdinKind) -> StdinSource<BufReader<Stdin>> {
+    use source::any::Erased;
+    use source::record::RecordReader;
+    use std::fs::File;
+    use std::io::Cursor;
+
+    if let Some(path) = std::env::var_os(INPUT_VAR) {
+        let file = File::open(&path).unwrap_or_else(|e| {
+            panic!(
+                "failed to open `{}` given by `{}`: {}",
+                path.to_string_lossy(),
+                INPUT_VAR,
+                e
+            )
+        });
+        let source = AutoSource::new(BufReader::new(file));
+        return StdinSource::Given(Box::new(source::any::Erased::new(source)));
+    }
+
+    if let Some(text) = std::env::var_os(INPUT_TEXT_VAR) {
+        let text = text.to_string_lossy().into_owned();
+        let source = AutoSource::new(BufReader::new(Cursor::new(text.into_bytes())));
+        return StdinSource::Given(Box::new(source::any::Erased::new(source)));
+    }
+
+    let record = RECORD_FILE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    let record = record.or_else(|| {
+        let path = std::env::var_os(RECORD_VAR)?;
+        let file = File::create(&path).unwrap_or_else(|e| {
+            panic!(
+                "failed to create `{}` given by `{}`: {}",
+                path.to_string_lossy(),
+                RECORD_VAR,
+                e
+            )
+        });
+        Some(file)
+    });
+    let record = record.or_else(memo::input_record);
+    if let Some(record) = record {
+        let reader = RecordReader::new(BufReader::new(io::stdin()), record);
+        // only the interactive one flushes the output before reading, as without the record.
+        return match kind {
+            StdinKind::Normal if BYTE_STDIN.load(Ordering::Relaxed) => {
+                StdinSource::Given(Box::new(Erased::new(ByteSource::new(reader))))
+            }
+            StdinKind::Normal => StdinSource::Given(Box::new(Erased::new(AutoSource::new(reader)))),
+            StdinKind::Interactive => {
+                StdinSource::Replaced(Box::new(Erased::new(LineSource::new(reader))))
+            }
+            StdinKind::Unknown => {
+                StdinSource::Given(Box::new(Erased::new(LineSource::new(reader))))
+            }
+        };
+    }
+
+    let stdin = BufReader::new(io::stdin());
+    match kind {
+        StdinKind::Normal if BYTE_STDIN.load(Ordering::Relaxed) => {
+            StdinSource::Bytes(ByteSource::new(stdin))
+        }
+        StdinKind::Normal => StdinSource::Normal(AutoSource::new(stdin)),
+        StdinKind::Interactive => StdinSource::Interactive(LineSource::new(stdin)),
+        StdinKind::Unknown => StdinSource::Unknown(LineSource::new(stdin)),
+    }
+}
+
+/// Records the input read from stdin into the file at `path`, for replaying the run later.
+///
+/// Everything read from stdin is written to the file as is, so that the file can be given as the
+/// input of another run, by the shell redirection or `PROCONIO_INPUT`.  In the interactive mode,
+/// the file has the responses of the judge read so far.  Setting the environment variable
+/// `PROCONIO_RECORD` to the path records the input without modifying the code.
+///
+/// ```no_run
+/// # extern crate proconio;
+/// use proconio::input;
+///
+/// proconio::record_stdin("input.txt").unwrap();
+/// input! {
+///     n: usize,
+///     a: [u32; n],
+/// }
+/// ```
+///
+/// Call this before reading stdin; this panics if stdin has been read already.  The input given by
+/// `PROCONIO_INPUT` or `PROCONIO_INPUT_TEXT` is not recorded.
+#[cfg(feature = "std")]
+pub fn record_stdin<P: AsRef<std::path::Path>>(path: P) -> io::Result<()> {
+    assert!(
+        !stdin_opened(),
+        "failed to record stdin: stdin has been read already"
+    );
+    let file = std::fs::File::create(path)?;
+    *RECORD_FILE.lock().unwrap_or_else(PoisonError::into_inner) = Some(file);
+    Ok(())
+}
+
+/// Makes `input!` read stdin by `source::bytes::ByteSource` instead of `AutoSource`.
+///
+/// The whole stdin is read at once as raw bytes, and only the tokens read as strings are validated
+/// as UTF-8.  The integers are parsed from the bytes directly, which helps with huge ASCII inputs.
+/// Call this before reading stdin.  This doesn't affect `input_interactive!`, nor the input given
+/// by `PROCONIO_INPUT` or `PROCONIO_INPUT_TEXT`.
+///
+/// ```no_run
+/// # extern crate proconio;
+/// use proconio::input;
+///
+/// proconio::read_stdin_as_bytes();
+/// input! {
+///     n: usize,
+///     a: [u64; n],
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn read_stdin_as_bytes() {
+    assert!(
+        !stdin_opened(),
+        "failed to read stdin as bytes: stdin has been read already"
+    );
+    BYTE_STDIN.store(true, Ordering::Relaxed);
+}
+
+// Gets the stdin source without opening stdin, so that the first reader opens it as its kind.
+#[cfg(feature = "std")]
+fn stdin_source() -> &'static Mutex<StdinSource<BufReader<Stdin>>> {
+    STDIN_SOURCE.get_or_init(|| Mutex::new(StdinSource::Unopened))
+}
+
+// Whether stdin has been opened by a reader, or replaced by `from_args()`.
+#[cfg(feature = "std")]
+fn stdin_opened() -> bool {
+    STDIN_SOURCE.get().map_or(false, |stdin| {
+        let stdin = stdin.lock().unwrap_or_else(PoisonError::into_inner);
+        !matches!(*stdin, StdinSource::Unopened)
+    })
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub enum StdoutSink {
+    Stdout(BufWriter<Stdout>),       // for output!
+    Captured(Vec<u8>),               // for capture_output()
+    Replaced(Box<dyn Write + Send>), // for harness::with_sink() and memo
+}
+
+#[cfg(feature = "std")]
+impl Write for StdoutSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = match self {
+            StdoutSink::Stdout(stdout) => stdout.write(buf),
+            StdoutSink::Captured(captured) => captured.write(buf),
+            StdoutSink::Replaced(sink) => sink.write(buf),
+        }?;
+        if let Some(&last) = buf[..written].last() {
+            STDOUT_LINE_OPEN.store(last != b'\n', Ordering::Relaxed);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            StdoutSink::Stdout(stdout) => stdout.flush(),
+            StdoutSink::Captured(_) => Ok(()),
+            StdoutSink::Replaced(sink) => sink.flush(),
+        }
+    }
+}
+
+// Whether the output written to `STDOUT` so far does not end with a newline.
+#[cfg(feature = "std")]
+static STDOUT_LINE_OPEN: AtomicBool = AtomicBool::new(false);
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub static STDOUT: OnceCell<Mutex<StdoutSink>> = OnceCell::new();
+
+// Gets the stdout sink, initializing it with the buffered stdout.
+#[cfg(feature = "std")]
+fn stdout_sink() -> &'static Mutex<StdoutSink> {
+    STDOUT.get_or_init(|| {
+        let sink = match memo::output_record() {
+            Some(memo) => {
+                let out = io::stdout();
+                StdoutSink::Replaced(Box::new(BufWriter::new(memo::TeeOutput { out, memo })))
+            }
+            None => StdoutSink::Stdout(BufWriter::new(io::stdout())),
+        };
+        Mutex::new(sink)
+    })
+}
+
 /// read input from stdin.
 ///
 /// basic syntax is:
 /// ```text
 /// input! {
 ///     from source,          // optional: if you omitted, stdin is used by default.
+///     in source,            // instead of `from`: borrows the binding `source` without moving.
+///     with { sep = ",", radix = 16 }, // optional: the settings of this invocation.
 ///     (mut) variable: type, // mut is optional: mut makes the variable mutable.
+///     (a, mut b): (type, type), // tuple patterns destructure the read tuple.
+///     _: type,              // the value is read and discarded.
+///     variable: [type; len] as modifier, // optional: modifier is applied to the read array.
+///     variable: [type; n * 2 - 1], // the length can be any expression.
+///     variable: {type},     // braces accept any type, such as `{MyPair<u32, Usize1>}`.
+///     variable: {[type; N]}, // a fixed-size array `[type; N]` with a constant length.
+///     variable: [type; until: sentinel], // read until the sentinel, which is consumed.
+///     variable: [[type; k[i]]; i in n], // `i` is the index of the row.
+///     _: skip(n),           // the next `n` tokens are skipped.
+///     _: skip_line,         // the next token and the rest of its line are skipped.
+///     _: end_line,          // checks that the values so far came from exactly one line.
 ///     ...
 /// }
 /// ```
 /// the trailing comma is optional.  `source` can be anything implementing `Source`.  This macro
 /// moves out the specified source.  If you want to prevent moving, you can use `&mut source` since
-/// `&mut S` where `S: Source` also implements `Source`.
+/// `&mut S` where `S: Source` also implements `Source`.  `in source` is the shorthand of `from &mut
+/// source`, so the same binding can be read by multiple `input!`:
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::source::auto::AutoSource;
+///
+/// let mut source = AutoSource::from("2 1 2\n3");
+/// input! {
+///     in source,
+///     n: usize,
+///     a: [u32; n],
+/// }
+/// input! {
+///     in source,
+///     m: u32,
+/// }
+/// assert_eq!((a, m), (vec![1, 2], 3));
+/// ```
+///
+/// Without `from`, `input!` can be nested in the same thread, such as in `Readable::read()` or in
+/// a function called by it.  The nested `input!` reads the stdin from where the outer one is, and
+/// the outer one continues after the values read by the nested one.  `is_stdin_empty()`,
+/// `peek_stdin_token()` and `assert_input_consumed!()` can be nested as well.  The other threads
+/// wait until the outermost `input!` finishes.
 #[macro_export]
 macro_rules! input {
     // terminator
@@ -598,16 +1354,42 @@ macro_rules! input {
 
     // parse kind (type)
     (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest) => {
+        // `skip(n)` and `skip_line` have no value.
+        #[allow(clippy::let_unit_value)]
         let $($mut)* $var = $crate::read_value!(@source [$source] @kind [$($kind)*]);
     };
     (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest, $($rest:tt)*) => {
         $crate::input!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest);
         $crate::input!(@from [$source] @rest $($rest)*);
     };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest as $modifier:expr) => {
+        let $($mut)* $var = $crate::read_value!(@source [$source] @kind [$($kind)*] @modifier [$modifier]);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest as $modifier:expr, $($rest:tt)*) => {
+        $crate::input!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest as $modifier);
+        $crate::input!(@from [$source] @rest $($rest)*);
+    };
     (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
         $crate::input!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)* $tt] @rest $($rest)*);
     };
 
+    (in $source:expr, $($rest:tt)*) => {
+        $crate::input! {
+            from &mut $source,
+            $($rest)*
+        }
+    };
+    (from $source:expr, with { $($key:ident = $value:expr),* $(,)? }, $($rest:tt)*) => {
+        let mut configured = $crate::source::config::ConfiguredSource::new(
+            $source,
+            $crate::source::config::Config::new()$(.$key($value))*,
+        );
+        $crate::input! {
+            @from [&mut configured]
+            @rest $($rest)*
+        }
+        configured.finish();
+    };
     (from $source:expr, $($rest:tt)*) => {
         #[allow(unused_variables, unused_mut)]
         let mut s = $source;
@@ -616,22 +1398,126 @@ macro_rules! input {
             @rest $($rest)*
         }
     };
+    (with { $($settings:tt)* }, $($rest:tt)*) => {
+        let mut locked_stdin = $crate::__lock_stdin_for_input();
+        $crate::input! {
+            from &mut locked_stdin,
+            with { $($settings)* },
+            $($rest)*
+        }
+        drop(locked_stdin); // release the lock
+    };
     ($($rest:tt)*) => {
-        let mut locked_stdin = $crate::STDIN_SOURCE
-            .get_or_init(|| {
-                std::sync::Mutex::new($crate::StdinSource::Normal(
-                    $crate::source::auto::AutoSource::new(std::io::BufReader::new(std::io::stdin())),
-                ))
-            })
-            .lock()
-            .expect(concat!(
-                "failed to lock the stdin; please re-run this program.  ",
-                "If this issue repeatedly occur, this is a bug in `proconio`.  ",
-                "Please report this issue from ",
-                "<https://github.com/statiolake/proconio-rs/issues>."
-            ));
+        let mut locked_stdin = $crate::__lock_stdin_for_input();
         $crate::input! {
-            @from [&mut *locked_stdin]
+            @from [&mut locked_stdin]
+            @rest $($rest)*
+        }
+        drop(locked_stdin); // release the lock
+    };
+}
+
+/// read input like `input!`, returning the error instead of panicking.
+///
+/// Each value is read by `source::TryReadable`, and the first error is returned from the
+/// function by `?`, so the function must return `Result<_, E>` where `E: From<ReadError>`.  The
+/// syntax is the same as `input!`, though the kinds of the values are limited to:
+///
+/// - the types implementing `TryReadable`, such as the primitives, `String`, `Usize1` and `Chars`,
+///   in braces or not,
+/// - tuples of them, and
+/// - arrays of them, `[type; len]` and `[type]` with the length read first.
+///
+/// Neither the end of the input nor an invalid token panics, so this is useful to read an
+/// untrusted input, such as the data given by a fuzzer with `source::arbitrary::ArbitrarySource`.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::source::auto::AutoSource;
+/// use proconio::source::ReadError;
+/// use proconio::try_input;
+///
+/// fn sum(input: &str) -> Result<u64, ReadError> {
+///     try_input! {
+///         from AutoSource::from(input),
+///         a: [u64],
+///     }
+///     Ok(a.iter().sum())
+/// }
+///
+/// assert_eq!(sum("3 1 2 3"), Ok(6));
+/// assert_eq!(
+///     sum("2 1 x").unwrap_err().to_string(),
+///     "line 1, token 3: failed to parse the input `x` to the value of type `u64`: \
+///      ParseIntError { kind: InvalidDigit }",
+/// );
+/// assert!(sum("3 1").unwrap_err().token.is_none());
+/// ```
+#[macro_export]
+macro_rules! try_input {
+    // terminator
+    (@from [$source:expr] @rest) => {};
+
+    // parse mutability
+    (@from [$source:expr] @rest mut $($rest:tt)*) => {
+        $crate::try_input! {
+            @from [$source]
+            @mut [mut]
+            @rest $($rest)*
+        }
+    };
+    (@from [$source:expr] @rest $($rest:tt)*) => {
+        $crate::try_input! {
+            @from [$source]
+            @mut []
+            @rest $($rest)*
+        }
+    };
+
+    // parse variable pattern
+    (@from [$source:expr] @mut [$($mut:tt)?] @rest $var:tt: $($rest:tt)*) => {
+        $crate::try_input! {
+            @from [$source]
+            @mut [$($mut)*]
+            @var $var
+            @kind []
+            @rest $($rest)*
+        }
+    };
+
+    // parse kind (type)
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest) => {
+        let $($mut)* $var = $crate::try_read_value!(@source [$source] @kind [$($kind)*])?;
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest, $($rest:tt)*) => {
+        $crate::try_input!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest);
+        $crate::try_input!(@from [$source] @rest $($rest)*);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest as $($rest:tt)*) => {
+        compile_error!("modifiers (`as ...`) are not supported by `try_input!`.")
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::try_input!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)* $tt] @rest $($rest)*);
+    };
+
+    (in $source:expr, $($rest:tt)*) => {
+        $crate::try_input! {
+            from &mut $source,
+            $($rest)*
+        }
+    };
+    (from $source:expr, $($rest:tt)*) => {
+        #[allow(unused_variables, unused_mut)]
+        let mut s = $source;
+        $crate::try_input! {
+            @from [&mut s]
+            @rest $($rest)*
+        }
+    };
+    ($($rest:tt)*) => {
+        let mut locked_stdin = $crate::__lock_stdin_for_input();
+        $crate::try_input! {
+            @from [&mut locked_stdin]
             @rest $($rest)*
         }
         drop(locked_stdin); // release the lock
@@ -650,54 +1536,181 @@ macro_rules! input {
 /// }
 /// ```
 /// read the documet of [input!](input) for further information.
+///
+/// the output buffered by `output!` and `outputln!` is flushed before reading, so that the judge
+/// can receive your query.
 #[macro_export]
 macro_rules! input_interactive {
     ($($rest:tt)*) => {
-        let mut locked_stdin = $crate::STDIN_SOURCE
-            .get_or_init(|| {
-                std::sync::Mutex::new($crate::StdinSource::Interactive(
-                    $crate::source::line::LineSource::new(std::io::BufReader::new(std::io::stdin())),
-                ))
-            })
-            .lock()
-            .expect(concat!(
-                "failed to lock the stdin; please re-run this program.  ",
-                "If this issue repeatedly occur, this is a bug in `proconio`.  ",
-                "Please report this issue from ",
-                "<https://github.com/statiolake/proconio-rs/issues>."
-            ));
+        let mut locked_stdin = $crate::__lock_stdin_for_interactive();
         $crate::input! {
-            from &mut *locked_stdin,
+            from &mut locked_stdin,
             $($rest)*
         }
         drop(locked_stdin); // release the lock
     };
 }
 
+/// run the test cases given as "the number of cases `t`, then `t` cases".
+///
+/// basic syntax is:
+/// ```text
+/// input_cases! {
+///     |case| {  // `case` is the 0-based index of the case, of type `usize`.
+///         input! { ... }
+///         ...
+///     }
+/// }
+/// ```
+/// `t` is read by `input!` from the stdin, and the body runs `t` times.  Each case is read by
+/// `input!` in the body as usual.  `continue` in the body goes on to the next case, and `break`
+/// stops the remaining cases.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::{input, input_cases, outputln};
+/// # use proconio::harness::with_source;
+/// # use proconio::source::auto::AutoSource;
+/// # let output = proconio::capture_output(|| with_source(AutoSource::from("2\n3\n1 2 3\n1\n5\n"), || {
+///
+/// input_cases! {
+///     |_case| {
+///         input! {
+///             n: usize,
+///             a: [i64; n],
+///         }
+///         outputln!("{}", a.iter().sum::<i64>());
+///     }
+/// }
+/// # }));
+/// # assert_eq!(output, "6\n5\n");
+/// ```
+///
+/// With `prefix,` at the beginning, `Case #k: ` is written by `output!` before each case, where
+/// `k` is the 1-based index of the case, as required by Google's contests.  The format can be
+/// specified by `prefix("format string"),`; it takes `k` as the only argument.
+/// ```text
+/// input_cases! {
+///     prefix,                  // writes `Case #1: `, `Case #2: `, ...
+///     |case| { ... }
+/// }
+/// input_cases! {
+///     prefix("Case #{}:\n"),   // for the answers of multiple lines.
+///     |case| { ... }
+/// }
+/// ```
+/// Since the prefix is written to the buffered stdout, the answer must be written by `output!` or
+/// `outputln!` too.  Otherwise, the prefix and the answer are printed out of order.
+///
+/// Instead of `prefix`, the answer can be written with the prefix by `caseln!(case, ...)`.
+#[macro_export]
+macro_rules! input_cases {
+    (prefix, $($rest:tt)*) => {
+        $crate::input_cases!(@run ["Case #{}: "] $($rest)*)
+    };
+    (prefix($format:expr), $($rest:tt)*) => {
+        $crate::input_cases!(@run [$format] $($rest)*)
+    };
+    (@run [$($format:expr)?] |$case:tt $(: $ty:ty)?| $body:expr $(,)?) => {{
+        $crate::input! {
+            t: usize,
+        }
+        for case in 0..t {
+            $($crate::output!($format, case + 1);)?
+            let $case $(: $ty)? = case;
+            $body;
+        }
+    }};
+    (|$($rest:tt)*) => {
+        $crate::input_cases!(@run [] |$($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn __init_stdin_interactive() {
+    STDIN_SOURCE.get_or_init(|| Mutex::new(init_stdin_source(StdinKind::Interactive)));
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! read_value {
     // array and variable length array
     (@source [$source:expr] @kind [[$($kind:tt)*]]) => {
-        $crate::read_value!(@array @source [$source] @kind [] @rest $($kind)*)
+        $crate::read_value!(@array @source [$source] @kind [] @modifier [] @rest $($kind)*)
+    };
+    (@source [$source:expr] @kind [[$($kind:tt)*]] @modifier [$modifier:expr]) => {
+        $crate::read_value!(@array @source [$source] @kind [] @modifier [$modifier] @rest $($kind)*)
     };
-    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest) => {{
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest) => {{
         let len = <usize as $crate::__Readable>::read($source);
-        $crate::read_value!(@source [$source] @kind [[$($kind)*; len]])
+        $crate::read_value!(@array @source [$source] @kind [$($kind)*] @modifier [$($modifier)?] @len [len])
     }};
-    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest ; $($rest:tt)*) => {
-        $crate::read_value!(@array @source [$source] @kind [$($kind)*] @len [$($rest)*])
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest ; $($rest:tt)*) => {
+        $crate::read_value!(@array @source [$source] @kind [$($kind)*] @modifier [$($modifier)?] @len [$($rest)*])
     };
-    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
-        $crate::read_value!(@array @source [$source] @kind [$($kind)* $tt] @rest $($rest)*)
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest $tt:tt $($rest:tt)*) => {
+        $crate::read_value!(@array @source [$source] @kind [$($kind)* $tt] @modifier [$($modifier)?] @rest $($rest)*)
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [] @len [until: $($sentinel:tt)+]) => {
+        $crate::read_value!(@until @source [$source] @kind [$($kind)*] @sentinel [$($sentinel)+])
+            .collect::<$crate::__Vec<_>>()
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr] @len [until: $($sentinel:tt)+]) => {
+        $crate::modifier::Modifier::modify(
+            $modifier,
+            $crate::read_value!(@until @source [$source] @kind [$($kind)*] @sentinel [$($sentinel)+]),
+        )
+    };
+    (@until @source [$source:expr] @kind [$($kind:tt)*] @sentinel [$sentinel:expr]) => {
+        $crate::__until($sentinel, || $crate::read_value!(@source [$source] @kind [$($kind)*]))
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [] @len [$index:ident in $len:expr]) => {{
+        let len = $len;
+        (0..len)
+            .map(|$index| $crate::read_value!(@source [$source] @kind [$($kind)*]))
+            .collect::<$crate::__Vec<_>>()
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr] @len [$index:ident in $len:expr]) => {{
+        let len = $len;
+        $crate::modifier::Modifier::modify(
+            $modifier,
+            (0..len).map(|$index| $crate::read_value!(@source [$source] @kind [$($kind)*])),
+        )
+    }};
+    // `skip_line` and `end_line` are not types, so they are excluded from the bulk reading below.
+    (@array @source [$source:expr] @kind [skip_line] @modifier [] @len [$len:expr]) => {
+        $crate::read_value!(@array @source [$source] @kind [skip_line] @modifier [] @len [_line in $len])
     };
-    (@array @source [$source:expr] @kind [$($kind:tt)*] @len [$($len:tt)*]) => {{
-        let len = $($len)*;
+    (@array @source [$source:expr] @kind [end_line] @modifier [] @len [$len:expr]) => {
+        $crate::read_value!(@array @source [$source] @kind [end_line] @modifier [] @len [_line in $len])
+    };
+    // a single type name, read at once if it is a primitive integer.  See `source::bulk`.
+    (@array @source [$source:expr] @kind [$kind:ident] @modifier [] @len [$len:expr]) => {{
+        #[allow(unused_imports)]
+        use $crate::source::bulk::{ReadFast as _, ReadGeneric as _};
+        let len = $len;
+        (&$crate::source::bulk::bulk::<$kind>()).read_vec($source, 0..len)
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [] @len [$len:expr]) => {{
+        let len = $len;
         (0..len)
             .map(|_| $crate::read_value!(@source [$source] @kind [$($kind)*]))
-            .collect::<Vec<_>>()
+            .collect::<$crate::__Vec<_>>()
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr] @len [$len:expr]) => {{
+        let len = $len;
+        $crate::modifier::Modifier::modify(
+            $modifier,
+            (0..len).map(|_| $crate::read_value!(@source [$source] @kind [$($kind)*])),
+        )
     }};
 
+    // modifier for non-array types
+    (@source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr]) => {
+        compile_error!("modifiers (`as ...`) can only be applied to arrays.")
+    };
+
     // tuple
     (@source [$source:expr] @kind [($($kinds:tt)*)]) => {
         $crate::read_value!(@tuple @source [$source] @kinds [] @current [] @rest $($kinds)*)
@@ -717,17 +1730,107 @@ macro_rules! read_value {
         $crate::read_value!(@tuple @source [$source] @kinds [$($kinds)*] @current [$($curr)* $tt] @rest $($rest)*)
     };
 
+    // skipped tokens and lines
+    (@source [$source:expr] @kind [skip($n:expr)]) => {
+        $crate::source::Source::skip_tokens($source, $n)
+    };
+    (@source [$source:expr] @kind [skip_line]) => {
+        $crate::source::Source::skip_line($source)
+    };
+    (@source [$source:expr] @kind [end_line]) => {
+        $crate::source::Source::end_line($source)
+    };
+
+    // escaped fixed-size array: read as `[T; N]` instead of `Vec<T>`
+    (@source [$source:expr] @kind [{[$kind:ty; $len:expr]}]) => {
+        <$crate::marker::ArrayOf<$kind, $len> as $crate::__Readable>::read($source)
+    };
+
+    // escaped type: any type in braces, typically generic types with multiple parameters
+    (@source [$source:expr] @kind [{$kind:ty}]) => {
+        <$kind as $crate::__Readable>::read($source)
+    };
+
     // unreachable
     (@source [$source:expr] @kind []) => {
         compile_error!(concat!("Reached unreachable statement while parsing macro input.  ", "This is a bug in `proconio`.  ", "Please report this issue from ", "<https://github.com/statiolake/proconio-rs/issues>."));
     };
 
+    // a single type name, parsed fast if it is a primitive integer.  See `source::bulk`.
+    (@source [$source:expr] @kind [$kind:ident]) => {{
+        #[allow(unused_imports)]
+        use $crate::source::bulk::{ReadFast as _, ReadGeneric as _};
+        (&$crate::source::bulk::bulk::<$kind>()).read_one($source)
+    }};
+
     // normal other
     (@source [$source:expr] @kind [$kind:ty]) => {
         <$kind as $crate::__Readable>::read($source)
     }
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! try_read_value {
+    // array and variable length array
+    (@source [$source:expr] @kind [[$($kind:tt)*]]) => {
+        $crate::try_read_value!(@array @source [$source] @kind [] @rest $($kind)*)
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest) => {
+        <usize as $crate::__TryReadable>::try_read($source).and_then(|len| {
+            $crate::__try_vec(len, || $crate::try_read_value!(@source [$source] @kind [$($kind)*]))
+        })
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest ; until: $($rest:tt)*) => {
+        compile_error!("`[type; until: sentinel]` is not supported by `try_input!`.")
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest ; $index:ident in $($rest:tt)*) => {
+        compile_error!("`[type; index in len]` is not supported by `try_input!`.")
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest ; $len:expr) => {{
+        let len = $len;
+        $crate::__try_vec(len, || $crate::try_read_value!(@source [$source] @kind [$($kind)*]))
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::try_read_value!(@array @source [$source] @kind [$($kind)* $tt] @rest $($rest)*)
+    };
+
+    // tuple
+    (@source [$source:expr] @kind [($($kinds:tt)*)]) => {
+        $crate::try_read_value!(@tuple @source [$source] @kinds [] @current [] @rest $($kinds)*)
+    };
+    (@tuple @source [$source:expr] @kinds [$([$($kind:tt)*])*] @current [] @rest) => {
+        $crate::__try_with(|| {
+            Ok((
+                $($crate::try_read_value!(@source [$source] @kind [$($kind)*])?,)*
+            ))
+        })
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest) => {
+        $crate::try_read_value!(@tuple @source [$source] @kinds [$($kinds)* [$($curr)*]] @current [] @rest)
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest, $($rest:tt)*) => {
+        $crate::try_read_value!(@tuple @source [$source] @kinds [$($kinds)* [$($curr)*]] @current [] @rest $($rest)*)
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::try_read_value!(@tuple @source [$source] @kinds [$($kinds)*] @current [$($curr)* $tt] @rest $($rest)*)
+    };
+
+    // escaped type
+    (@source [$source:expr] @kind [{$kind:ty}]) => {
+        <$kind as $crate::__TryReadable>::try_read($source)
+    };
+
+    // unreachable
+    (@source [$source:expr] @kind []) => {
+        compile_error!(concat!("Reached unreachable statement while parsing macro input.  ", "This is a bug in `proconio`.  ", "Please report this issue from ", "<https://github.com/statiolake/proconio-rs/issues>."));
+    };
+
+    (@source [$source:expr] @kind [$kind:ty]) => {
+        <$kind as $crate::__TryReadable>::try_read($source)
+    };
+}
+
 /// Checks if some of tokens are left on stdin.
 ///
 /// This is useful when the number of test cases is not specified like ICPC problems.
@@ -742,242 +1845,2704 @@ macro_rules! read_value {
 ///     input! { ... }
 /// }
 /// ```
+#[cfg(feature = "std")]
 pub fn is_stdin_empty() -> bool {
     use source::Source;
-    let mut lock = STDIN_SOURCE
-        .get_or_init(|| {
-            Mutex::new(StdinSource::Unknown(LineSource::new(BufReader::new(
-                io::stdin(),
-            ))))
-        })
-        .lock()
-        .expect(concat!(
-            "failed to lock the stdin; please re-run this program.  ",
-            "If this issue repeatedly occur, this is a bug in `proconio`.  ",
-            "Please report this issue from ",
-            "<https://github.com/statiolake/proconio-rs/issues>."
-        ));
+    let mut lock = __lock_stdin();
     lock.is_empty()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::source::auto::AutoSource;
+/// Gets the next token on stdin without consuming it.
+///
+/// This is useful when the input is terminated by a sentinel, like ICPC problems.
+///
+/// ```text
+/// while peek_stdin_token().as_deref() != Some("0") {
+///     // do the normal logic
+///     input! { ... }
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn peek_stdin_token() -> Option<String> {
+    use source::Source;
+    let mut lock = __lock_stdin();
+    lock.peek_token().map(str::to_string)
+}
 
-    #[test]
-    fn input_empty() {
-        let source = AutoSource::from("");
-        input! {
-            from source,
-        }
+/// Makes `input!` without `from` read the command line arguments instead of stdin.
+///
+/// The arguments except the program name are read as the tokens by `source::args::ArgsSource`, so
+/// a solution can be used as a command line tool without modification:
+///
+/// ```no_run
+/// # extern crate proconio;
+/// use proconio::input;
+///
+/// // `myprog 2 3` prints `5`.
+/// proconio::from_args();
+/// input! {
+///     a: u32,
+///     b: u32,
+/// }
+/// println!("{}", a + b);
+/// ```
+///
+/// Call this before reading stdin; the input of stdin not read yet is ignored afterwards.
+#[cfg(feature = "std")]
+pub fn from_args() {
+    let args = || {
+        let source = source::args::ArgsSource::new();
+        StdinSource::Given(Box::new(source::any::Erased::new(source)))
+    };
+
+    let mut installed = false;
+    let stdin = STDIN_SOURCE.get_or_init(|| {
+        installed = true;
+        Mutex::new(args())
+    });
+    if !installed {
+        *stdin.lock().unwrap_or_else(PoisonError::into_inner) = args();
     }
+}
 
-    #[test]
-    fn input_number() {
-        let source = AutoSource::from("    32   54 -23\r\r\n\nfalse");
+/// Asserts that the whole input is consumed.
+///
+/// Unread input left at the end usually means that the input is read in a wrong format.  Without
+/// arguments, this checks the source of `input!` without `from`, that is, stdin.  Otherwise, this
+/// checks the given source like `from` of `input!`.  Panics if some tokens are left.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::{assert_input_consumed, input};
+/// use proconio::source::auto::AutoSource;
+///
+/// let mut source = AutoSource::from("2 1 2");
+/// input! {
+///     from &mut source,
+///     n: usize,
+///     a: [u32; n],
+/// }
+/// assert_input_consumed!(&mut source);
+/// ```
+///
+/// With `debug` module enabled (see `debug::enabled()`), the guard of `flush_on_exit()` also warns
+/// to stderr at the end of the program if the stdin is not consumed.
+#[macro_export]
+macro_rules! assert_input_consumed {
+    () => {
+        $crate::__assert_input_consumed(&mut $crate::__lock_stdin())
+    };
+    ($source:expr $(,)?) => {
+        $crate::__assert_input_consumed($source)
+    };
+}
 
-        input! {
-            from source,
-            n: u8,
-            m: u32,
-            l: i32,
-        }
+#[cfg(feature = "std")]
+type StdinPtr = *mut StdinSource<BufReader<Stdin>>;
 
-        assert_eq!(n, 32);
-        assert_eq!(m, 54);
-        assert_eq!(l, -23);
-    }
+#[cfg(feature = "std")]
+std::thread_local! {
+    // the stdin locked by the outermost `input!` of this thread, or null.
+    static LOCKED_STDIN: std::cell::Cell<StdinPtr> = const { std::cell::Cell::new(std::ptr::null_mut()) };
+    // whether a method of the locked stdin is running.
+    static READING_STDIN: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
 
-    #[test]
-    fn input_str() {
-        use crate::marker::{Bytes, Chars};
-        let source = AutoSource::from("  string   chars\nbytes");
+// The stdin locked by `input!`, which reports the position in the stdin if `input!` panics.
+//
+// The lock is re-entrant: `input!` nested in the same thread, such as in `Readable::read()`, gets
+// the stdin locked by the outermost one instead of waiting for itself.  Since the nested one may
+// read the next line into the buffer of the source, the tokens are copied out of it, unless the
+// source reads the whole input at first and never overwrites it.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub struct __InputLock {
+    // the guard of the outermost `input!`, or `None` for the nested one.
+    guard: Option<MutexGuard<'static, StdinSource<BufReader<Stdin>>>>,
+    source: StdinPtr,
+    // whether the tokens are returned borrowed from the source instead of copied.
+    borrow: bool,
+    token: String,
+    token_bytes: Vec<u8>,
+}
 
-        input! {
-            from source,
-            string: String,
-            chars: Chars,
-            bytes: Bytes,
+#[cfg(feature = "std")]
+impl __InputLock {
+    fn new(kind: StdinKind) -> __InputLock {
+        let held = LOCKED_STDIN.with(|locked| locked.get());
+        if !held.is_null() {
+            return __InputLock {
+                guard: None,
+                source: held,
+                borrow: false,
+                token: String::new(),
+                token_bytes: Vec::new(),
+            };
         }
 
-        assert_eq!(string, "string");
-        assert_eq!(chars, ['c', 'h', 'a', 'r', 's']);
-        assert_eq!(bytes, b"bytes");
+        // a panic caught while reading, such as by `harness` or `stress`, poisons the lock; the
+        // source is still valid, so the next `input!` continues to read it.
+        let mut guard = STDIN_SOURCE
+            .get_or_init(|| Mutex::new(init_stdin_source(kind)))
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let StdinSource::Unopened = *guard {
+            *guard = init_stdin_source(kind);
+        }
+        let source: StdinPtr = &mut *guard;
+        LOCKED_STDIN.with(|locked| locked.set(source));
+        __InputLock {
+            borrow: guard.keeps_input(),
+            guard: Some(guard),
+            source,
+            token: String::new(),
+            token_bytes: Vec::new(),
+        }
+    }
+
+    // runs `f` with the source.  `f` must not return a borrow of the source.
+    fn with<T>(&self, f: impl FnOnce(&mut StdinSource<BufReader<Stdin>>) -> T) -> T {
+        struct Reading;
+        impl Drop for Reading {
+            fn drop(&mut self) {
+                READING_STDIN.with(|reading| reading.set(false));
+            }
+        }
+
+        assert!(
+            !READING_STDIN.with(|reading| reading.replace(true)),
+            "failed to read the stdin: `input!` is called while the source of the stdin is reading"
+        );
+        let _reading = Reading;
+        // SAFETY: the source is locked by the outermost guard of this thread, which outlives the
+        // nested locks, and `READING_STDIN` ensures that only one `f` accesses it at a time.
+        f(unsafe { &mut *self.source })
+    }
+
+    // reads a token or a line by `f`, borrowed from the source if `borrow` or copied out of it.
+    fn read_with(
+        &mut self,
+        f: impl FnOnce(&mut StdinSource<BufReader<Stdin>>) -> Option<&str>,
+    ) -> Option<&str> {
+        if self.borrow {
+            // SAFETY: the source is locked by the guard of this lock, and the input borrowed is
+            // never overwritten by the nested locks.
+            return f(unsafe { &mut *self.source });
+        }
+
+        let mut token = mem::take(&mut self.token);
+        token.clear();
+        let found = self.with(|source| f(source).map(|t| token.push_str(t)).is_some());
+        self.token = token;
+        if found {
+            Some(&self.token)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl source::Source<BufReader<Stdin>> for __InputLock {
+    fn next_token(&mut self) -> Option<&str> {
+        self.read_with(|source| source.next_token())
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        if self.borrow {
+            // SAFETY: the same as `read_with()`.
+            return unsafe { &mut *self.source }.next_token_bytes();
+        }
+
+        let mut token = mem::take(&mut self.token_bytes);
+        token.clear();
+        let found = self.with(|source| {
+            source
+                .next_token_bytes()
+                .map(|t| token.extend_from_slice(t))
+                .is_some()
+        });
+        self.token_bytes = token;
+        if found {
+            Some(&self.token_bytes)
+        } else {
+            None
+        }
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.with(|source| source.is_empty())
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.read_with(|source| source.peek_token())
+    }
+
+    fn skip_line(&mut self) {
+        self.with(|source| source.skip_line())
+    }
+
+    fn end_line(&mut self) {
+        self.with(|source| source.end_line())
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.read_with(|source| source.next_line())
+    }
+
+    fn position(&self) -> Option<source::Position> {
+        self.with(|source| source.position())
+    }
+
+    fn checkpoint(&mut self) -> source::Checkpoint {
+        self.with(|source| source.checkpoint())
+    }
+
+    fn rewind(&mut self, checkpoint: source::Checkpoint) {
+        self.with(|source| source.rewind(checkpoint))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for __InputLock {
+    fn drop(&mut self) {
+        if self.guard.is_none() {
+            return;
+        }
+
+        if std::thread::panicking() && REPORT_INPUT_POSITION.load(Ordering::Relaxed) {
+            if let Some(guard) = &self.guard {
+                report_input_position(guard);
+            }
+        }
+        LOCKED_STDIN.with(|locked| locked.set(std::ptr::null_mut()));
+    }
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn __lock_stdin_for_input() -> __InputLock {
+    __InputLock::new(StdinKind::Normal)
+}
+
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn __lock_stdin_for_interactive() -> __InputLock {
+    __InputLock::new(StdinKind::Interactive)
+}
+
+// Locks the stdin for the functions other than `input!`, initializing it as `Unknown` if no source
+// is used yet.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn __lock_stdin() -> __InputLock {
+    __InputLock::new(StdinKind::Unknown)
+}
+
+#[doc(hidden)]
+pub fn __assert_input_consumed<R: source::BufRead, S: source::Source<R>>(mut source: S) {
+    if let Some(token) = source.next_token() {
+        panic!(
+            "failed to assert that the input is consumed: the next token is `{}`",
+            token
+        );
+    }
+}
+
+// Warns if the stdin used by `input!` is not consumed.  This never reads stdin for the check, since
+// it would block on a terminal or an interactive judge; `LineSource` of debug build checks only
+// the rest of the line read already.  This does nothing for the interactive source.
+#[cfg(feature = "std")]
+fn warn_unconsumed_stdin() {
+    use source::Source;
+    let mut lock = match STDIN_SOURCE.get() {
+        Some(stdin) => stdin.lock().unwrap_or_else(PoisonError::into_inner),
+        None => return,
+    };
+    let token = match &mut *lock {
+        #[cfg(debug_assertions)]
+        StdinSource::Normal(source) => source.buffered_token(),
+        #[cfg(not(debug_assertions))]
+        StdinSource::Normal(source) => source.peek_token(),
+        StdinSource::Bytes(source) => source.peek_token(),
+        _ => None,
+    };
+    if let Some(token) = token {
+        let mut stderr = stderr_writer();
+        let _ = writeln!(
+            stderr,
+            "warning: the input is not consumed; the next token is `{}`",
+            token
+        );
+    }
+}
+
+/// A locked handle of the buffered stdout used by `output!` and `outputln!`.
+///
+/// This is returned by `stdout_writer()`.  See its document for details.
+#[cfg(feature = "std")]
+pub struct StdoutWriter(MutexGuard<'static, StdoutSink>);
+
+#[cfg(feature = "std")]
+impl Write for StdoutWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Locks the buffered stdout used by `output!` and `outputln!` and returns the handle.
+///
+/// The buffered stdout is shared by all threads and protected by a mutex, so the output macros
+/// can be safely used from multiple threads (for example, threads spawned by `rayon`).  Each
+/// invocation of the macros writes its output at once, so the output of a single invocation is
+/// never interleaved with others.
+///
+/// If you want to write several pieces of output without being interleaved, or pass the stdout to
+/// a function taking `impl Write`, use this handle.  While the handle is alive, other threads
+/// calling the output macros wait for it to be dropped.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::stdout_writer;
+/// use std::io::Write;
+///
+/// let mut writer = stdout_writer();
+/// for i in 0..3 {
+///     write!(writer, "{} ", i).unwrap();
+/// }
+/// writeln!(writer).unwrap();
+/// ```
+///
+/// **Note:** The lock is not reentrant.  Using `output!` or `outputln!` in the same thread while
+/// holding the handle causes deadlock.
+#[cfg(feature = "std")]
+pub fn stdout_writer() -> StdoutWriter {
+    // as the stdin, the buffer is still valid after a panic caught while writing.
+    let lock = stdout_sink().lock().unwrap_or_else(PoisonError::into_inner);
+    StdoutWriter(lock)
+}
+
+/// write output to the buffered stdout.
+///
+/// basic syntax is the same with `print!`:
+/// ```text
+/// output!("format string", args...);
+/// ```
+/// the output is buffered, so you must call `flush_output()` at the end of your program, or use
+/// `flush_on_exit()` at the start of your program.  Otherwise, some of the output may be lost.
+///
+/// In place of the format string, you can specify `fixed(precision)` or `fixed` followed by
+/// values.  In this form, each value is formatted by `out::Fixed` with the specified precision
+/// (or the default precision for `fixed`), and separated by a space.
+/// ```text
+/// output!(fixed(10), x, y); // same as output!("{:.10} {:.10}", x, y);
+/// ```
+///
+/// Similarly, `fast` formats each value by `out::Fast`, which needs `fastfmt` feature.
+/// ```text
+/// output!(fast, x, y); // same as output!("{} {}", x, y) for integers, but faster.
+/// ```
+///
+/// The destination can be specified by `to sink,` at the beginning, where `sink` implements
+/// `sink::Sink`.  See `sink` module for details.
+/// ```text
+/// output!(to sink, "format string", args...);
+/// ```
+#[macro_export]
+macro_rules! output {
+    (to $sink:expr, fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [&mut $sink] [$precision] [""] $($value),+)
+    };
+    (to $sink:expr, fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [&mut $sink] [$crate::out::default_precision()] [""] $($value),+)
+    };
+    (to $sink:expr, fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [&mut $sink] [""] $($value),+)
+    };
+    (to $sink:expr, $($arg:tt)*) => {
+        ::std::write!($crate::sink::__Writer(&mut $sink), $($arg)*)
+            .expect("failed to write to the sink")
+    };
+    (fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stdout_writer()] [$precision] [""] $($value),+)
+    };
+    (fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stdout_writer()] [$crate::out::default_precision()] [""] $($value),+)
+    };
+    (fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [$crate::stdout_writer()] [""] $($value),+)
+    };
+    (@fixed [$sink:expr] [$precision:expr] [$end:expr] $first:expr $(, $rest:expr)*) => {{
+        let precision = $precision;
+        let mut sink = $sink;
+        let mut writer = $crate::sink::__Writer(&mut sink);
+        ::std::write!(writer, "{}", $crate::out::Fixed($first, precision))
+            .expect("failed to write to the sink");
+        $(
+            ::std::write!(writer, " {}", $crate::out::Fixed($rest, precision))
+                .expect("failed to write to the sink");
+        )*
+        ::std::write!(writer, "{}", $end).expect("failed to write to the sink");
+    }};
+    (@fast [$sink:expr] [$end:expr] $first:expr $(, $rest:expr)*) => {{
+        let mut sink = $sink;
+        let mut writer = $crate::sink::__Writer(&mut sink);
+        ::std::write!(writer, "{}", $crate::out::Fast($first)).expect("failed to write to the sink");
+        $(
+            ::std::write!(writer, " {}", $crate::out::Fast($rest))
+                .expect("failed to write to the sink");
+        )*
+        ::std::write!(writer, "{}", $end).expect("failed to write to the sink");
+    }};
+    ($($arg:tt)*) => {{
+        use std::io::Write as _;
+        ::std::write!($crate::stdout_writer(), $($arg)*).expect("failed to write to the stdout");
+    }};
+}
+
+/// write output to the buffered stdout, with a newline.
+///
+/// this macro is the same with `output!` except that a newline is appended.  See the document of
+/// [output!](output) for further information.
+#[macro_export]
+macro_rules! outputln {
+    () => {
+        $crate::output!("{}", $crate::out::newline())
+    };
+    (to $sink:expr) => {
+        $crate::output!(to $sink, "{}", $crate::out::newline())
+    };
+    (to $sink:expr, fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [&mut $sink] [$precision] [$crate::out::newline()] $($value),+)
+    };
+    (to $sink:expr, fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [&mut $sink] [$crate::out::default_precision()] [$crate::out::newline()] $($value),+)
+    };
+    (to $sink:expr, fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [&mut $sink] [$crate::out::newline()] $($value),+)
+    };
+    (to $sink:expr, $($arg:tt)*) => {
+        ::std::write!(
+            $crate::sink::__Writer(&mut $sink),
+            "{}{}",
+            ::std::format_args!($($arg)*),
+            $crate::out::newline(),
+        )
+        .expect("failed to write to the sink")
+    };
+    (fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stdout_writer()] [$precision] [$crate::out::newline()] $($value),+)
+    };
+    (fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stdout_writer()] [$crate::out::default_precision()] [$crate::out::newline()] $($value),+)
+    };
+    (fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [$crate::stdout_writer()] [$crate::out::newline()] $($value),+)
+    };
+    ($($arg:tt)*) => {{
+        use std::io::Write as _;
+        ::std::write!(
+            $crate::stdout_writer(),
+            "{}{}",
+            ::std::format_args!($($arg)*),
+            $crate::out::newline(),
+        )
+        .expect("failed to write to the stdout");
+    }};
+}
+
+/// write an answer of a test case to the buffered stdout, prefixed by `Case #k: `.
+///
+/// basic syntax is:
+/// ```text
+/// caseln!(case, "format string", args...);
+/// ```
+/// `case` is the 0-based index of the case, and `k` is the 1-based one, so that the index given by
+/// `input_cases!` or `for case in 0..t` can be used as is.  The rest is the same with `outputln!`,
+/// including `fixed(precision)` and `to sink`:
+/// ```text
+/// caseln!(to sink, case, "format string", args...);
+/// ```
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::caseln;
+///
+/// let output = proconio::capture_output(|| {
+///     for (case, ans) in [3, 1].iter().enumerate() {
+///         caseln!(case, "{}", ans);
+///     }
+/// });
+/// assert_eq!(output, "Case #1: 3\nCase #2: 1\n");
+/// ```
+#[macro_export]
+macro_rules! caseln {
+    (to $sink:expr, $case:expr, $($arg:tt)*) => {{
+        $crate::output!(to $sink, "Case #{}: ", $case + 1);
+        $crate::outputln!(to $sink, $($arg)*);
+    }};
+    ($case:expr, $($arg:tt)*) => {{
+        $crate::output!("Case #{}: ", $case + 1);
+        $crate::outputln!($($arg)*);
+    }};
+}
+
+/// write the items of an iterator to the buffered stdout, one item per line.
+///
+/// basic syntax is:
+/// ```text
+/// output_lines!(iter);      // each item followed by "\n".
+/// output_lines!(iter, sep); // the items separated by `sep`, followed by "\n".
+/// ```
+/// the items must implement `Display`.  They are written directly to the buffer, without joining
+/// them to a string.  As `output!`, the destination can be specified by `to sink,` at the
+/// beginning.
+///
+/// **Note:** The stdout is locked while the iterator is consumed.  Using `output!` in the
+/// iterator, like in the closure of `map()`, causes deadlock.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::output_lines;
+///
+/// let output = proconio::capture_output(|| {
+///     let a = vec![3, 1, 4];
+///     output_lines!(&a);
+///     output_lines!(a.iter().map(|x| x * 2), " ");
+/// });
+/// assert_eq!(output, "3\n1\n4\n6 2 8\n");
+/// ```
+#[macro_export]
+macro_rules! output_lines {
+    (to $sink:expr, $iter:expr $(,)?) => {
+        $crate::out::write_lines(&mut $sink, $iter).expect("failed to write to the sink")
+    };
+    (to $sink:expr, $iter:expr, $sep:expr $(,)?) => {
+        $crate::out::write_joined(&mut $sink, $iter, $sep).expect("failed to write to the sink")
+    };
+    ($iter:expr $(,)?) => {
+        $crate::out::write_lines(&mut $crate::stdout_writer(), $iter)
+            .expect("failed to write to the stdout")
+    };
+    ($iter:expr, $sep:expr $(,)?) => {
+        $crate::out::write_joined(&mut $crate::stdout_writer(), $iter, $sep)
+            .expect("failed to write to the stdout")
+    };
+}
+
+/// Flushes the output buffered by `output!` and `outputln!`.
+#[cfg(feature = "std")]
+pub fn flush_output() {
+    stdout_writer().flush().expect("failed to flush the stdout");
+}
+
+/// A locked handle of the buffered stderr used by `eoutput!` and `eoutputln!`.
+///
+/// This is returned by `stderr_writer()`.
+#[cfg(feature = "std")]
+pub struct StderrWriter(MutexGuard<'static, BufWriter<Stderr>>);
+
+#[cfg(feature = "std")]
+impl Write for StderrWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(feature = "std")]
+static STDERR: OnceCell<Mutex<BufWriter<Stderr>>> = OnceCell::new();
+
+// Gets the buffered stderr.  When it is created, a panic hook flushing it is installed so that the
+// messages written before a panic are printed.
+#[cfg(feature = "std")]
+fn stderr_sink() -> &'static Mutex<BufWriter<Stderr>> {
+    STDERR.get_or_init(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            try_flush_stderr();
+            default_hook(info);
+        }));
+        Mutex::new(BufWriter::new(io::stderr()))
+    })
+}
+
+/// Locks the buffered stderr used by `eoutput!` and `eoutputln!` and returns the handle.
+///
+/// This is the stderr version of `stdout_writer()`.  See its document for details.
+#[cfg(feature = "std")]
+pub fn stderr_writer() -> StderrWriter {
+    let lock = stderr_sink().lock().unwrap_or_else(PoisonError::into_inner);
+    StderrWriter(lock)
+}
+
+/// write output to the buffered stderr.
+///
+/// this macro is the same with `output!` except that the destination is the stderr, and `to` is
+/// not accepted.  Unlike `eprint!`, the output is buffered, so printing many diagnostic messages
+/// is fast.  The buffer is flushed by `flush_stderr()`, by the guard of `flush_on_exit()`, and on
+/// panic.  See the document of [output!](output) for the syntax.
+#[macro_export]
+macro_rules! eoutput {
+    (fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stderr_writer()] [$precision] [""] $($value),+)
+    };
+    (fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stderr_writer()] [$crate::out::default_precision()] [""] $($value),+)
+    };
+    (fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [$crate::stderr_writer()] [""] $($value),+)
+    };
+    ($($arg:tt)*) => {{
+        use std::io::Write as _;
+        ::std::write!($crate::stderr_writer(), $($arg)*).expect("failed to write to the stderr");
+    }};
+}
+
+/// write output to the buffered stderr, with a newline.
+///
+/// this macro is the same with `eoutput!` except that a newline is appended.  See the document of
+/// [eoutput!](eoutput) for further information.
+#[macro_export]
+macro_rules! eoutputln {
+    () => {
+        $crate::eoutput!("\n")
+    };
+    (fixed($precision:expr), $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stderr_writer()] [$precision] ["\n"] $($value),+)
+    };
+    (fixed, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fixed [$crate::stderr_writer()] [$crate::out::default_precision()] ["\n"] $($value),+)
+    };
+    (fast, $($value:expr),+ $(,)?) => {
+        $crate::output!(@fast [$crate::stderr_writer()] ["\n"] $($value),+)
+    };
+    ($($arg:tt)*) => {{
+        use std::io::Write as _;
+        ::std::writeln!($crate::stderr_writer(), $($arg)*).expect("failed to write to the stderr");
+    }};
+}
+
+/// Flushes the output buffered by `eoutput!` and `eoutputln!`.
+#[cfg(feature = "std")]
+pub fn flush_stderr() {
+    stderr_writer().flush().expect("failed to flush the stderr");
+}
+
+/// Captures the output written by `output!` and `outputln!` during `f` and returns it.
+///
+/// While `f` is running, the output is stored in a buffer instead of being printed, so that tests
+/// can inspect what the solution printed.  The output buffered before the call is flushed first.
+/// The output of other threads during `f` is also captured since the stdout is shared.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::{capture_output, outputln};
+///
+/// let output = capture_output(|| {
+///     outputln!("{} {}", 1, 2);
+///     outputln!("Yes");
+/// });
+/// assert_eq!(output, "1 2\nYes\n");
+/// ```
+///
+/// The capture can be nested; the inner capture takes the output while it is active.  The output
+/// printed by `print!`, `println!` or `#[fastout]` is not captured.  See also `harness` module.
+#[cfg(feature = "std")]
+pub fn capture_output<F: FnOnce()>(f: F) -> String {
+    harness::capture(f).1
+}
+
+/// A guard flushing the output buffered by `output!` and `outputln!` when dropped.
+///
+/// This is returned by `flush_on_exit()`.  See its document for details.
+#[must_use = "the output is flushed when the guard is dropped; bind it to a variable like `_guard`"]
+#[cfg(feature = "std")]
+pub struct FlushGuard(());
+
+#[cfg(feature = "std")]
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        if !std::thread::panicking() && debug::enabled() {
+            warn_unconsumed_stdin();
+        }
+        if !std::thread::panicking() && out::final_newline() {
+            try_end_output_line();
+        }
+        try_flush_output();
+        try_flush_stderr();
+    }
+}
+
+/// Makes sure the output buffered by `output!` and `outputln!` is flushed at the end of `main`.
+///
+/// Forgetting to call `flush_output()` silently loses your output.  Bind the returned guard to a
+/// variable at the start of your `main()`, then the output is flushed when the guard is dropped,
+/// that is, when `main()` returns.  This also installs a panic hook that flushes the output before
+/// the panic message is printed, so the output written before a panic is not lost even if the
+/// program is built with `panic = "abort"`.  The stderr buffered by `eoutput!` and `eoutputln!` is
+/// flushed by the guard as well.
+///
+/// On a panic, the hook also prints the position in the stdin where the program is, like `note:
+/// the input was read up to the token 3 of the line 2`.  If the panic occurs in `input!`, such as
+/// a parse error, it is printed while unwinding after the panic message, so it is not printed with
+/// `panic = "abort"`.  The position is known only for `AutoSource`, which is the default.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::{flush_on_exit, outputln};
+///
+/// fn main() {
+///     let _guard = flush_on_exit(); // don't bind it to `_`: it drops the guard immediately.
+///     outputln!("{}", 42);
+/// } // the output is flushed here.
+/// ```
+#[cfg(feature = "std")]
+pub fn flush_on_exit() -> FlushGuard {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            try_flush_output();
+            default_hook(info);
+            try_report_input_position();
+        }));
+    });
+    REPORT_INPUT_POSITION.store(true, Ordering::Relaxed);
+
+    FlushGuard(())
+}
+
+/// Runs `f` in a new thread with the specified size (in bytes) of stack, and returns its result.
+///
+/// Deep recursion (e.g. DFS on a graph with 10^6 vertices) overflows the default stack of the main
+/// thread on many judges.  This function spawns a thread having enough stack, runs `f` in it, and
+/// waits for it.  If `f` panics, the panic is propagated to the caller.
+///
+/// `input!` and `output!` work in the thread as usual since stdin and stdout are shared among
+/// threads.  The output buffered by `output!` and `outputln!` in `f` is flushed after the thread
+/// finishes.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::run_with_stack;
+///
+/// fn depth(n: u32) -> u32 {
+///     if n == 0 { 0 } else { depth(n - 1) + 1 }
+/// }
+///
+/// let res = run_with_stack(256 * 1024 * 1024, || depth(1_000_000));
+/// assert_eq!(res, 1_000_000);
+/// ```
+///
+/// `#[fastout]` cannot be used for closures; use a function having `#[fastout]` instead.  You can
+/// also use `#[proconio::main(stack = "256MB")]` to do the same for your `main()`.
+#[cfg(feature = "std")]
+pub fn run_with_stack<F, T>(size: usize, f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let res = std::thread::Builder::new()
+        .stack_size(size)
+        .spawn(f)
+        .expect("failed to spawn a thread with the specified size of stack")
+        .join();
+    try_flush_output();
+
+    match res {
+        Ok(res) => res,
+        Err(e) => std::panic::resume_unwind(e),
+    }
+}
+
+// Flushes the buffered stdout if possible.  Unlike `flush_output()`, this never blocks nor panics
+// since this may be called while panicking (possibly while the stdout is locked in the same
+// thread).
+#[cfg(feature = "std")]
+pub(crate) fn try_flush_output() {
+    let stdout = match STDOUT.get() {
+        Some(stdout) => stdout,
+        None => return,
+    };
+
+    let mut lock = match stdout.try_lock() {
+        Ok(lock) => lock,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => return,
+    };
+
+    let _ = lock.flush();
+}
+
+// Whether the position in the stdin is reported on panic, which is enabled by `flush_on_exit()`.
+#[cfg(feature = "std")]
+static REPORT_INPUT_POSITION: AtomicBool = AtomicBool::new(false);
+
+// Prints the position in the stdin source.
+#[cfg(feature = "std")]
+fn report_input_position(source: &StdinSource<BufReader<Stdin>>) {
+    use source::Source;
+    if let Some(position) = source.position() {
+        eprintln!("note: the input was read up to {}", position);
+    }
+}
+
+// Prints the position in the stdin for the panic hook, unless `input!` holds the stdin; then its
+// guard reports it instead.  This never blocks nor panics as `try_flush_output()`.
+#[cfg(feature = "std")]
+fn try_report_input_position() {
+    let stdin = match STDIN_SOURCE.get() {
+        Some(stdin) => stdin,
+        None => return,
+    };
+
+    match stdin.try_lock() {
+        Ok(lock) => report_input_position(&lock),
+        Err(TryLockError::Poisoned(poisoned)) => report_input_position(&poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => {}
+    }
+}
+
+// Writes a newline if the output does not end with it, for `out::set_final_newline()`.  This never
+// blocks nor panics as `try_flush_output()`.
+#[cfg(feature = "std")]
+fn try_end_output_line() {
+    if !STDOUT_LINE_OPEN.load(Ordering::Relaxed) {
+        return;
+    }
+    let stdout = match STDOUT.get() {
+        Some(stdout) => stdout,
+        None => return,
+    };
+
+    if let Ok(mut lock) = stdout.try_lock() {
+        let _ = lock.write_all(out::newline().as_bytes());
+    }
+}
+
+// Flushes the buffered stderr if possible.  This never blocks nor panics as `try_flush_output()`.
+#[cfg(feature = "std")]
+fn try_flush_stderr() {
+    let stderr = match STDERR.get() {
+        Some(stderr) => stderr,
+        None => return,
+    };
+
+    let mut lock = match stderr.try_lock() {
+        Ok(lock) => lock,
+        Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+        Err(TryLockError::WouldBlock) => return,
+    };
+
+    let _ = lock.flush();
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::source::auto::AutoSource;
+
+    #[test]
+    fn input_empty() {
+        let source = AutoSource::from("");
+        input! {
+            from source,
+        }
+    }
+
+    #[test]
+    fn input_number() {
+        let source = AutoSource::from("    32   54 -23\r\r\n\nfalse");
+
+        input! {
+            from source,
+            n: u8,
+            m: u32,
+            l: i32,
+        }
+
+        assert_eq!(n, 32);
+        assert_eq!(m, 54);
+        assert_eq!(l, -23);
+    }
+
+    #[test]
+    fn input_str() {
+        use crate::marker::{Bytes, Chars};
+        let source = AutoSource::from("  string   chars\nbytes");
+
+        input! {
+            from source,
+            string: String,
+            chars: Chars,
+            bytes: Bytes,
+        }
+
+        assert_eq!(string, "string");
+        assert_eq!(chars, ['c', 'h', 'a', 'r', 's']);
+        assert_eq!(bytes, b"bytes");
+    }
+
+    #[test]
+    fn input_byte_source() {
+        use crate::marker::{Bytes, Chars};
+        use crate::source::bytes::ByteSource;
+        let source = ByteSource::from(&b" 3\r\n\xff\xfe chars\n-7 \t"[..]);
+
+        input! {
+            from source,
+            n: usize,
+            bytes: Bytes,
+            chars: Chars,
+            m: i32,
+        }
+
+        assert_eq!(n, 3);
+        assert_eq!(bytes, b"\xff\xfe");
+        assert_eq!(chars, ['c', 'h', 'a', 'r', 's']);
+        assert_eq!(m, -7);
+    }
+
+    #[test]
+    fn input_from_bytes() {
+        let source = AutoSource::from(&b"2 1 2"[..]);
+
+        input! {
+            from source,
+            n: usize,
+            a: [u8; n],
+        }
+
+        assert_eq!(a, [1, 2]);
+    }
+
+    #[test]
+    fn input_delimiters() {
+        use crate::marker::Chars;
+        use crate::source::bytes::ByteSource;
+
+        let source = ByteSource::with_delimiters(&b"3,14,,15\r\nab;-2\n"[..], ",;\r\n");
+        input! {
+            from source,
+            a: [u32; 3],
+            s: Chars,
+            x: i32,
+        }
+
+        assert_eq!(a, [3, 14, 15]);
+        assert_eq!(s, ['a', 'b']);
+        assert_eq!(x, -2);
+    }
+
+    #[test]
+    #[should_panic(expected = "the delimiter `、` is not an ASCII character")]
+    fn input_err_non_ascii_delimiters() {
+        use crate::source::bytes::ByteSource;
+
+        ByteSource::with_delimiters(&b"1"[..], ",、");
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a valid UTF-8 string")]
+    fn input_err_byte_source_invalid_utf8() {
+        use crate::source::bytes::ByteSource;
+        let source = ByteSource::from(&b"\xff"[..]);
+
+        input! {
+            from source,
+            _s: String,
+        }
+    }
+
+    #[test]
+    fn input_array() {
+        let source = AutoSource::from("5 3 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5");
+
+        input! {
+            from source,
+            n: usize,
+            m: usize,
+            a: [i32; n],
+            b: [[i32; n]; m] // no trailing comma is OK
+        }
+
+        assert_eq!(a, [1, 2, 3, 4, 5]);
+        assert_eq!(b, [[1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5]]);
+    }
+
+    #[test]
+    fn input_vla() {
+        let source = AutoSource::from("5 3 1 2 3 2 1 2 4 1 2 3 4 0 6 1 2 3 4 5 6");
+
+        input! {
+            from source,
+            n: usize,
+            a: [[i32]; n],
+        }
+
+        assert_eq!(
+            a,
+            vec![
+                vec![1, 2, 3],
+                vec![1, 2],
+                vec![1, 2, 3, 4],
+                vec![],
+                vec![1, 2, 3, 4, 5, 6],
+            ]
+        );
+    }
+
+    #[test]
+    fn input_computed_lengths() {
+        let source = AutoSource::from("3 2 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17");
+
+        input! {
+            from source,
+            n: usize,
+            m: u32,
+            a: [[i32; m as usize * 2]; n - 1],
+            b: [u8; n / 2 + 1],
+            c: [[u8; a.len()]; if n > 2 { 1 } else { 0 }],
+            d: [u8; m - 1],
+        }
+
+        assert_eq!(a, [[1, 2, 3, 4], [5, 6, 7, 8]]);
+        assert_eq!(b, [9, 10]);
+        assert_eq!(c, [[11, 12]]);
+        assert_eq!(d, [13]);
+    }
+
+    #[test]
+    fn input_bulk() {
+        use crate::marker::Usize1;
+
+        let source = AutoSource::from("3 1 -2 3\nx\ny\n1 2 +3 4 5");
+        input! {
+            from source,
+            n: u8,
+            a: [i64; n],
+            _: [skip_line; 2],
+            b: [Usize1; 2],
+            c: [u32; n - 1],
+            d: [[u8; 1]; 1],
+        }
+
+        assert_eq!(a, [1, -2, 3]);
+        assert_eq!(b, [0, 1]);
+        assert_eq!(c, [3, 4]);
+        assert_eq!(d, [[5]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `256`")]
+    fn input_err_bulk() {
+        let source = AutoSource::from("255 256");
+        input! {
+            from source,
+            _a: [u8; 2],
+        }
+    }
+
+    #[test]
+    fn input_indexed_rows() {
+        use crate::modifier::Indexed;
+
+        let source = AutoSource::from("3 1 3 0 7 4 5 6 1 2 3 4 5 6 7 8 9");
+
+        input! {
+            from source,
+            n: usize,
+            k: [usize; n],
+            a: [[i32; k[i]]; i in n],
+            b: [[u8; i]; i in 4],
+            c: [[u8; 2 - i]; i in 2] as Indexed,
+        }
+
+        assert_eq!(a, [vec![7], vec![4, 5, 6], vec![]]);
+        assert_eq!(b, [vec![], vec![1], vec![2, 3], vec![4, 5, 6]]);
+        assert_eq!(c, [(0, vec![7, 8]), (1, vec![9])]);
+    }
+
+    #[test]
+    fn input_tuple() {
+        let source = AutoSource::from("4 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5");
+
+        input! {
+            from source,
+            n: usize,
+            t: [(i32, i32, i32, i32, i32); n],
+        }
+
+        assert_eq!(
+            t,
+            [
+                (1, 2, 3, 4, 5),
+                (1, 2, 3, 4, 5),
+                (1, 2, 3, 4, 5),
+                (1, 2, 3, 4, 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn input_multiple_times() {
+        let mut source = AutoSource::from("4 1 2 3 4\n1 2\r\n\r\r\n3 4");
+
+        input! {
+            from &mut source,
+            n: usize,
+        }
+
+        for i in 0..n {
+            input! {
+                from &mut source,
+                j: i32, k: i32,
+            }
+
+            assert_eq!(j, if i % 2 == 0 { 1 } else { 3 });
+            assert_eq!(k, if i % 2 == 0 { 2 } else { 4 });
+        }
+    }
+
+    #[test]
+    fn input_iusize1() {
+        use crate::marker::Usize1;
+
+        let mut source = AutoSource::from("4 1 2 3 4 5 6 7 8");
+
+        input! {
+            from &mut source,
+            n: usize,
+        }
+
+        for i in 0..n {
+            input! {
+                from &mut source,
+                from: Usize1, to: Usize1
+            }
+
+            assert_eq!(from, i * 2);
+            assert_eq!(to, i * 2 + 1);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_zero_as_usize1() {
+        use crate::marker::Usize1;
+        let mut source = AutoSource::from("0");
+        input! {
+            from &mut source,
+            _v: Usize1,
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_min_as_isize1() {
+        use crate::marker::Isize1;
+        let min_string = isize::MIN.to_string();
+        let mut source = AutoSource::from(&*min_string);
+        input! {
+            from &mut source,
+            _v: Isize1,
+        }
+    }
+
+    #[test]
+    fn input_loose_f64() {
+        use crate::marker::LooseF64;
+
+        let mut source = AutoSource::from("1e9 +3.5 -2,25 1,234.5 .5");
+        input! {
+            from &mut source,
+            a: [LooseF64; 5],
+        }
+        assert_eq!(a, [1e9, 3.5, -2.25, 1234.5, 0.5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_err_loose_f64() {
+        use crate::marker::LooseF64;
+
+        let mut source = AutoSource::from("1,2,3");
+        input! {
+            from &mut source,
+            _x: LooseF64,
+        }
+    }
+
+    #[test]
+    fn input_collections() {
+        use crate::marker::{BTreeSetOf, BinaryHeapOf, HashSetOf, Usize1, VecDequeOf};
+        use crate::modifier::collect;
+        use std::collections::{HashSet, VecDeque};
+
+        let source = AutoSource::from("3 3 1 3  2 5 4  3 1 2 3  2 2 1  4 8 8 9 8");
+        input! {
+            from source,
+            hash_set: HashSetOf<i32>,
+            btree_set: BTreeSetOf<i32>,
+            deque: VecDequeOf<Usize1>,
+            heap: BinaryHeapOf<u8>,
+            n: usize,
+            collected: [u8; n] as collect::<HashSet<_>>(),
+        }
+
+        assert_eq!(hash_set, [1, 3].iter().copied().collect());
+        assert_eq!(btree_set.into_iter().collect::<Vec<_>>(), [4, 5]);
+        assert_eq!(deque, VecDeque::from(vec![0, 1, 2]));
+        assert_eq!(heap.into_sorted_vec(), [1, 2]);
+        assert_eq!(collected, [8, 9].iter().copied().collect());
+    }
+
+    #[test]
+    fn input_len_prefixed() {
+        use crate::marker::{LenPrefixed, Usize1};
+
+        let source = AutoSource::from("3  2 1 2  0  1 3  1 5 x");
+        input! {
+            from source,
+            n: usize,
+            rows: [LenPrefixed<Usize1>; n],
+            pair: (LenPrefixed<u8>, char),
+        }
+
+        assert_eq!(rows, [vec![0, 1], vec![], vec![2]]);
+        assert_eq!(pair, (vec![5], 'x'));
+    }
+
+    #[test]
+    fn input_line_bytes() {
+        use crate::marker::{Line, LineBytes};
+        use crate::source::line::LineSource;
+        use crate::source::once::OnceSource;
+        use crate::source::slice::SliceSource;
+        use crate::source::{BufRead, Source};
+
+        fn check<R: BufRead, S: Source<R>>(mut source: S) {
+            input! {
+                from &mut source,
+                h: usize,
+                _w: usize,
+                grid: [LineBytes; h],
+                blank: Line,
+                word: String,
+                rest: Line,
+                last: Line,
+            }
+
+            assert_eq!(grid, [b"# #".to_vec(), b" . ".to_vec()]);
+            assert_eq!(blank, "");
+            assert_eq!(word, "ab");
+            assert_eq!(rest, "  x y");
+            assert_eq!(last, "z");
+            assert_eq!(source.next_line(), None);
+        }
+
+        let input = "2 3\n# #\n . \r\n\nab c\n  x y\nz";
+        check(OnceSource::from(input));
+        check(LineSource::from(input));
+        check(SliceSource::from(input));
+    }
+
+    #[test]
+    fn read_into() {
+        use crate::marker::{Bytes, Chars, Line, LineBytes};
+        use crate::source::Readable;
+
+        let mut source = AutoSource::from("7 abcdef xy\nlong line\nab\n");
+        let mut n = 0;
+        u32::read_into(&mut source, &mut n);
+        let mut bytes = Vec::new();
+        Bytes::read_into(&mut source, &mut bytes);
+        let capacity = bytes.capacity();
+        let mut chars = vec!['z'; 3];
+        Chars::read_into(&mut source, &mut chars);
+        let mut line = String::new();
+        Line::read_into(&mut source, &mut line);
+        Bytes::read_into(&mut source, &mut bytes);
+
+        assert_eq!(n, 7);
+        assert_eq!(chars, ['x', 'y']);
+        assert_eq!(line, "long line");
+        // the shorter token is read into the allocation of the previous one.
+        assert_eq!(bytes, b"ab");
+        assert_eq!(bytes.capacity(), capacity);
+
+        let mut source = AutoSource::from("# #\n.\n");
+        let mut row = Vec::new();
+        LineBytes::read_into(&mut source, &mut row);
+        assert_eq!(row, b"# #");
+        LineBytes::read_into(&mut source, &mut row);
+        assert_eq!(row, b".");
+    }
+
+    #[test]
+    fn source_position() {
+        use crate::source::line::LineSource;
+        use crate::source::once::OnceSource;
+        use crate::source::slice::SliceSource;
+        use crate::source::{BufRead, Position, Source};
+
+        fn check<R: BufRead, S: Source<R>>(mut source: S) {
+            let at = |line, token| Some(Position { line, token });
+            assert_eq!(source.position(), None);
+            source.next_token();
+            source.next_token();
+            assert_eq!(source.position(), at(1, 2));
+            source.skip_line();
+            assert_eq!(source.position(), at(2, 1));
+            source.next_line();
+            assert_eq!(source.position(), at(3, 0));
+            source.next_token();
+            assert_eq!(source.position(), at(5, 1));
+        }
+
+        let input = "1 2\n3 4\n  line \n\n 5";
+        check(OnceSource::from(input));
+        check(LineSource::from(input));
+        check(SliceSource::from(input));
+        assert_eq!(
+            Position { line: 5, token: 1 }.to_string(),
+            "the token 1 of the line 5"
+        );
+    }
+
+    #[test]
+    fn input_in() {
+        let mut source = AutoSource::from("1 2\nab 3 c");
+        input! {
+            in source,
+            a: (u8, u8),
+        }
+        input! {
+            in source,
+            with { sep = "b" },
+            s: String,
+        }
+        let mut sources = [source];
+        input! {
+            in sources[0],
+            rest: [String; 2],
+        }
+
+        assert_eq!(a, (1, 2));
+        assert_eq!(s, "a");
+        assert_eq!(rest, ["3", "c"]);
+    }
+
+    #[test]
+    fn source_rewind() {
+        use crate::source::bytes::ByteSource;
+        use crate::source::once::OnceSource;
+        use crate::source::slice::SliceSource;
+        use crate::source::{BufRead, Source};
+
+        fn check<R: BufRead, S: Source<R>>(mut source: S) {
+            source.skip_line();
+            let checkpoint = source.checkpoint();
+            let position = source.position();
+            assert_eq!(source.next_token(), Some("3"));
+            assert_eq!(source.next_line(), Some("  line "));
+            assert_eq!(source.next_token(), Some("5"));
+            assert!(source.is_empty());
+
+            source.rewind(checkpoint);
+            assert_eq!(source.position(), position);
+            assert_eq!(source.next_line(), Some("3 4"));
+            assert_eq!(source.next_token(), Some("line"));
+            source.rewind(checkpoint);
+            assert_eq!(source.next_token(), Some("3"));
+        }
+
+        let input = "1 2\n3 4\n  line \n\n 5";
+        check(OnceSource::from(input));
+        check(ByteSource::from(input));
+        check(SliceSource::from(input));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to save a checkpoint: this source reads the input as a stream"
+    )]
+    fn input_err_rewind_line_source() {
+        use crate::source::line::LineSource;
+        use crate::source::Source;
+
+        LineSource::from("1 2").checkpoint();
+    }
+
+    #[test]
+    fn input_maps() {
+        use crate::marker::{BTreeMapOf, Chars, HashMapOf, Usize1};
+        use crate::modifier::collect;
+        use std::collections::BTreeMap;
+
+        let source = AutoSource::from("2 a 1 b 2  3 1 x 2 y 1 z  2 3 4 3 5");
+        input! {
+            from source,
+            hash_map: {HashMapOf<String, i64>},
+            btree_map: {BTreeMapOf<Usize1, Chars>},
+            n: usize,
+            collected: [(u8, u8); n] as collect::<BTreeMap<_, _>>(),
+        }
+
+        assert_eq!(hash_map.len(), 2);
+        assert_eq!(hash_map["a"], 1);
+        assert_eq!(hash_map["b"], 2);
+        assert_eq!(
+            btree_map.into_iter().collect::<Vec<_>>(),
+            [(0, vec!['z']), (1, vec!['y'])]
+        );
+        assert_eq!(collected.into_iter().collect::<Vec<_>>(), [(3, 5)]);
+    }
+
+    #[test]
+    fn input_padded() {
+        use crate::marker::Chars;
+        use crate::modifier::Padded;
+
+        let source = AutoSource::from("3 1 2 3  2 ab c  1 2 2 1 2");
+        input! {
+            from source,
+            n: usize,
+            a: [i64; n] as Padded(0),
+            h: usize,
+            grid: [Chars; h] as Padded('#'),
+            vla: [[u8]; 2] as Padded(9),
+        }
+
+        assert_eq!(a, [0, 1, 2, 3, 0]);
+        assert_eq!(
+            grid,
+            [
+                ['#', '#', '#', '#'],
+                ['#', 'a', 'b', '#'],
+                ['#', 'c', '#', '#'],
+                ['#', '#', '#', '#'],
+            ]
+        );
+        assert_eq!(
+            vla,
+            [[9, 9, 9, 9], [9, 2, 9, 9], [9, 1, 2, 9], [9, 9, 9, 9]]
+        );
+    }
+
+    #[test]
+    fn input_indexed() {
+        use crate::modifier::Indexed;
+
+        let source = AutoSource::from("3 5 -1 5  2 a 1 b 2");
+        input! {
+            from source,
+            n: usize,
+            a: [i64; n] as Indexed,
+            pairs: [(char, u8)] as Indexed,
+            empty: [u8; 0] as Indexed,
+        }
+
+        assert_eq!(a, [(0, 5), (1, -1), (2, 5)]);
+        assert_eq!(pairs, [(0, ('a', 1)), (1, ('b', 2))]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn input_unzip() {
+        use crate::marker::{Chars, Usize1};
+        use crate::modifier::Unzip;
+
+        let source = AutoSource::from("2 1 ab 3 c  0");
+        input! {
+            from source,
+            n: usize,
+            (idx, mut s): [(Usize1, Chars); n] as Unzip,
+            (empty, _): [(u8, u8)] as Unzip,
+        }
+
+        s.push(vec!['d']);
+        assert_eq!(idx, [0, 2]);
+        assert_eq!(s, [vec!['a', 'b'], vec!['c'], vec!['d']]);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn input_prefix_sum() {
+        use crate::modifier::{prefix_sum, PrefixSum};
+
+        let source = AutoSource::from("3 4000000000 4000000000 -1  2 1e18 1e18  0");
+        input! {
+            from source,
+            n: usize,
+            a: [i64; n] as PrefixSum,
+            b: [f64] as prefix_sum::<f64>(),
+            empty: [u64] as PrefixSum,
+        }
+
+        assert_eq!(a, [0, 4_000_000_000, 8_000_000_000, 7_999_999_999]);
+        assert_eq!(b, [0.0, 1e18, 2e18]);
+        assert_eq!(empty, [0]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to add `18446744073709551615` to the prefix sum: the value does not fit in `i64`"
+    )]
+    fn input_err_prefix_sum_overflow() {
+        use crate::modifier::PrefixSum;
+
+        let source = AutoSource::from("1 18446744073709551615");
+        input! {
+            from source,
+            _a: [u64] as PrefixSum,
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to add `9223372036854775807` at index 1 to the prefix sum: the sum overflows `i64`"
+    )]
+    fn input_err_prefix_sum_sum_overflow() {
+        use crate::modifier::PrefixSum;
+
+        let source = AutoSource::from("2 1 9223372036854775807");
+        input! {
+            from source,
+            _a: [i64] as PrefixSum,
+        }
+    }
+
+    #[test]
+    fn try_input() {
+        use crate::marker::{Chars, Usize1};
+        use crate::source::{Position, ReadError};
+
+        type Values = (usize, Vec<(u8, char)>, Vec<Vec<char>>);
+        fn read(input: &str) -> Result<Values, ReadError> {
+            try_input! {
+                from AutoSource::from(input),
+                n: Usize1,
+                mut a: [(u8, char); n],
+                s: [Chars],
+            }
+            a.reverse();
+            Ok((n, a, s))
+        }
+
+        assert_eq!(
+            read("3 1 a 2 b 2 xy z"),
+            Ok((2, vec![(2, 'b'), (1, 'a')], vec![vec!['x', 'y'], vec!['z']]))
+        );
+
+        let error = read("3 1 a x").unwrap_err();
+        assert_eq!(error.position, Some(Position { line: 1, token: 4 }));
+        assert_eq!(error.token.as_deref(), Some("x"));
+        assert_eq!(error.type_name, "u8");
+
+        // a broken length reads the values until the end of the input.
+        let error = read("18446744073709551615 1 a").unwrap_err();
+        assert_eq!((error.token, error.type_name), (None, "u8"));
+
+        let error = read("0").unwrap_err();
+        assert_eq!(error.token.as_deref(), Some("0"));
+        assert_eq!(error.type_name, "proconio::marker::Usize1");
+    }
+
+    #[test]
+    fn input_prefix_sum_2d() {
+        use crate::modifier::PrefixSum2D;
+
+        let source = AutoSource::from("3 2  -1 2  3 -4  5 6  0");
+        input! {
+            from source,
+            h: usize,
+            w: usize,
+            g: [[i8; w]; h] as PrefixSum2D,
+            empty: [[i8; 3]] as PrefixSum2D,
+        }
+
+        assert_eq!((g.height(), g.width()), (3, 2));
+        assert_eq!(g.sum(0, 0, 3, 2), 11);
+        assert_eq!(g.sum(1, 0, 3, 1), 8);
+        assert_eq!(g.sum(2, 1, 2, 2), 0);
+        assert_eq!(g.get(1, 1), -4);
+        assert_eq!((empty.height(), empty.width()), (0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the rows have different lengths")]
+    fn input_err_prefix_sum_2d_jagged() {
+        use crate::modifier::PrefixSum2D;
+
+        let source = AutoSource::from("2  1 5  2 1 2");
+        input! {
+            from source,
+            _g: [[u8]] as PrefixSum2D,
+        }
+    }
+
+    #[test]
+    fn input_counts() {
+        use crate::marker::Chars;
+        use crate::modifier::{CountMap, Counts};
+
+        let source = AutoSource::from("4 2 0 2 2  3 b a b  0");
+        input! {
+            from source,
+            n: usize,
+            a: [usize; n] as Counts(3),
+            s: [char] as CountMap,
+            empty: [Chars] as CountMap,
+        }
+
+        assert_eq!(a, [1, 0, 3, 0]);
+        assert_eq!((s.len(), s[&'a'], s[&'b']), (2, 1, 2));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to count `-1`: the value is not in `0..=3`")]
+    fn input_err_counts_negative() {
+        use crate::modifier::Counts;
+
+        let source = AutoSource::from("2 3 -1");
+        input! {
+            from source,
+            _a: [i32] as Counts(3),
+        }
+    }
+
+    #[test]
+    fn input_digits() {
+        use crate::marker::{Digits, DigitsRev};
+
+        let source = AutoSource::from("1203 0045 9");
+        input! {
+            from source,
+            digits: Digits,
+            rev: DigitsRev,
+            single: Digits,
+        }
+
+        assert_eq!(digits, [1, 2, 0, 3]);
+        assert_eq!(rev, [5, 4, 0, 0]);
+        assert_eq!(single, [9]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the input `12a4` as digits: `a` is not a digit")]
+    fn input_err_digits() {
+        use crate::marker::Digits;
+
+        let source = AutoSource::from("12a4");
+        input! {
+            from source,
+            _digits: Digits,
+        }
+    }
+
+    #[test]
+    fn input_mod() {
+        use crate::marker::{Mod, Mod1000000007, Mod998244353};
+
+        let source = AutoSource::from("998244354 -1 123456789012345678901234567890 +7 -14 0");
+        input! {
+            from source,
+            a: Mod998244353,
+            b: Mod998244353,
+            c: Mod1000000007,
+            d: Mod<5>,
+            e: Mod<7>,
+            f: Mod<7>,
+        }
+
+        assert_eq!(a, 1);
+        assert_eq!(b, 998_244_352);
+        assert_eq!(c, 197_434_842);
+        assert_eq!((d, e, f), (2, 0, 0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to parse the input `1e9` as an integer modulo 998244353: `e` is not a digit"
+    )]
+    fn input_err_mod() {
+        use crate::marker::Mod998244353;
+
+        let source = AutoSource::from("1e9");
+        input! {
+            from source,
+            _a: Mod998244353,
+        }
+    }
+
+    #[test]
+    fn input_clock() {
+        use crate::marker::{HhMm, HhMmSs};
+
+        let source = AutoSource::from("12:34 9:05 00:00:00 23:59:59");
+        input! {
+            from source,
+            a: HhMm,
+            b: HhMm,
+            c: HhMmSs,
+            d: HhMmSs,
+        }
+
+        assert_eq!(a, 12 * 3600 + 34 * 60);
+        assert_eq!(b, 9 * 3600 + 5 * 60);
+        assert_eq!(c, 0);
+        assert_eq!(d, 86399);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `24:00` as a HhMm: `24` is out of range")]
+    fn input_err_clock_out_of_range() {
+        use crate::marker::HhMm;
+
+        let source = AutoSource::from("24:00");
+        input! {
+            from source,
+            _t: HhMm,
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "as a HhMmSs: expected 3 fields separated by `:`")]
+    fn input_err_clock_fields() {
+        use crate::marker::HhMmSs;
+
+        let source = AutoSource::from("12:34");
+        input! {
+            from source,
+            _t: HhMmSs,
+        }
+    }
+
+    #[test]
+    fn input_ymd() {
+        use crate::marker::Ymd;
+
+        let source = AutoSource::from("2019/04/01 2024-2-29 2000/02/29");
+        input! {
+            from source,
+            dates: [Ymd; 3],
+        }
+
+        assert_eq!(dates, [(2019, 4, 1), (2024, 2, 29), (2000, 2, 29)]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to parse the input `1900/02/29` as a Ymd: day 29 is out of range"
+    )]
+    fn input_err_ymd_not_leap_year() {
+        use crate::marker::Ymd;
+
+        let source = AutoSource::from("1900/02/29");
+        input! {
+            from source,
+            _date: Ymd,
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "as a Ymd: `04-01` is not a number")]
+    fn input_err_ymd_mixed_separators() {
+        use crate::marker::Ymd;
+
+        let source = AutoSource::from("2019/04-01");
+        input! {
+            from source,
+            _date: Ymd,
+        }
+    }
+
+    #[test]
+    fn input_128bit_integers() {
+        let input = format!("{} {} 0", u128::MAX, i128::MIN);
+        let source = AutoSource::from(&*input);
+        input! {
+            from source,
+            a: u128,
+            b: i128,
+            c: i128,
+        }
+
+        assert_eq!(a, u128::MAX);
+        assert_eq!(b, i128::MIN);
+        assert_eq!(c, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "to the value of type `u128`: ParseIntError { kind: PosOverflow }")]
+    fn input_err_u128_overflow() {
+        let source = AutoSource::from("340282366920938463463374607431768211456");
+        input! {
+            from source,
+            _a: u128,
+        }
+    }
+
+    #[test]
+    fn input_radix() {
+        use crate::marker::{Bin, Hex, Oct};
+
+        let source = AutoSource::from("ff 0x1A -7f 17 0o17 1011 0b11 -101");
+        input! {
+            from source,
+            h: [Hex<u64>; 2],
+            hn: Hex<i8>,
+            o: [Oct<u32>; 2],
+            b: [Bin<u32>; 2],
+            bn: Bin<i32>,
+        }
+
+        assert_eq!(h, [255, 26]);
+        assert_eq!(hn, -127);
+        assert_eq!(o, [15, 15]);
+        assert_eq!(b, [11, 3]);
+        assert_eq!(bn, -5);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to parse the input `102` as an integer of type `u32` in radix 2"
+    )]
+    fn input_err_radix() {
+        use crate::marker::Bin;
+
+        let source = AutoSource::from("102");
+        input! {
+            from source,
+            _b: Bin<u32>,
+        }
+    }
+
+    #[test]
+    fn input_from_str() {
+        use crate::marker::FromStrReadable;
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+        let source = AutoSource::from("192.168.0.1 ::1 10.0.0.1:8080 [::1]:443 127.0.0.1");
+        input! {
+            from source,
+            v4: Ipv4Addr,
+            v6: Ipv6Addr,
+            addrs: [SocketAddr; 2],
+            ip: FromStrReadable<IpAddr>,
+        }
+
+        assert_eq!(v4, Ipv4Addr::new(192, 168, 0, 1));
+        assert_eq!(v6, Ipv6Addr::LOCALHOST);
+        assert_eq!(addrs[0], SocketAddr::from(([10, 0, 0, 1], 8080)));
+        assert_eq!(addrs[1].port(), 443);
+        assert_eq!(ip, IpAddr::V4(Ipv4Addr::LOCALHOST));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `1.2.3` to the value of type")]
+    fn input_err_from_str() {
+        use crate::marker::FromStrReadable;
+        use std::net::Ipv4Addr;
+
+        let source = AutoSource::from("1.2.3");
+        input! {
+            from source,
+            _ip: FromStrReadable<Ipv4Addr>,
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Interval(u32, u32);
+
+    impl crate::source::FromTokens for Interval {
+        const TOKENS: usize = 2;
+        type Err = &'static str;
+        fn from_tokens(tokens: &[&str]) -> Result<Interval, &'static str> {
+            let l = tokens[0].parse().map_err(|_| "invalid left")?;
+            let r = tokens[1].parse().map_err(|_| "invalid right")?;
+            if l <= r {
+                Ok(Interval(l, r))
+            } else {
+                Err("left > right")
+            }
+        }
+    }
+
+    #[test]
+    fn input_from_tokens() {
+        use crate::marker::Tokens;
+
+        let source = AutoSource::from("2 1 3 4 4");
+        input! {
+            from source,
+            n: usize,
+            a: [Tokens<Interval>; n],
+        }
+
+        assert_eq!(a, [Interval(1, 3), Interval(4, 4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the tokens [\"3\", \"1\"] to the value of type")]
+    fn input_err_from_tokens() {
+        use crate::marker::Tokens;
+
+        let source = AutoSource::from("3 1");
+        input! {
+            from source,
+            _a: Tokens<Interval>,
+        }
+    }
+
+    #[test]
+    fn input_mut() {
+        let mut source = AutoSource::from("8 1 2 3 4 5 6 7 8");
+
+        input! {
+            from &mut source,
+            mut n: usize,
+        }
+
+        let mut sum = 0;
+        while n > 0 {
+            input!(from &mut source, x: u32);
+            sum += x;
+            n -= 1;
+        }
+        assert_eq!(sum, 36);
+    }
+
+    #[test]
+    fn input_with_complex_type() {
+        let mut source = AutoSource::from("Hello\n2\n3 1 2 3\n4 5 ab\n4");
+        input! {
+            from &mut source,
+            hello: crate::marker::Bytes,
+            from: crate::marker::Usize1,
+            vla: [crate::marker::Isize1],
+            tuple: (crate::marker::Usize1, crate::marker::Isize1, crate::marker::Chars),
+            unit: (crate::marker::Usize1),
+        }
+        assert_eq!(hello, b"Hello");
+        assert_eq!(from, 1);
+        assert_eq!(vla, [0, 1, 2]);
+        assert_eq!(tuple, (3, 4, vec!['a', 'b']));
+        assert_eq!(unit, (3,));
+    }
+
+    #[test]
+    fn input_escaped_type() {
+        use crate::marker::Usize1;
+        use crate::source::{Readable, Source};
+        use crate::write::{assert_roundtrip, Writable};
+        use std::io::BufRead;
+        use std::marker::PhantomData;
+
+        struct Both<A, B>(PhantomData<(A, B)>);
+        impl<A: Readable, B: Readable> Readable for Both<A, B> {
+            type Output = (A::Output, B::Output);
+            fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+                (A::read(source), B::read(source))
+            }
+        }
+        impl<A: Writable, B: Writable> Writable for Both<A, B> {
+            fn write((a, b): &Self::Output, out: &mut String) {
+                A::write(a, out);
+                B::write(b, out);
+            }
+        }
+        assert_roundtrip::<Both<Usize1, i32>>(&(0, -2));
+
+        let source = AutoSource::from("1 2 2 3 4 5 6 7 8 9");
+        input! {
+            from source,
+            both: {Both<Usize1, i32>},
+            n: usize,
+            array: [{Both<u8, Usize1>}; n],
+            tuple: ({Both<i32, i32>}, i32),
+        }
+
+        assert_eq!(both, (0, 2));
+        assert_eq!(array, [(3, 3), (5, 5)]);
+        assert_eq!(tuple, ((7, 8), 9));
+    }
+
+    #[test]
+    fn input_fixed_size_array() {
+        use crate::marker::{ArrayOf, Usize1};
+
+        const K: usize = 2;
+        let source = AutoSource::from("1 2 3\n2\n4 5\n6 7\n8 9 10 11");
+        input! {
+            from source,
+            p: {[i64; 3]},
+            n: usize,
+            q: [{[Usize1; K]}; n],
+            r: {[ArrayOf<u8, 2>; 2]},
+        }
+
+        assert_eq!(p, [1, 2, 3]);
+        assert_eq!(q, [[3, 4], [5, 6]]);
+        assert_eq!(r, [[8, 9], [10, 11]]);
+
+        let mut source = AutoSource::from("");
+        input! {
+            from &mut source,
+            empty: {ArrayOf<u8, 0>},
+        }
+        assert_eq!(empty, []);
+    }
+
+    #[test]
+    fn input_single_tt_pattern() {
+        let mut source = AutoSource::from("3 42 0\n1 2 3\n");
+        input! {
+            from &mut source,
+            (n, mut k): (usize, usize),
+            _: usize,
+            xs: [u32; n],
+        }
+        k += 1;
+        assert_eq!(n, 3);
+        assert_eq!(k, 43);
+        assert_eq!(xs, [1, 2, 3]);
+    }
+
+    #[test]
+    fn input_tuple_pattern() {
+        use crate::marker::{Chars, Usize1};
+
+        let mut source = AutoSource::from(
+            "1 2 3
+4 ab 5 6
+2
+7 8
+9 10
+",
+        );
+        input! {
+            from &mut source,
+            (a, b, c): (i64, i64, i64),
+            (mut x, s, (y, z)): (Usize1, Chars, (u8, u8)),
+            n: usize,
+            edges: [(Usize1, Usize1); n],
+        }
+        x += 10;
+
+        assert_eq!((a, b, c), (1, 2, 3));
+        assert_eq!((x, s, y, z), (13, vec!['a', 'b'], 5, 6));
+        for (i, &(u, v)) in edges.iter().enumerate() {
+            assert_eq!((u, v), (6 + 2 * i, 7 + 2 * i));
+        }
+    }
+
+    #[test]
+    fn input_discard() {
+        let mut source = AutoSource::from("2 1 2 3 4\n5 6 7\n8\n9 x\n");
+        input! {
+            from &mut source,
+            n: usize,
+            _: [[i64; 2]; n],
+            (a, _, b): (u8, i64, u8),
+            _: u8,
+            _: (i32, String),
+        }
+
+        assert_eq!((a, b), (5, 7));
+        assert!(crate::source::Source::is_empty(&mut source));
+    }
+
+    #[test]
+    fn input_skip() {
+        use crate::source::bytes::ByteSource;
+        use crate::source::line::LineSource;
+        use crate::source::once::OnceSource;
+        use crate::source::{BufRead, Source};
+
+        fn check<R: BufRead, S: Source<R>>(mut source: S) {
+            input! {
+                from &mut source,
+                n: usize,
+                _: skip(n),
+                a: u8,
+                _: skip_line,
+                _: skip_line,
+                b: u8,
+                _: skip_line,
+            }
+
+            assert_eq!((a, b), (4, 7));
+            assert!(source.is_empty());
+        }
+
+        let input = "3 1 2\n3 4 5 6\n\nskipped line\n7 8\n";
+        check(LineSource::from(input));
+        check(OnceSource::from(input));
+        check(ByteSource::from(input));
     }
 
     #[test]
-    fn input_array() {
-        let source = AutoSource::from("5 3 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5");
+    fn input_until() {
+        let source = AutoSource::from("1 2 0\n-1\n\nab cd -\n5 3 1 0");
 
         input! {
             from source,
-            n: usize,
-            m: usize,
-            a: [i32; n],
-            b: [[i32; n]; m] // no trailing comma is OK
+            a: [u8; until: 0],
+            b: [i8; until: -1],
+            c: [String; until: "-"],
+            d: [u32; until: 0] as crate::modifier::Sorted,
         }
 
-        assert_eq!(a, [1, 2, 3, 4, 5]);
-        assert_eq!(b, [[1, 2, 3, 4, 5], [1, 2, 3, 4, 5], [1, 2, 3, 4, 5]]);
+        assert_eq!(a, [1, 2]);
+        assert!(b.is_empty());
+        assert_eq!(c, ["ab", "cd"]);
+        assert_eq!(d, [1, 3, 5]);
     }
 
     #[test]
-    fn input_vla() {
-        let source = AutoSource::from("5 3 1 2 3 2 1 2 4 1 2 3 4 0 6 1 2 3 4 5 6");
+    fn input_until_variable() {
+        let source = AutoSource::from("1 2 3 -1 4 5");
+        let until = 2;
 
         input! {
             from source,
-            n: usize,
-            a: [[i32]; n],
+            a: [i8; until + 1],
+            b: [i8; until - 1],
+            c: [i8; until],
         }
 
-        assert_eq!(
-            a,
-            vec![
-                vec![1, 2, 3],
-                vec![1, 2],
-                vec![1, 2, 3, 4],
-                vec![],
-                vec![1, 2, 3, 4, 5, 6],
-            ]
-        );
+        assert_eq!(a, [1, 2, 3]);
+        assert_eq!(b, [-1]);
+        assert_eq!(c, [4, 5]);
     }
 
     #[test]
-    fn input_tuple() {
-        let source = AutoSource::from("4 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5");
+    #[should_panic(expected = "failed to get the next token")]
+    fn input_err_until_missing_sentinel() {
+        let source = AutoSource::from("1 2 3");
 
         input! {
             from source,
-            n: usize,
-            t: [(i32, i32, i32, i32, i32); n],
+            _a: [u32; until: 0],
         }
+    }
 
-        assert_eq!(
-            t,
-            [
-                (1, 2, 3, 4, 5),
-                (1, 2, 3, 4, 5),
-                (1, 2, 3, 4, 5),
-                (1, 2, 3, 4, 5)
-            ]
-        );
+    #[test]
+    fn input_peek() {
+        use crate::source::bytes::ByteSource;
+        use crate::source::line::LineSource;
+        use crate::source::once::OnceSource;
+        use crate::source::{BufRead, Source};
+
+        fn check<R: BufRead, S: Source<R>>(mut source: S) {
+            assert_eq!(source.peek_token(), Some("1"));
+            assert_eq!(source.peek_token(), Some("1"));
+            assert_eq!(source.next_if(|token| token == "0"), None);
+            input! {
+                from &mut source,
+                a: u8,
+            }
+            assert_eq!(a, 1);
+            assert_eq!(source.next_if(|token| token == "0"), Some("0"));
+            assert_eq!(source.peek_token(), Some("x"));
+            source.skip_tokens(1);
+            assert_eq!(source.peek_token(), None);
+            assert!(source.is_empty());
+        }
+
+        let input = " 1\n\n0 x\n";
+        check(LineSource::from(input));
+        check(OnceSource::from(input));
+        check(ByteSource::from(input));
     }
 
     #[test]
-    fn input_multiple_times() {
-        let mut source = AutoSource::from("4 1 2 3 4\n1 2\r\n\r\r\n3 4");
+    #[should_panic(expected = "failed to get the next token")]
+    fn input_err_skip_line() {
+        let source = AutoSource::from("1\n");
+        input! {
+            from source,
+            _: skip_line,
+            _: skip_line,
+        }
+    }
+
+    #[test]
+    fn input_end_line() {
+        use crate::source::line::LineSource;
+        use crate::source::once::OnceSource;
 
+        let input = "2 3\n\n1 2 3\n4 5 6\n";
+        let mut source = LineSource::from(input);
         input! {
             from &mut source,
-            n: usize,
+            (n, m): (usize, usize),
+            _: end_line,
         }
+        let a: Vec<Vec<u8>> = (0..n)
+            .map(|_| {
+                input! {
+                    from &mut source,
+                    row: [u8; m],
+                    _: end_line,
+                }
+                row
+            })
+            .collect();
+        assert_eq!(a, [[1, 2, 3], [4, 5, 6]]);
 
-        for i in 0..n {
-            input! {
-                from &mut source,
-                j: i32, k: i32,
-            }
+        input! {
+            from &mut LineSource::from(input),
+            _: end_line,
+            _: skip_line,
+            _: [u8; 3],
+            _: end_line,
+            b: [u8; 3],
+            _: end_line,
+        }
+        assert_eq!(b, [4, 5, 6]);
 
-            assert_eq!(j, if i % 2 == 0 { 1 } else { 3 });
-            assert_eq!(k, if i % 2 == 0 { 2 } else { 4 });
+        // sources not aware of lines don't check.
+        input! {
+            from OnceSource::from(input),
+            c: [u8; 3],
+            _: end_line,
         }
+        assert_eq!(c, [2, 3, 1]);
     }
 
     #[test]
-    fn input_iusize1() {
-        use crate::marker::Usize1;
+    fn input_end_line_after_peek() {
+        use crate::source::line::LineSource;
+        use crate::source::Source;
 
-        let mut source = AutoSource::from("4 1 2 3 4 5 6 7 8");
+        let mut source = LineSource::from("1 2\n3\n");
+        input! {
+            from &mut source,
+            a: [u8; 2],
+        }
+        assert_eq!(source.peek_token(), Some("3"));
+        assert!(!source.is_empty());
+        input! {
+            from &mut source,
+            _: end_line,
+            b: u8,
+            _: end_line,
+        }
+
+        assert_eq!(a, [1, 2]);
+        assert_eq!(b, 3);
+    }
+
+    #[test]
+    fn line_source_buffered_token() {
+        use crate::source::line::LineSource;
+        use crate::source::Source;
+
+        let mut source = LineSource::from("1 2\n3\n");
+        assert_eq!(source.buffered_token(), None);
+        assert_eq!(source.next_token(), Some("1"));
+        assert_eq!(source.buffered_token(), Some("2"));
+        assert_eq!(source.next_token(), Some("2"));
+        assert_eq!(source.buffered_token(), None);
+        assert_eq!(source.next_token(), Some("3"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to end the line 4: the values are read from the lines 3 to 4; maybe the line 3 has fewer tokens than expected"
+    )]
+    fn input_err_end_line_spilled() {
+        use crate::source::line::LineSource;
+
+        input! {
+            from LineSource::from("2 3\n\n1 2\n3 4 5 6\n"),
+            _: (usize, usize),
+            _: end_line,
+            _: [u8; 3],
+            _: end_line,
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to end the line 1: the line has more tokens than expected, from `3`"
+    )]
+    fn input_err_end_line_rest() {
+        use crate::source::line::LineSource;
+
+        input! {
+            from LineSource::from("1 2 3\n"),
+            _: (u8, u8),
+            _: end_line,
+        }
+    }
+
+    #[test]
+    fn input_assert_consumed() {
+        use crate::harness::with_source;
 
+        let mut source = AutoSource::from("1 2\n\n");
         input! {
             from &mut source,
-            n: usize,
+            _: [u8; 2],
         }
+        assert_input_consumed!(&mut source);
 
-        for i in 0..n {
+        with_source(AutoSource::from("3"), || {
             input! {
-                from &mut source,
-                from: Usize1, to: Usize1
+                _: u8,
             }
+            assert_input_consumed!();
+        });
+    }
 
-            assert_eq!(from, i * 2);
-            assert_eq!(to, i * 2 + 1);
+    #[test]
+    #[should_panic(expected = "failed to assert that the input is consumed: the next token is `3`")]
+    fn input_err_assert_consumed() {
+        use crate::harness::with_source;
+
+        with_source(AutoSource::from("1 2 3"), || {
+            input! {
+                _: [u8; 2],
+            }
+            assert_input_consumed!();
+        });
+    }
+
+    #[test]
+    fn input_nested() {
+        use crate::harness::with_source;
+        use crate::source::{BufRead, Readable, Source};
+        use crate::write::{to_text, Writable};
+        use crate::{is_stdin_empty, peek_stdin_token};
+
+        // reads the values from the stdin by itself, not from the given source.
+        struct Group;
+        impl Readable for Group {
+            type Output = Vec<u32>;
+            fn read<R: BufRead, S: Source<R>>(_: &mut S) -> Vec<u32> {
+                input! {
+                    n: usize,
+                    a: [u32; n],
+                }
+                assert!(!is_stdin_empty());
+                a
+            }
         }
+        impl Writable for Group {
+            fn write(a: &Vec<u32>, out: &mut String) {
+                usize::write(&a.len(), out);
+                for x in a {
+                    u32::write(x, out);
+                }
+            }
+        }
+        assert_eq!(to_text::<Group>(&vec![1, 2]), "2 1 2");
+
+        with_source(AutoSource::from("2 1 2 x 1 5 ."), || {
+            input! {
+                a: Group,
+                s: String,
+                b: Group,
+            }
+
+            assert_eq!(a, [1, 2]);
+            assert_eq!(s, "x");
+            assert_eq!(b, [5]);
+            assert_eq!(peek_stdin_token().as_deref(), Some("."));
+        });
     }
 
     #[test]
-    #[should_panic]
-    fn input_zero_as_usize1() {
-        use crate::marker::Usize1;
-        let mut source = AutoSource::from("0");
+    fn input_nested_after_token() {
+        use crate::harness::with_source;
+        use crate::source::line::LineSource;
+        use crate::source::{BufRead, Readable, Source};
+
+        // reads a token, and then the next lines by the nested `input!`.
+        struct Named;
+        impl Readable for Named {
+            type Output = (String, Vec<u32>);
+            fn read<R: BufRead, S: Source<R>>(source: &mut S) -> (String, Vec<u32>) {
+                let name = source.next_token_unwrap();
+                input! {
+                    a: [u32; 2],
+                }
+                (name.to_string(), a)
+            }
+        }
+
+        with_source(LineSource::from("hello\n1\n2\n"), || {
+            input! {
+                named: Named,
+            }
+            assert_eq!(named, ("hello".to_string(), vec![1, 2]));
+        });
+    }
+
+    #[test]
+    fn input_after_caught_panic() {
+        use crate::harness::with_source;
+        use crate::stdout_writer;
+        use std::panic;
+
+        with_source(AutoSource::from("x 1"), || {
+            let res = panic::catch_unwind(|| {
+                input! {
+                    _: u32,
+                }
+            });
+            assert!(res.is_err());
+
+            input! {
+                a: u32,
+            }
+            assert_eq!(a, 1);
+        });
+
+        let res = panic::catch_unwind(|| {
+            let _writer = stdout_writer();
+            panic!("oops");
+        });
+        assert!(res.is_err());
+        drop(stdout_writer());
+    }
+
+    #[test]
+    fn input_bounded() {
+        use crate::marker::{Bounded, DebugBounded, Usize1};
+
+        let source = AutoSource::from("3 1 100000 7 -5 18446744073709551615");
         input! {
-            from &mut source,
-            _v: Usize1,
+            from source,
+            n: {Bounded<usize, 1, 100>},
+            a: [{Bounded<u32, 1, 100_000>}; n - 1],
+            i: {Bounded<Usize1, 0, 6>},
+            b: {DebugBounded<i8, -5, 5>},
+            c: {Bounded<u64, 0, { u64::MAX as i128 }>},
         }
+
+        assert_eq!(n, 3);
+        assert_eq!(a, [1, 100_000]);
+        assert_eq!(i, 6);
+        assert_eq!(b, -5);
+        assert_eq!(c, u64::MAX);
     }
 
     #[test]
-    #[should_panic]
-    fn input_min_as_isize1() {
-        use crate::marker::Isize1;
-        let min_string = std::isize::MIN.to_string();
-        let mut source = AutoSource::from(&*min_string);
+    #[should_panic(
+        expected = "failed to read the value `0` of type `u32`: the value is not in `1..=100000`"
+    )]
+    fn input_err_bounded() {
+        use crate::marker::Bounded;
+
+        let source = AutoSource::from("0");
         input! {
-            from &mut source,
-            _v: Isize1,
+            from source,
+            _a: {Bounded<u32, 1, 100_000>},
         }
     }
 
     #[test]
-    fn input_mut() {
-        let mut source = AutoSource::from("8 1 2 3 4 5 6 7 8");
+    #[should_panic(expected = "the value is not in `0..=1`")]
+    fn input_err_bounded_overflow() {
+        use crate::marker::Bounded;
 
+        let source = AutoSource::from("340282366920938463463374607431768211455");
         input! {
-            from &mut source,
-            mut n: usize,
+            from source,
+            _a: {Bounded<u128, 0, 1>},
         }
+    }
 
-        let mut sum = 0;
-        while n > 0 {
-            input!(from &mut source, x: u32);
-            sum += x;
-            n -= 1;
+    #[test]
+    fn input_matches() {
+        use crate::marker::class::{AlphaNum, Digit, Lower, Upper};
+        use crate::marker::Matches;
+
+        let source = AutoSource::from("abc XYZ 0123 a1B2");
+        input! {
+            from source,
+            lower: Matches<Lower>,
+            upper: Matches<Upper>,
+            digit: Matches<Digit>,
+            alpha_num: Matches<AlphaNum>,
         }
-        assert_eq!(sum, 36);
+
+        assert_eq!(lower, "abc");
+        assert_eq!(upper, "XYZ");
+        assert_eq!(digit, "0123");
+        assert_eq!(alpha_num, "a1B2");
     }
 
     #[test]
-    fn input_with_complex_type() {
-        let mut source = AutoSource::from("Hello\n2\n3 1 2 3\n4 5 ab\n4");
+    #[should_panic(expected = "failed to read the string `abC`: `C` at 2 is not in [a-z]")]
+    fn input_err_matches() {
+        use crate::marker::class::Lower;
+        use crate::marker::Matches;
+
+        let source = AutoSource::from("abC");
+        input! {
+            from source,
+            _s: Matches<Lower>,
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    enum Query {}
+
+    #[cfg(feature = "regex")]
+    impl crate::marker::Pattern for Query {
+        const PATTERN: &'static str = "[+-][0-9]+";
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn input_matches_regex() {
+        use crate::marker::MatchesRegex;
+
+        let source = AutoSource::from("+12 -3");
+        input! {
+            from source,
+            a: [MatchesRegex<Query>; 2],
+        }
+
+        assert_eq!(a, ["+12", "-3"]);
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    #[should_panic(expected = "failed to read the string `+12-3`: it does not match `[+-][0-9]+`")]
+    fn input_err_matches_regex() {
+        use crate::marker::MatchesRegex;
+
+        let source = AutoSource::from("+12-3");
+        input! {
+            from source,
+            _a: MatchesRegex<Query>,
+        }
+    }
+
+    #[test]
+    fn input_saturating_and_checked() {
+        use crate::marker::{Checked, ParseError, Saturating};
+
+        let source = AutoSource::from("300 -300 +42 99999999999999999999 256 7 x");
+        input! {
+            from source,
+            a: [Saturating<u8>; 2],
+            b: Saturating<i64>,
+            c: Saturating<i64>,
+            d: [Checked<u8>; 2],
+            e: Checked<char>,
+        }
+
+        assert_eq!(a, [255, 0]);
+        assert_eq!(b, 42);
+        assert_eq!(c, i64::MAX);
+        let err = d[0].clone().unwrap_err();
+        assert_eq!(err.token, "256");
+        assert_eq!(err.type_name, "u8");
+        assert_eq!(
+            err.to_string(),
+            "failed to parse the input `256` to the value of type `u8`: number too large to fit in target type"
+        );
+        assert_eq!(d[1], Ok(7));
+        assert_eq!(e, Ok::<_, ParseError>('x'));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `1.5` as an integer of type `i32`")]
+    fn input_err_saturating() {
+        use crate::marker::Saturating;
+
+        let source = AutoSource::from("1.5");
+        input! {
+            from source,
+            _a: Saturating<i32>,
+        }
+    }
+
+    #[test]
+    fn input_modifier() {
+        use crate::modifier::{AssertSorted, Sorted};
+
+        let mut source = AutoSource::from("3 3 1 2\n2 5 3\n1 2 2");
         input! {
             from &mut source,
-            hello: crate::marker::Bytes,
-            from: crate::marker::Usize1,
-            vla: [crate::marker::Isize1],
-            tuple: (crate::marker::Usize1, crate::marker::Isize1, crate::marker::Chars),
-            unit: (crate::marker::Usize1),
+            n: usize,
+            a: [i32; n] as Sorted,
+            b: [i32] as Sorted,
+            c: [u8; 3] as AssertSorted
         }
-        assert_eq!(hello, b"Hello");
-        assert_eq!(from, 1);
-        assert_eq!(vla, [0, 1, 2]);
-        assert_eq!(tuple, (3, 4, vec!['a', 'b']));
-        assert_eq!(unit, (3,));
+
+        assert_eq!(a, [1, 2, 3]);
+        assert_eq!(b, [3, 5]);
+        assert_eq!(c, [1, 2, 2]);
     }
 
     #[test]
-    fn input_single_tt_pattern() {
-        let mut source = AutoSource::from("3 42 0\n1 2 3\n");
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn input_err_assert_sorted() {
+        use crate::modifier::AssertSorted;
+
+        let mut source = AutoSource::from("3 1 3 2");
         input! {
             from &mut source,
-            (n, mut k): (usize, usize),
-            _: usize,
-            xs: [u32; n],
+            n: usize,
+            _a: [i32; n] as AssertSorted,
         }
-        k += 1;
-        assert_eq!(n, 3);
-        assert_eq!(k, 43);
-        assert_eq!(xs, [1, 2, 3]);
+    }
+
+    #[test]
+    fn output_to_sink() {
+        use std::io::Write;
+
+        let mut buf = Vec::new();
+        output!(to buf, "{} ", 1);
+        outputln!(to buf, "{}", 2);
+        outputln!(to &mut buf, fixed(3), 0.5, 1.0 / 3.0);
+        outputln!(to buf);
+        crate::sink::Sink::flush(&mut buf).unwrap();
+        assert_eq!(buf, b"1 2\n0.500 0.333\n\n");
+
+        // `Write` in scope doesn't make the macros ambiguous.
+        let mut writer = std::io::BufWriter::new(Vec::new());
+        writeln!(writer, "a").unwrap();
+        outputln!(to writer, "{}", "b");
+        assert_eq!(writer.into_inner().unwrap(), b"a\nb\n");
+    }
+
+    #[test]
+    fn run_with_stack_returns_value() {
+        let source = AutoSource::from("3 1 2 3");
+        let sum = crate::run_with_stack(16 * 1024 * 1024, move || {
+            input! {
+                from source,
+                n: usize,
+                a: [u64; n],
+            }
+            a.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    #[should_panic(expected = "panic in the thread")]
+    fn run_with_stack_propagates_panic() {
+        crate::run_with_stack(16 * 1024 * 1024, || panic!("panic in the thread"));
     }
 
     #[test]
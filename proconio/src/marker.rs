@@ -7,8 +7,28 @@
 
 //! Declares special marker types.
 
-use crate::source::{Readable, Source};
-use std::io::BufRead;
+use crate::source::{
+    bulk, end_of_input, try_next_token, BufRead, FromTokens, ReadError, Readable, Source,
+    TryReadable,
+};
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::convert::TryInto;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::num::{IntErrorKind, ParseIntError};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::Hash;
 
 /// Chars: read a string as array of chars.
 pub enum Chars {}
@@ -18,6 +38,17 @@ impl Readable for Chars {
     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<char> {
         source.next_token_unwrap().chars().collect()
     }
+
+    fn read_into<R: BufRead, S: Source<R>>(source: &mut S, output: &mut Vec<char>) {
+        output.clear();
+        output.extend(source.next_token_unwrap().chars());
+    }
+}
+
+impl TryReadable for Chars {
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<Vec<char>, ReadError> {
+        Ok(try_next_token::<Chars, R, S>(source)?.chars().collect())
+    }
 }
 
 /// Bytes: read a string as array of bytes.
@@ -26,10 +57,68 @@ pub enum Bytes {}
 impl Readable for Bytes {
     type Output = Vec<u8>;
     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<u8> {
-        source.next_token_unwrap().bytes().collect()
+        source.next_token_bytes_unwrap().to_vec()
+    }
+
+    fn read_into<R: BufRead, S: Source<R>>(source: &mut S, output: &mut Vec<u8>) {
+        output.clear();
+        output.extend_from_slice(source.next_token_bytes_unwrap());
+    }
+}
+
+impl TryReadable for Bytes {
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<Vec<u8>, ReadError> {
+        if source.is_empty() {
+            return Err(end_of_input::<Bytes, R, S>(source));
+        }
+        Ok(source.next_token_bytes_unwrap().to_vec())
+    }
+}
+
+/// Line: read an entire line as is, keeping the spaces in it.  Output of reading has type
+/// `String`.
+///
+/// If a token in the current line is already read, the next line is read.  This needs a source
+/// supporting `Source::next_line()`, such as `AutoSource` and the stdin.
+pub enum Line {}
+
+impl Readable for Line {
+    type Output = String;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> String {
+        read_line(source).to_string()
+    }
+
+    fn read_into<R: BufRead, S: Source<R>>(source: &mut S, output: &mut String) {
+        output.clear();
+        output.push_str(read_line(source));
     }
 }
 
+/// LineBytes: read an entire line as array of bytes, keeping the spaces in it.  Output of reading
+/// has type `Vec<u8>`.
+///
+/// This is for the grids where a space is a cell, which `Bytes` can't read.  See `Line` for the
+/// line to be read.
+pub enum LineBytes {}
+
+impl Readable for LineBytes {
+    type Output = Vec<u8>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<u8> {
+        read_line(source).as_bytes().to_vec()
+    }
+
+    fn read_into<R: BufRead, S: Source<R>>(source: &mut S, output: &mut Vec<u8>) {
+        output.clear();
+        output.extend_from_slice(read_line(source).as_bytes());
+    }
+}
+
+fn read_line<R: BufRead, S: Source<R>>(source: &mut S) -> &str {
+    source
+        .next_line()
+        .expect("failed to read a line: reached the end of the input")
+}
+
 /// Usize1: 1-indexed usize.  Output of reading has type usize.
 pub enum Usize1 {}
 
@@ -37,12 +126,20 @@ impl Readable for Usize1 {
     type Output = usize;
     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> usize {
         // panic if the subtraction overflows
-        usize::read(source)
+        bulk::read_fast_one::<usize, R, S>(source)
             .checked_sub(1)
             .expect("attempted to read the value 0 as a Usize1")
     }
 }
 
+impl TryReadable for Usize1 {
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<usize, ReadError> {
+        usize::try_read(source)?.checked_sub(1).ok_or_else(|| {
+            ReadError::new::<Usize1>(source.position(), Some("0"), "the value is not 1-indexed")
+        })
+    }
+}
+
 /// Isize1: 1-indexed isize.  Output of reading has type isize.
 pub enum Isize1 {}
 
@@ -52,14 +149,980 @@ impl Readable for Isize1 {
         // FIXME: Which is appropriate, forbidding all negative values or only isize::MIN. For now
         // we disallow only isize::MIN.
         // ensure the value is more than isize::MIN, or subtract overflows.
-        isize::read(source).checked_sub(1).unwrap_or_else(|| {
+        bulk::read_fast_one::<isize, R, S>(source)
+            .checked_sub(1)
+            .unwrap_or_else(|| {
+                panic!(
+                    concat!(
+                        "attempted to read the value {} as a Isize1:",
+                        " the value is isize::MIN and cannot be decremented"
+                    ),
+                    isize::MIN,
+                )
+            })
+    }
+}
+
+impl TryReadable for Isize1 {
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<isize, ReadError> {
+        isize::try_read(source)?.checked_sub(1).ok_or_else(|| {
+            let token = isize::MIN.to_string();
+            let reason = "the value is isize::MIN and cannot be decremented";
+            ReadError::new::<Isize1>(source.position(), Some(&token), reason)
+        })
+    }
+}
+
+/// Digits: read a number as array of its decimal digits.  Output of reading has type `Vec<u8>`.
+///
+/// The most significant digit comes first: `1203` is read as `[1, 2, 0, 3]`.  The token must
+/// consist of ASCII digits only; a sign is not accepted.
+pub enum Digits {}
+
+impl Readable for Digits {
+    type Output = Vec<u8>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<u8> {
+        read_digits(source)
+    }
+}
+
+/// DigitsRev: read a number as array of its decimal digits in reversed order.  Output of reading
+/// has type `Vec<u8>`.
+///
+/// The least significant digit comes first: `1203` is read as `[3, 0, 2, 1]`, so that the index
+/// is the exponent of 10.
+pub enum DigitsRev {}
+
+impl Readable for DigitsRev {
+    type Output = Vec<u8>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<u8> {
+        let mut digits = read_digits(source);
+        digits.reverse();
+        digits
+    }
+}
+
+fn read_digits<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<u8> {
+    let token = source.next_token_bytes_unwrap();
+    token
+        .iter()
+        .map(|&b| match b {
+            b'0'..=b'9' => b - b'0',
+            _ => panic!(
+                "failed to read the input `{}` as digits: `{}` is not a digit",
+                String::from_utf8_lossy(token),
+                char::from(b)
+            ),
+        })
+        .collect()
+}
+
+/// Mod: an integer reduced modulo `P`.  Output of reading has type `u64`, in `0..P`.
+///
+/// The token is reduced digit by digit, so it can be longer than any primitive integer.  A
+/// negative value is also reduced into `0..P`: `-1` is read as `P - 1`.  The result can be passed
+/// to the constructor of your modint type.
+pub enum Mod<const P: u64> {}
+
+/// Mod998244353: an integer reduced modulo 998244353.  Output of reading has type `u64`.
+pub type Mod998244353 = Mod<998_244_353>;
+
+/// Mod1000000007: an integer reduced modulo 1000000007.  Output of reading has type `u64`.
+pub type Mod1000000007 = Mod<1_000_000_007>;
+
+impl<const P: u64> Readable for Mod<P> {
+    type Output = u64;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> u64 {
+        assert!(P > 0, "the modulus of `Mod` must be positive");
+
+        let token = source.next_token_unwrap();
+        let (negative, digits) = match token.as_bytes().first() {
+            Some(b'-') => (true, &token[1..]),
+            Some(b'+') => (false, &token[1..]),
+            _ => (false, token),
+        };
+        if digits.is_empty() {
+            panic!(
+                "failed to parse the input `{}` as an integer modulo {}: no digits",
+                token, P
+            );
+        }
+
+        let mut value = 0;
+        for c in digits.chars() {
+            let digit = c.to_digit(10).unwrap_or_else(|| {
+                panic!(
+                    "failed to parse the input `{}` as an integer modulo {}: `{}` is not a digit",
+                    token, P, c
+                )
+            });
+            value = ((u128::from(value) * 10 + u128::from(digit)) % u128::from(P)) as u64;
+        }
+
+        if negative && value != 0 {
+            P - value
+        } else {
+            value
+        }
+    }
+}
+
+/// Bounded: a value in `MIN..=MAX`.  Output of reading has type `T::Output`.
+///
+/// This checks your assumption about the constraints, such as `Bounded<u32, 1, 100_000>`, and
+/// panics with the value if it is out of the range.  `T` can be any type whose output is an
+/// integer, like `Bounded<Usize1, 0, 99>`.  The check is always done; use `DebugBounded` to skip
+/// it in release build.  In `input!`, wrap it with braces since it contains commas.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::marker::Bounded;
+/// # use proconio::source::auto::AutoSource;
+/// # let source = AutoSource::from("3 1 2 3");
+///
+/// input! {
+/// #   from source,
+///     n: {Bounded<usize, 1, 100_000>},
+///     a: [{Bounded<u32, 1, 1_000_000_000>}; n],
+/// }
+/// # assert_eq!(a, [1, 2, 3]);
+/// ```
+pub struct Bounded<T, const MIN: i128, const MAX: i128>(PhantomData<fn() -> T>);
+
+impl<T: Readable, const MIN: i128, const MAX: i128> Readable for Bounded<T, MIN, MAX>
+where
+    T::Output: TryInto<i128> + Copy + Debug,
+{
+    type Output = T::Output;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T::Output {
+        let value = T::read(source);
+        check_bounds::<T, MIN, MAX>(value);
+        value
+    }
+}
+
+/// DebugBounded: a value in `MIN..=MAX`, checked only in debug build.  Output of reading has type
+/// `T::Output`.
+///
+/// This is the same as `Bounded` in debug build, and the same as `T` in release build.
+pub struct DebugBounded<T, const MIN: i128, const MAX: i128>(PhantomData<fn() -> T>);
+
+impl<T: Readable, const MIN: i128, const MAX: i128> Readable for DebugBounded<T, MIN, MAX>
+where
+    T::Output: TryInto<i128> + Copy + Debug,
+{
+    type Output = T::Output;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T::Output {
+        let value = T::read(source);
+        if cfg!(debug_assertions) {
+            check_bounds::<T, MIN, MAX>(value);
+        }
+        value
+    }
+}
+
+fn check_bounds<T: Readable, const MIN: i128, const MAX: i128>(value: T::Output)
+where
+    T::Output: TryInto<i128> + Copy + Debug,
+{
+    let in_range = match value.try_into() {
+        Ok(v) => (MIN..=MAX).contains(&v),
+        Err(_) => false,
+    };
+    if !in_range {
+        panic!(
+            "failed to read the value `{:?}` of type `{}`: the value is not in `{}..={}`",
+            value,
+            type_name::<T>(),
+            MIN,
+            MAX
+        );
+    }
+}
+
+/// A set of characters, which `Matches` checks the characters of a string against.
+///
+/// Implement this for your own type to check the other characters.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::marker::{CharClass, Matches};
+/// # use proconio::source::auto::AutoSource;
+///
+/// enum Cell {}
+///
+/// impl CharClass for Cell {
+///     const DESCRIPTION: &'static str = "[.#]";
+///     fn contains(c: char) -> bool {
+///         c == '.' || c == '#'
+///     }
+/// }
+///
+/// # let source = AutoSource::from("#..#");
+/// input! {
+/// #   from source,
+///     s: Matches<Cell>,
+/// }
+/// # assert_eq!(s, "#..#");
+/// ```
+pub trait CharClass {
+    /// The description of the class shown on the panic, such as `[a-z]`.
+    const DESCRIPTION: &'static str;
+    fn contains(c: char) -> bool;
+}
+
+/// The common classes of characters for `Matches`.
+pub mod class {
+    use super::CharClass;
+
+    macro_rules! char_class {
+        ($(#[$attr:meta])* $name:ident, $description:expr, $contains:expr) => {
+            $(#[$attr])*
+            pub enum $name {}
+
+            impl CharClass for $name {
+                const DESCRIPTION: &'static str = $description;
+                fn contains(c: char) -> bool {
+                    $contains(c)
+                }
+            }
+        };
+    }
+
+    char_class!(
+        /// Lower: lowercase letters, `[a-z]`.
+        Lower,
+        "[a-z]",
+        |c: char| c.is_ascii_lowercase()
+    );
+    char_class!(
+        /// Upper: uppercase letters, `[A-Z]`.
+        Upper,
+        "[A-Z]",
+        |c: char| c.is_ascii_uppercase()
+    );
+    char_class!(
+        /// Alpha: letters, `[a-zA-Z]`.
+        Alpha,
+        "[a-zA-Z]",
+        |c: char| c.is_ascii_alphabetic()
+    );
+    char_class!(
+        /// Digit: decimal digits, `[0-9]`.
+        Digit,
+        "[0-9]",
+        |c: char| c.is_ascii_digit()
+    );
+    char_class!(
+        /// AlphaNum: letters and decimal digits, `[a-zA-Z0-9]`.
+        AlphaNum,
+        "[a-zA-Z0-9]",
+        |c: char| c.is_ascii_alphanumeric()
+    );
+}
+
+/// Matches: a string of which every character is in `C`.  Output of reading has type `String`.
+///
+/// This checks the guarantee of the problem like "`S` consists of lowercase English letters", and
+/// panics with the string if it is violated.  See `class` for the common classes.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::marker::{class::Lower, Matches};
+/// # use proconio::source::auto::AutoSource;
+///
+/// # let source = AutoSource::from("atcoder");
+/// input! {
+/// #   from source,
+///     s: Matches<Lower>,
+/// }
+/// # assert_eq!(s, "atcoder");
+/// ```
+pub struct Matches<C>(PhantomData<fn() -> C>);
+
+impl<C: CharClass> Readable for Matches<C> {
+    type Output = String;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> String {
+        let token = source.next_token_unwrap();
+        if let Some((pos, c)) = token.char_indices().find(|&(_, c)| !C::contains(c)) {
+            panic!(
+                "failed to read the string `{}`: `{}` at {} is not in {}",
+                token,
+                c,
+                pos,
+                C::DESCRIPTION
+            );
+        }
+        String::from(token)
+    }
+}
+
+/// A regular expression, which `MatchesRegex` checks a string against.
+#[cfg(feature = "regex")]
+pub trait Pattern {
+    /// The regular expression, which must match the whole string.
+    const PATTERN: &'static str;
+}
+
+/// MatchesRegex: a string matching the regular expression `P::PATTERN`.  Output of reading has
+/// type `String`.
+///
+/// The pattern must match the whole string.  The regular expression is compiled only once.  This
+/// requires `regex` feature.
+///
+/// ```
+/// # extern crate proconio;
+/// # #[cfg(feature = "regex")]
+/// # {
+/// use proconio::input;
+/// use proconio::marker::{MatchesRegex, Pattern};
+/// # use proconio::source::auto::AutoSource;
+///
+/// enum Ident {}
+///
+/// impl Pattern for Ident {
+///     const PATTERN: &'static str = "[a-z_][a-z0-9_]*";
+/// }
+///
+/// # let source = AutoSource::from("snake_case_2");
+/// input! {
+/// #   from source,
+///     name: MatchesRegex<Ident>,
+/// }
+/// # assert_eq!(name, "snake_case_2");
+/// # }
+/// ```
+#[cfg(feature = "regex")]
+pub struct MatchesRegex<P>(PhantomData<fn() -> P>);
+
+#[cfg(feature = "regex")]
+impl<P: Pattern> Readable for MatchesRegex<P> {
+    type Output = String;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> String {
+        use once_cell::sync::OnceCell;
+        use regex::Regex;
+        use std::sync::Mutex;
+
+        // the compiled patterns.  A static in a generic function is shared by all `P`.
+        static REGEXES: OnceCell<Mutex<HashMap<&'static str, Regex>>> = OnceCell::new();
+
+        let token = source.next_token_unwrap();
+        let mut regexes = REGEXES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let regex = regexes.entry(P::PATTERN).or_insert_with(|| {
+            Regex::new(&format!("^(?:{})$", P::PATTERN))
+                .unwrap_or_else(|e| panic!("failed to compile the pattern `{}`: {}", P::PATTERN, e))
+        });
+        if !regex.is_match(token) {
+            panic!(
+                "failed to read the string `{}`: it does not match `{}`",
+                token,
+                P::PATTERN
+            );
+        }
+        String::from(token)
+    }
+}
+
+/// ComplexPair: a complex number written as two tokens, the real part and the imaginary part.
+/// Output of reading has type `Complex<T::Output>`.
+///
+/// This requires `complex` feature.  See `complex` module for details.
+#[cfg(feature = "complex")]
+pub struct ComplexPair<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "complex")]
+impl<T: Readable> Readable for ComplexPair<T> {
+    type Output = num_complex::Complex<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let re = T::read(source);
+        let im = T::read(source);
+        num_complex::Complex::new(re, im)
+    }
+}
+
+/// ComplexToken: a complex number written as a single token such as `1+2i`, `-3.5j` or `4`.
+/// Output of reading has type `Complex<T>`.
+///
+/// This reads the same as `Complex<T>` itself, with the error message for complex numbers.  This
+/// requires `complex` feature.  See `complex` module for details.
+#[cfg(feature = "complex")]
+pub struct ComplexToken<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "complex")]
+impl<T> Readable for ComplexToken<T>
+where
+    num_complex::Complex<T>: FromStr,
+    <num_complex::Complex<T> as FromStr>::Err: Display,
+{
+    type Output = num_complex::Complex<T>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output {
+        let token = source.next_token_unwrap();
+        token.parse().unwrap_or_else(|e| {
+            panic!(
+                "failed to read the complex number `{}` of `{}`: {}",
+                token,
+                type_name::<T>(),
+                e
+            )
+        })
+    }
+}
+
+/// HhMm: clock time written as `HH:MM`.  Output of reading has type `u32`, the seconds from
+/// midnight.
+///
+/// The hour must be less than 24 and the minute must be less than 60.  Leading zeros are optional:
+/// `9:05` is read as `32700`.
+pub enum HhMm {}
+
+impl Readable for HhMm {
+    type Output = u32;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> u32 {
+        read_clock(source, 2, "HhMm")
+    }
+}
+
+/// HhMmSs: clock time written as `HH:MM:SS`.  Output of reading has type `u32`, the seconds from
+/// midnight.
+///
+/// The hour must be less than 24 and the minute and the second must be less than 60.
+pub enum HhMmSs {}
+
+impl Readable for HhMmSs {
+    type Output = u32;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> u32 {
+        read_clock(source, 3, "HhMmSs")
+    }
+}
+
+fn read_clock<R: BufRead, S: Source<R>>(source: &mut S, fields: usize, marker: &str) -> u32 {
+    const LIMITS: [u32; 3] = [24, 60, 60];
+    const UNITS: [u32; 3] = [3600, 60, 1];
+
+    let token = source.next_token_unwrap();
+    let values: Vec<&str> = token.split(':').collect();
+    let invalid = |reason: &str| -> ! {
+        panic!(
+            "failed to parse the input `{}` as a {}: {}",
+            token, marker, reason
+        )
+    };
+
+    if values.len() != fields {
+        invalid(&format!("expected {} fields separated by `:`", fields));
+    }
+
+    let mut seconds = 0;
+    for ((value, limit), unit) in values.iter().zip(&LIMITS).zip(&UNITS) {
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            invalid(&format!("`{}` is not a number", value));
+        }
+        match value.parse::<u32>() {
+            Ok(v) if v < *limit => seconds += v * unit,
+            _ => invalid(&format!("`{}` is out of range", value)),
+        }
+    }
+
+    seconds
+}
+
+/// Ymd: date written as `YYYY/MM/DD` or `YYYY-MM-DD`.  Output of reading has type
+/// `(u32, u32, u32)`, the year, the month and the day.
+///
+/// The month must be in `1..=12` and the day must exist in the month, taking leap years into
+/// account.  Leading zeros are optional.
+pub enum Ymd {}
+
+impl Readable for Ymd {
+    type Output = (u32, u32, u32);
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> (u32, u32, u32) {
+        let token = source.next_token_unwrap();
+        let invalid = |reason: &str| -> ! {
+            panic!("failed to parse the input `{}` as a Ymd: {}", token, reason)
+        };
+
+        let separator = if token.contains('/') { '/' } else { '-' };
+        let values: Vec<u32> = token
+            .split(separator)
+            .map(|value| {
+                if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+                    invalid(&format!("`{}` is not a number", value));
+                }
+                value
+                    .parse()
+                    .unwrap_or_else(|_| invalid(&format!("`{}` is out of range", value)))
+            })
+            .collect();
+
+        let (year, month, day) = match values[..] {
+            [year, month, day] => (year, month, day),
+            _ => invalid("expected 3 fields separated by `/` or `-`"),
+        };
+        if !(1..=12).contains(&month) {
+            invalid(&format!("month {} is out of range", month));
+        }
+        if !(1..=days_in_month(year, month)).contains(&day) {
+            invalid(&format!("day {} is out of range", day));
+        }
+
+        (year, month, day)
+    }
+}
+
+// `u32::is_multiple_of()` is too new to be used here.
+#[allow(unknown_lints, clippy::manual_is_multiple_of)]
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// LooseF64: f64 written in a loose format.  Output of reading has type f64.
+///
+/// In addition to the formats accepted by `f64::from_str()` such as `1e9` or `+3.5`, a comma is
+/// accepted as a decimal separator (`3,5` is read as `3.5`).  If the token contains both a comma
+/// and a dot, commas are considered as thousands separators and ignored (`1,234.5` is read as
+/// `1234.5`).
+pub enum LooseF64 {}
+
+impl Readable for LooseF64 {
+    type Output = f64;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> f64 {
+        let token = source.next_token_unwrap();
+        let normalized = if token.contains('.') {
+            token.replace(',', "")
+        } else {
+            token.replace(',', ".")
+        };
+
+        normalized.parse().unwrap_or_else(|e| {
             panic!(
-                concat!(
-                    "attempted to read the value {} as a Isize1:",
-                    " the value is isize::MIN and cannot be decremented"
-                ),
-                std::isize::MIN,
+                "failed to parse the input `{}` as a LooseF64: {:?}",
+                token, e
             )
         })
     }
 }
+
+/// Integer types which can be parsed from a string in the specified radix.
+///
+/// This is implemented for all primitive integer types and used by `Hex`, `Oct` and `Bin`.
+pub trait FromStrRadix: Sized {
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_from_str_radix {
+    ($($ty:ty)*) => {
+        $(
+            impl FromStrRadix for $ty {
+                fn from_str_radix(src: &str, radix: u32) -> Result<$ty, ParseIntError> {
+                    <$ty>::from_str_radix(src, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_radix!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Integer types which can be clamped to their range.
+///
+/// This is implemented for all primitive integer types and used by `Saturating`.
+pub trait Saturate: FromStrRadix {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_saturate {
+    ($($ty:ident)*) => {
+        $(
+            impl Saturate for $ty {
+                const MIN: $ty = $ty::MIN;
+                const MAX: $ty = $ty::MAX;
+            }
+        )*
+    };
+}
+
+impl_saturate!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+/// Saturating: an integer clamped to the range of `T`.  Output of reading has type `T`.
+///
+/// A value too large for `T` is read as `T::MAX`, and a value too small, including a negative value
+/// for an unsigned type, is read as `T::MIN`, instead of panicking.  Tokens which are not integers
+/// still panic.
+pub struct Saturating<T>(PhantomData<fn() -> T>);
+
+impl<T: Saturate> Readable for Saturating<T> {
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        let token = source.next_token_unwrap();
+        T::from_str_radix(token, 10).unwrap_or_else(|e| match e.kind() {
+            IntErrorKind::PosOverflow => T::MAX,
+            IntErrorKind::NegOverflow => T::MIN,
+            // a negative integer for an unsigned type.
+            _ if is_negative_integer(token) => T::MIN,
+            _ => panic!(
+                "failed to parse the input `{}` as an integer of type `{}`: {}",
+                token,
+                type_name::<T>(),
+                e
+            ),
+        })
+    }
+}
+
+fn is_negative_integer(token: &str) -> bool {
+    match token.strip_prefix('-') {
+        Some(digits) => !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// The error of `Checked`, with the token and the type which failed to be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The token failed to be parsed.
+    pub token: String,
+    /// The name of the type to which the token is parsed.
+    pub type_name: &'static str,
+    /// The reason of the failure, which is the error of `FromStr` in text.
+    pub reason: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse the input `{}` to the value of type `{}`: {}",
+            self.token, self.type_name, self.reason
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Checked: a value parsed by `T::from_str()`, or the error.  Output of reading has type
+/// `Result<T, ParseError>`.
+///
+/// Unlike reading `T` directly, this doesn't panic on an invalid token such as an overflowing
+/// integer.  The token is consumed even if it is invalid.
+pub struct Checked<T>(PhantomData<fn() -> T>);
+
+impl<T: FromStr> Readable for Checked<T>
+where
+    T::Err: Display,
+{
+    type Output = Result<T, ParseError>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<T, ParseError> {
+        let token = source.next_token_unwrap();
+        token.parse().map_err(|e: T::Err| ParseError {
+            token: String::from(token),
+            type_name: type_name::<T>(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+fn read_radix<T: FromStrRadix, R: BufRead, S: Source<R>>(
+    source: &mut S,
+    radix: u32,
+    prefixes: &[&str],
+) -> T {
+    let token = source.next_token_unwrap();
+    let (sign, digits) = match token.as_bytes().first() {
+        Some(b'+') | Some(b'-') => token.split_at(1),
+        _ => ("", token),
+    };
+    let digits = prefixes
+        .iter()
+        .find_map(|prefix| digits.strip_prefix(prefix))
+        .unwrap_or(digits);
+
+    T::from_str_radix(&format!("{}{}", sign, digits), radix).unwrap_or_else(|e| {
+        panic!(
+            "failed to parse the input `{}` as an integer of type `{}` in radix {}: {}",
+            token,
+            type_name::<T>(),
+            radix,
+            e
+        )
+    })
+}
+
+/// Hex: an integer written in hexadecimal.  Output of reading has type `T`.
+///
+/// Both upper and lower case letters are accepted.  An optional prefix `0x` or `0X` is ignored.
+pub struct Hex<T>(PhantomData<fn() -> T>);
+
+impl<T: FromStrRadix> Readable for Hex<T> {
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        read_radix(source, 16, &["0x", "0X"])
+    }
+}
+
+/// Oct: an integer written in octal.  Output of reading has type `T`.
+///
+/// An optional prefix `0o` is ignored.
+pub struct Oct<T>(PhantomData<fn() -> T>);
+
+impl<T: FromStrRadix> Readable for Oct<T> {
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        read_radix(source, 8, &["0o"])
+    }
+}
+
+/// Bin: an integer written in binary.  Output of reading has type `T`.
+///
+/// An optional prefix `0b` is ignored.
+pub struct Bin<T>(PhantomData<fn() -> T>);
+
+impl<T: FromStrRadix> Readable for Bin<T> {
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        read_radix(source, 2, &["0b"])
+    }
+}
+
+/// FromStrReadable: a value parsed by `T::from_str()`.  Output of reading has type `T`.
+///
+/// Any `FromStr` type whose error implements `Debug`, such as `std::net::Ipv4Addr` or
+/// `std::net::SocketAddr`, is already `Readable` by itself.  This marker only requires the error
+/// to implement `Display`, and reports it in the panic message by `Display`.
+pub struct FromStrReadable<T>(PhantomData<fn() -> T>);
+
+impl<T: FromStr> Readable for FromStrReadable<T>
+where
+    T::Err: Display,
+{
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        let token = source.next_token_unwrap();
+        token.parse().unwrap_or_else(|e| {
+            panic!(
+                "failed to parse the input `{}` to the value of type `{}`: {}",
+                token,
+                type_name::<T>(),
+                e
+            )
+        })
+    }
+}
+
+/// Tokens: a value parsed from `T::TOKENS` tokens by `FromTokens`.  Output of reading has type
+/// `T`.
+pub struct Tokens<T>(PhantomData<fn() -> T>);
+
+impl<T: FromTokens> Readable for Tokens<T> {
+    type Output = T;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
+        let tokens: Vec<String> = (0..T::TOKENS)
+            .map(|_| String::from(source.next_token_unwrap()))
+            .collect();
+        let tokens: Vec<&str> = tokens.iter().map(String::as_str).collect();
+        T::from_tokens(&tokens).unwrap_or_else(|e| {
+            panic!(
+                "failed to parse the tokens {:?} to the value of type `{}`: {:?}",
+                tokens,
+                type_name::<T>(),
+                e
+            )
+        })
+    }
+}
+
+/// ArrayOf: `N` elements.  Output of reading has type `[T::Output; N]`.
+///
+/// Unlike `[T; n]` in `input!`, the length is fixed at compile time.  In `input!`, `{[T; N]}` is
+/// read as this, and `#[derive_readable]` reads a field of type `[T; N]` as this too.
+pub struct ArrayOf<T, const N: usize>(PhantomData<fn() -> T>);
+
+impl<T: Readable, const N: usize> Readable for ArrayOf<T, N> {
+    type Output = [T::Output; N];
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> [T::Output; N] {
+        let items: Vec<T::Output> = (0..N).map(|_| T::read(source)).collect();
+        match items.try_into() {
+            Ok(array) => array,
+            Err(_) => unreachable!(concat!(
+                "the number of the elements differs from the length of the array.  ",
+                "This is a bug in `proconio`.  ",
+                "Please report this issue from ",
+                "<https://github.com/statiolake/proconio-rs/issues>."
+            )),
+        }
+    }
+}
+
+/// BoxOf: a value in `Box`.  Output of reading has type `Box<T::Output>`.
+///
+/// `#[derive_readable]` reads a field of type `Box<T>` as this.
+pub struct BoxOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for BoxOf<T> {
+    type Output = Box<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Box<T::Output> {
+        Box::new(T::read(source))
+    }
+}
+
+/// RcOf: a value in `Rc`.  Output of reading has type `Rc<T::Output>`.
+///
+/// `#[derive_readable]` reads a field of type `Rc<T>` as this.
+pub struct RcOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for RcOf<T> {
+    type Output = Rc<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Rc<T::Output> {
+        Rc::new(T::read(source))
+    }
+}
+
+/// ArcOf: a value in `Arc`.  Output of reading has type `Arc<T::Output>`.
+///
+/// `#[derive_readable]` reads a field of type `Arc<T>` as this.
+pub struct ArcOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for ArcOf<T> {
+    type Output = Arc<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Arc<T::Output> {
+        Arc::new(T::read(source))
+    }
+}
+
+fn read_collection<T: Readable, C: FromIterator<T::Output>, R: BufRead, S: Source<R>>(
+    source: &mut S,
+) -> C {
+    let len = usize::read(source);
+    (0..len).map(|_| T::read(source)).collect()
+}
+
+/// LenPrefixed: the length followed by the elements.  Output of reading has type
+/// `Vec<T::Output>`.
+///
+/// This is the same as `[T]` in `input!`, but can be used anywhere a type is expected, such as
+/// `[LenPrefixed<Usize1>; n]`, in a tuple, or as a field of `#[derive_readable]`.
+pub struct LenPrefixed<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for LenPrefixed<T> {
+    type Output = Vec<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Vec<T::Output> {
+        read_collection::<T, _, _, _>(source)
+    }
+}
+
+/// HashSetOf: the length followed by the elements of a set.  Output of reading has type
+/// `HashSet<T::Output>`.  This requires `std` feature.
+#[cfg(feature = "std")]
+pub struct HashSetOf<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "std")]
+impl<T: Readable> Readable for HashSetOf<T>
+where
+    T::Output: Eq + Hash,
+{
+    type Output = HashSet<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> HashSet<T::Output> {
+        read_collection::<T, _, _, _>(source)
+    }
+}
+
+/// BTreeSetOf: the length followed by the elements of a set.  Output of reading has type
+/// `BTreeSet<T::Output>`.
+pub struct BTreeSetOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for BTreeSetOf<T>
+where
+    T::Output: Ord,
+{
+    type Output = BTreeSet<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> BTreeSet<T::Output> {
+        read_collection::<T, _, _, _>(source)
+    }
+}
+
+/// VecDequeOf: the length followed by the elements of a deque.  Output of reading has type
+/// `VecDeque<T::Output>`.
+pub struct VecDequeOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for VecDequeOf<T> {
+    type Output = VecDeque<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> VecDeque<T::Output> {
+        read_collection::<T, _, _, _>(source)
+    }
+}
+
+/// BinaryHeapOf: the length followed by the elements of a max-heap.  Output of reading has type
+/// `BinaryHeap<T::Output>`.
+pub struct BinaryHeapOf<T>(PhantomData<fn() -> T>);
+
+impl<T: Readable> Readable for BinaryHeapOf<T>
+where
+    T::Output: Ord,
+{
+    type Output = BinaryHeap<T::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> BinaryHeap<T::Output> {
+        read_collection::<T, _, _, _>(source)
+    }
+}
+
+fn read_map<K: Readable, V: Readable, C, R: BufRead, S: Source<R>>(source: &mut S) -> C
+where
+    C: FromIterator<(K::Output, V::Output)>,
+{
+    let len = usize::read(source);
+    (0..len)
+        .map(|_| {
+            let key = K::read(source);
+            let value = V::read(source);
+            (key, value)
+        })
+        .collect()
+}
+
+/// HashMapOf: the length followed by the pairs of a key and a value.  Output of reading has type
+/// `HashMap<K::Output, V::Output>`.  This requires `std` feature.
+///
+/// If a key appears more than once, the last value is stored.  Since the type contains a comma,
+/// write it in braces in `input!`: `map: {HashMapOf<String, i64>}`.
+#[cfg(feature = "std")]
+pub struct HashMapOf<K, V>(PhantomData<fn() -> (K, V)>);
+
+#[cfg(feature = "std")]
+impl<K: Readable, V: Readable> Readable for HashMapOf<K, V>
+where
+    K::Output: Eq + Hash,
+{
+    type Output = HashMap<K::Output, V::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> HashMap<K::Output, V::Output> {
+        read_map::<K, V, _, _, _>(source)
+    }
+}
+
+/// BTreeMapOf: the length followed by the pairs of a key and a value.  Output of reading has type
+/// `BTreeMap<K::Output, V::Output>`.
+///
+/// If a key appears more than once, the last value is stored.  Since the type contains a comma,
+/// write it in braces in `input!`: `map: {BTreeMapOf<String, i64>}`.
+pub struct BTreeMapOf<K, V>(PhantomData<fn() -> (K, V)>);
+
+impl<K: Readable, V: Readable> Readable for BTreeMapOf<K, V>
+where
+    K::Output: Ord,
+{
+    type Output = BTreeMap<K::Output, V::Output>;
+    fn read<R: BufRead, S: Source<R>>(source: &mut S) -> BTreeMap<K::Output, V::Output> {
+        read_map::<K, V, _, _, _>(source)
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Persistent memo of the input and output of the last run.
+//!
+//! This is a local development helper.  When you are refactoring your solution, you may want to
+//! make sure the new version still prints the same answers for the inputs you have already tried.
+//! Set the environment variable `PROCONIO_MEMO` to some id (the name of the sample, for example)
+//! and run your solution as usual.  The input read from stdin by `input!` and the output written
+//! by `output!` and `outputln!` are recorded under the directory `PROCONIO_MEMO_DIR`
+//! (`.proconio-memo` by default).  The output of the previous run with the same id is kept, so
+//! that you can compare the two runs by `diff_last()`.
+//!
+//! ```text
+//! $ PROCONIO_MEMO=sample1 cargo run < sample1.txt
+//! $ # ... refactor the solution ...
+//! $ PROCONIO_MEMO=sample1 cargo run < sample1.txt
+//! ```
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::memo;
+//!
+//! // the outputs are compared token-wise, so the difference of whitespaces is ignored.
+//! assert_eq!(memo::diff_tokens("1 2\n3\n", "1 2 3"), None);
+//!
+//! let mismatch = memo::diff_tokens("1 2 3", "1 5 3").unwrap();
+//! assert_eq!(mismatch.position, 1);
+//! assert_eq!(mismatch.old.as_deref(), Some("2"));
+//! assert_eq!(mismatch.new.as_deref(), Some("5"));
+//! ```
+//!
+//! The recording starts when the stdin or the output is used first, so set the variable before
+//! the run.  The output printed by `print!`, `println!` or `#[fastout]` is not recorded.  The
+//! memos can also be stored explicitly by `save()`, or in a directory other than
+//! `PROCONIO_MEMO_DIR` by `MemoDir`.  If `PROCONIO_MEMO` is not set, nothing is recorded, so it
+//! is safe to submit the code as is.
+
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// The environment variable specifying the id of the current run.
+pub const ID_VAR: &str = "PROCONIO_MEMO";
+
+/// The environment variable specifying the directory where memos are stored.
+pub const DIR_VAR: &str = "PROCONIO_MEMO_DIR";
+
+const DEFAULT_DIR: &str = ".proconio-memo";
+const INPUT_FILE: &str = "input.txt";
+const OUTPUT_FILE: &str = "output.txt";
+const PREVIOUS_OUTPUT_FILE: &str = "output.prev.txt";
+
+/// A stored memo for an id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Memo {
+    /// The input consumed by the last run.
+    pub input: String,
+
+    /// The output produced by the last run.
+    pub output: String,
+
+    /// The output produced by the run before the last one, if any.
+    pub previous_output: Option<String>,
+}
+
+/// The first token where two outputs differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// 0-indexed position of the differing token.
+    pub position: usize,
+
+    /// The token in the old output.  `None` if the old output is shorter.
+    pub old: Option<String>,
+
+    /// The token in the new output.  `None` if the new output is shorter.
+    pub new: Option<String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |token: &Option<String>| match token {
+            Some(token) => format!("`{}`", token),
+            None => "end of output".to_string(),
+        };
+
+        write!(
+            f,
+            "token #{} differs: {} -> {}",
+            self.position,
+            show(&self.old),
+            show(&self.new)
+        )
+    }
+}
+
+/// Returns the id of the current run specified by `PROCONIO_MEMO`.
+pub fn current_id() -> Option<String> {
+    env::var(ID_VAR).ok().filter(|id| !id.is_empty())
+}
+
+/// Saves the input and output of the current run if `PROCONIO_MEMO` is set.
+///
+/// Returns `Ok(false)` without touching the filesystem if the id is not specified.
+pub fn save(input: &str, output: &str) -> io::Result<bool> {
+    match current_id() {
+        Some(id) => save_as(&id, input, output).map(|_| true),
+        None => Ok(false),
+    }
+}
+
+/// Saves the input and output for the specified id under `PROCONIO_MEMO_DIR`.
+///
+/// The output stored so far is kept as the previous output.
+pub fn save_as(id: &str, input: &str, output: &str) -> io::Result<()> {
+    MemoDir::from_env().save(id, input, output)
+}
+
+/// Loads the memo stored for the specified id under `PROCONIO_MEMO_DIR`.
+pub fn load(id: &str) -> io::Result<Memo> {
+    MemoDir::from_env().load(id)
+}
+
+/// Compares the outputs of the last two runs for the specified id under `PROCONIO_MEMO_DIR`.
+///
+/// Returns `Ok(None)` if the outputs are the same or there is only one run so far.
+pub fn diff_last(id: &str) -> io::Result<Option<Mismatch>> {
+    MemoDir::from_env().diff_last(id)
+}
+
+/// The directory where the memos are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoDir {
+    root: PathBuf,
+}
+
+impl MemoDir {
+    /// Creates a handle of the memos stored under `root`.
+    pub fn new<P: Into<PathBuf>>(root: P) -> MemoDir {
+        MemoDir { root: root.into() }
+    }
+
+    /// Creates a handle of the memos stored under `PROCONIO_MEMO_DIR` (`.proconio-memo` by
+    /// default).
+    pub fn from_env() -> MemoDir {
+        let root = env::var_os(DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_DIR));
+        MemoDir::new(root)
+    }
+
+    /// Saves the input and output for the specified id.
+    ///
+    /// The output stored so far is kept as the previous output.
+    pub fn save(&self, id: &str, input: &str, output: &str) -> io::Result<()> {
+        self.create_input(id)?.write_all(input.as_bytes())?;
+        self.create_output(id)?.write_all(output.as_bytes())
+    }
+
+    /// Loads the memo stored for the specified id.
+    pub fn load(&self, id: &str) -> io::Result<Memo> {
+        let dir = self.root.join(id);
+        let previous_output = match fs::read_to_string(dir.join(PREVIOUS_OUTPUT_FILE)) {
+            Ok(output) => Some(output),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Memo {
+            input: fs::read_to_string(dir.join(INPUT_FILE))?,
+            output: fs::read_to_string(dir.join(OUTPUT_FILE))?,
+            previous_output,
+        })
+    }
+
+    /// Compares the outputs of the last two runs for the specified id.
+    ///
+    /// Returns `Ok(None)` if the outputs are the same or there is only one run so far.
+    pub fn diff_last(&self, id: &str) -> io::Result<Option<Mismatch>> {
+        let memo = self.load(id)?;
+        Ok(memo
+            .previous_output
+            .as_ref()
+            .and_then(|previous| diff_tokens(previous, &memo.output)))
+    }
+
+    // Creates the file to store the input for the id.
+    fn create_input(&self, id: &str) -> io::Result<File> {
+        let dir = self.root.join(id);
+        fs::create_dir_all(&dir)?;
+        File::create(dir.join(INPUT_FILE))
+    }
+
+    // Creates the file to store the output for the id, keeping the current one as the previous
+    // output.
+    fn create_output(&self, id: &str) -> io::Result<File> {
+        let dir = self.root.join(id);
+        fs::create_dir_all(&dir)?;
+
+        let output_path = dir.join(OUTPUT_FILE);
+        if output_path.exists() {
+            fs::rename(&output_path, dir.join(PREVIOUS_OUTPUT_FILE))?;
+        }
+        File::create(output_path)
+    }
+}
+
+// Creates the file recording the input read from stdin, if `PROCONIO_MEMO` is set.
+pub(crate) fn input_record() -> Option<File> {
+    let id = current_id()?;
+    let file = MemoDir::from_env()
+        .create_input(&id)
+        .unwrap_or_else(|e| panic!("failed to record the input for the memo `{}`: {}", id, e));
+    Some(file)
+}
+
+// Creates the file recording the output, if `PROCONIO_MEMO` is set.
+pub(crate) fn output_record() -> Option<File> {
+    let id = current_id()?;
+    let file = MemoDir::from_env()
+        .create_output(&id)
+        .unwrap_or_else(|e| panic!("failed to record the output for the memo `{}`: {}", id, e));
+    Some(file)
+}
+
+// Writes the output to both the stdout and the memo.
+pub(crate) struct TeeOutput<W, M> {
+    pub(crate) out: W,
+    pub(crate) memo: M,
+}
+
+impl<W: Write, M: Write> Write for TeeOutput<W, M> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.out.write(buf)?;
+        self.memo.write_all(&buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()?;
+        self.memo.flush()
+    }
+}
+
+/// Compares two outputs token-wise and returns the first mismatch.
+pub fn diff_tokens(old: &str, new: &str) -> Option<Mismatch> {
+    let mut old_tokens = old.split_whitespace();
+    let mut new_tokens = new.split_whitespace();
+
+    let mut position = 0;
+    loop {
+        match (old_tokens.next(), new_tokens.next()) {
+            (None, None) => return None,
+            (old, new) if old != new => {
+                return Some(Mismatch {
+                    position,
+                    old: old.map(str::to_string),
+                    new: new.map(str::to_string),
+                })
+            }
+            _ => position += 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_shorter_output() {
+        let mismatch = diff_tokens("1 2 3", "1 2").unwrap();
+        assert_eq!(mismatch.position, 2);
+        assert_eq!(mismatch.old.as_deref(), Some("3"));
+        assert_eq!(mismatch.new, None);
+        assert_eq!(
+            mismatch.to_string(),
+            "token #2 differs: `3` -> end of output"
+        );
+    }
+
+    #[test]
+    fn save_and_diff_last() {
+        let root = env::temp_dir().join(format!("proconio-memo-test-{}", std::process::id()));
+        let memos = MemoDir::new(&root);
+
+        memos.save("sample1", "3\n1 2 3\n", "6\n").unwrap();
+        assert_eq!(memos.diff_last("sample1").unwrap(), None);
+
+        memos.save("sample1", "3\n1 2 3\n", "7\n").unwrap();
+        let memo = memos.load("sample1").unwrap();
+        assert_eq!(memo.input, "3\n1 2 3\n");
+        assert_eq!(memo.output, "7\n");
+        assert_eq!(memo.previous_output.as_deref(), Some("6\n"));
+        assert_eq!(memos.diff_last("sample1").unwrap().unwrap().position, 0);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,546 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Declares modifiers applied to arrays while reading.
+//!
+//! A modifier is written after `as` following an array type in `input!`.  It receives the elements
+//! of the array as they are read, and decides what value the variable finally has.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! use proconio::modifier::Sorted;
+//! # let source = AutoSource::from("5 3 1 4 1 5");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     a: [i32; n] as Sorted, // `a` is Vec<i32> sorted in ascending order.
+//! }
+//!
+//! assert_eq!(a, [1, 1, 3, 4, 5]);
+//! ```
+//!
+//! To collect the elements into another collection, use `collect()` with the type of the
+//! collection, just like `Iterator::collect()`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! use proconio::modifier::collect;
+//! use std::collections::BTreeSet;
+//! # let source = AutoSource::from("5 3 1 4 1 5");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     s: [i32; n] as collect::<BTreeSet<_>>(),
+//! }
+//!
+//! assert_eq!(s.into_iter().collect::<Vec<_>>(), [1, 3, 4, 5]);
+//! ```
+//!
+//! An array of pairs can be collected into a map in the same way.
+//!
+//! ```
+//! # extern crate proconio;
+//! # use proconio::source::auto::AutoSource;
+//! use proconio::input;
+//! use proconio::modifier::collect;
+//! use std::collections::HashMap;
+//! # let source = AutoSource::from("2 alice 3 bob 5");
+//!
+//! input! {
+//! #   from source,
+//!     n: usize,
+//!     m: [(String, i64); n] as collect::<HashMap<_, _>>(),
+//! }
+//!
+//! assert_eq!(m["bob"], 5);
+//! ```
+//!
+//! You can define your own modifier by implementing `Modifier` trait.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::convert::TryInto;
+use core::fmt::Debug;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+/// A trait representing how to build the value from the elements of an array.
+pub trait Modifier<T> {
+    type Output;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Self::Output;
+}
+
+/// Sorted: sort the array in ascending order.  Output of reading has type `Vec<T>`.
+pub struct Sorted;
+
+impl<T: Ord> Modifier<T> for Sorted {
+    type Output = Vec<T>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<T> {
+        let mut res: Vec<T> = items.collect();
+        res.sort();
+        res
+    }
+}
+
+/// AssertSorted: the array is guaranteed to be sorted in ascending order.  Output of reading has
+/// type `Vec<T>`.
+///
+/// The array is not sorted; instead, it is checked to be sorted in debug build.
+pub struct AssertSorted;
+
+impl<T: Ord> Modifier<T> for AssertSorted {
+    type Output = Vec<T>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<T> {
+        let res: Vec<T> = items.collect();
+        debug_assert!(
+            res.windows(2).all(|w| w[0] <= w[1]),
+            "the array read as AssertSorted is not sorted"
+        );
+        res
+    }
+}
+
+/// Indexed: attach the 0-based index to each element.  Output of reading has type
+/// `Vec<(usize, T)>`.
+///
+/// This is for sorting the array while remembering the original positions.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::Indexed;
+/// # let source = AutoSource::from("3 30 10 20");
+///
+/// input! {
+/// #   from source,
+///     n: usize,
+///     mut a: [i64; n] as Indexed,
+/// }
+///
+/// a.sort_by_key(|&(_, x)| x);
+/// assert_eq!(a, [(1, 10), (2, 20), (0, 30)]);
+/// ```
+pub struct Indexed;
+
+impl<T> Modifier<T> for Indexed {
+    type Output = Vec<(usize, T)>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<(usize, T)> {
+        items.enumerate().collect()
+    }
+}
+
+/// Unzip: split the array of pairs into two arrays.  Output of reading has type
+/// `(Vec<A>, Vec<B>)`.
+///
+/// The pairs are split while reading, without storing the array of pairs.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::Unzip;
+/// # let source = AutoSource::from("3 1 -1 2 -4 3 -9");
+///
+/// input! {
+/// #   from source,
+///     n: usize,
+///     (xs, ys): [(i64, i64); n] as Unzip,
+/// }
+///
+/// assert_eq!(xs, [1, 2, 3]);
+/// assert_eq!(ys, [-1, -4, -9]);
+/// ```
+pub struct Unzip;
+
+impl<A, B> Modifier<(A, B)> for Unzip {
+    type Output = (Vec<A>, Vec<B>);
+    fn modify<I: Iterator<Item = (A, B)>>(self, items: I) -> (Vec<A>, Vec<B>) {
+        items.unzip()
+    }
+}
+
+/// Collect: collect the array into `C`.  Output of reading has type `C`.
+///
+/// This is usually created by `collect()`.
+pub struct Collect<C>(PhantomData<fn() -> C>);
+
+/// Creates `Collect` modifier collecting the array into `C`.
+pub fn collect<C>() -> Collect<C> {
+    Collect(PhantomData)
+}
+
+impl<T, C: FromIterator<T>> Modifier<T> for Collect<C> {
+    type Output = C;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> C {
+        items.collect()
+    }
+}
+
+/// PrefixSum: the prefix sums of the array in `i64`.  Output of reading has type `Vec<i64>`.
+///
+/// The result has `n + 1` elements, where the `i`-th element is the sum of the first `i` elements,
+/// so the sum of `a[l..r]` is `s[r] - s[l]`.  The elements are converted to `i64` before being
+/// added, so the elements of a smaller type don't overflow.  Elements not fitting in `i64`, such
+/// as `u64` greater than `i64::MAX`, cause panic, and so does the sum overflowing `i64`.  To
+/// accumulate in another type, such as `i128`, use `prefix_sum()`.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::{prefix_sum, PrefixSum};
+/// # let source = AutoSource::from("3 1 2 3 2 200 200");
+///
+/// input! {
+/// #   from source,
+///     n: usize,
+///     a: [u32; n] as PrefixSum,
+///     b: [u8] as prefix_sum::<u32>(),
+/// }
+///
+/// assert_eq!(a, [0, 1, 3, 6]);
+/// assert_eq!(b, [0, 200, 400]);
+/// ```
+pub struct PrefixSum;
+
+impl<T> Modifier<T> for PrefixSum
+where
+    T: TryInto<i64> + Debug + Copy,
+{
+    type Output = Vec<i64>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<i64> {
+        prefix_sum::<i64>().modify(items)
+    }
+}
+
+/// PrefixSumOf: the prefix sums of the array in `A`.  Output of reading has type `Vec<A>`.
+///
+/// This is usually created by `prefix_sum()`.  See `PrefixSum` for details.
+pub struct PrefixSumOf<A>(PhantomData<fn() -> A>);
+
+/// Creates `PrefixSumOf` modifier accumulating the array in `A`.
+pub fn prefix_sum<A>() -> PrefixSumOf<A> {
+    PrefixSumOf(PhantomData)
+}
+
+impl<T, A> Modifier<T> for PrefixSumOf<A>
+where
+    T: TryInto<A> + Debug + Copy,
+    A: CheckedAdd + Default + Copy,
+{
+    type Output = Vec<A>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<A> {
+        let mut res = vec![A::default()];
+        let mut sum = A::default();
+        for (i, item) in items.enumerate() {
+            let value = item.try_into().unwrap_or_else(|_| {
+                panic!(
+                    "failed to add `{:?}` to the prefix sum: the value does not fit in `{}`",
+                    item,
+                    type_name::<A>()
+                )
+            });
+            sum = sum.checked_add(value).unwrap_or_else(|| {
+                panic!(
+                    "failed to add `{:?}` at index {} to the prefix sum: the sum overflows `{}`",
+                    item,
+                    i,
+                    type_name::<A>()
+                )
+            });
+            res.push(sum);
+        }
+        res
+    }
+}
+
+/// The types the prefix sums are accumulated in by `prefix_sum()`.
+///
+/// The sum is checked not to overflow.  Implement this to accumulate in your own type.
+pub trait CheckedAdd: Sized {
+    /// Adds `rhs`, returning `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_add {
+    (int: $($ty:ty)*; float: $($fty:ty)*) => {
+        $(
+            impl CheckedAdd for $ty {
+                fn checked_add(self, rhs: $ty) -> Option<$ty> {
+                    <$ty>::checked_add(self, rhs)
+                }
+            }
+        )*
+        $(
+            impl CheckedAdd for $fty {
+                fn checked_add(self, rhs: $fty) -> Option<$fty> {
+                    Some(self + rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_add!(
+    int: i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize;
+    float: f32 f64
+);
+
+/// PrefixSum2D: the 2D prefix sums of the 2D array in `i64`.  Output of reading has type `Grid2D`.
+///
+/// The sums are computed while reading, and the elements are converted to `i64` as `PrefixSum`.
+/// All rows must have the same length.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::PrefixSum2D;
+/// # let source = AutoSource::from("2 3  1 2 3  4 5 6");
+///
+/// input! {
+/// #   from source,
+///     h: usize,
+///     w: usize,
+///     g: [[u32; w]; h] as PrefixSum2D,
+/// }
+///
+/// assert_eq!(g.sum(0, 0, 2, 3), 21);
+/// assert_eq!(g.sum(1, 1, 2, 3), 11); // 5 + 6
+/// assert_eq!(g.get(0, 2), 3);
+/// ```
+pub struct PrefixSum2D;
+
+impl<T> Modifier<Vec<T>> for PrefixSum2D
+where
+    T: TryInto<i64> + Debug + Copy,
+{
+    type Output = Grid2D;
+    fn modify<I: Iterator<Item = Vec<T>>>(self, items: I) -> Grid2D {
+        let mut prefix: Vec<Vec<i64>> = Vec::new();
+        for row in items {
+            let sums = PrefixSum.modify(row.into_iter());
+            if prefix.is_empty() {
+                prefix.push(vec![0; sums.len()]);
+            }
+
+            let above = &prefix[prefix.len() - 1];
+            assert_eq!(
+                above.len(),
+                sums.len(),
+                "failed to build the 2D prefix sums: the rows have different lengths"
+            );
+            let row = prefix.len() - 1;
+            let res = above
+                .iter()
+                .zip(sums)
+                .map(|(&a, s)| {
+                    a.checked_add(s).unwrap_or_else(|| {
+                        panic!(
+                            "failed to build the 2D prefix sums: the sum overflows `i64` at row {}",
+                            row
+                        )
+                    })
+                })
+                .collect();
+            prefix.push(res);
+        }
+        if prefix.is_empty() {
+            prefix.push(vec![0]);
+        }
+        Grid2D { prefix }
+    }
+}
+
+/// The 2D prefix sums of a grid, built by `PrefixSum2D`.
+///
+/// The ranges are half-open: `sum(r1, c1, r2, c2)` is the sum of the cells `(r, c)` where
+/// `r1 <= r < r2` and `c1 <= c < c2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid2D {
+    prefix: Vec<Vec<i64>>,
+}
+
+impl Grid2D {
+    /// Gets the number of the rows.
+    pub fn height(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    /// Gets the number of the columns.
+    pub fn width(&self) -> usize {
+        self.prefix[0].len() - 1
+    }
+
+    /// Gets the sum of the rectangle `[r1, r2) × [c1, c2)`.
+    pub fn sum(&self, r1: usize, c1: usize, r2: usize, c2: usize) -> i64 {
+        assert!(
+            r1 <= r2 && r2 <= self.height() && c1 <= c2 && c2 <= self.width(),
+            "the rectangle [{}, {}) × [{}, {}) is out of the {} × {} grid",
+            r1,
+            r2,
+            c1,
+            c2,
+            self.height(),
+            self.width()
+        );
+        let p = &self.prefix;
+        p[r2][c2] - p[r1][c2] - p[r2][c1] + p[r1][c1]
+    }
+
+    /// Gets the value of the cell `(r, c)`.
+    pub fn get(&self, r: usize, c: usize) -> i64 {
+        self.sum(r, c, r + 1, c + 1)
+    }
+}
+
+/// Counts: count the occurrences of each value in `0..=max`.  Output of reading has type
+/// `Vec<usize>`.
+///
+/// The result has `max + 1` elements, where the `x`-th element is the number of `x` in the array.
+/// The array itself is not stored.  Values greater than `max` (or negative) cause panic.  For
+/// larger values, use `CountMap`.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::Counts;
+/// # let source = AutoSource::from("5 3 1 4 1 5");
+///
+/// input! {
+/// #   from source,
+///     n: usize,
+///     cnt: [u32; n] as Counts(5),
+/// }
+///
+/// assert_eq!(cnt, [0, 2, 0, 1, 1, 1]);
+/// ```
+pub struct Counts(pub usize);
+
+impl<T: TryInto<usize> + Debug + Copy> Modifier<T> for Counts {
+    type Output = Vec<usize>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<usize> {
+        let Counts(max) = self;
+        let mut res = vec![0; max + 1];
+        for item in items {
+            match item.try_into() {
+                Ok(x) if x <= max => res[x] += 1,
+                _ => panic!(
+                    "failed to count `{:?}`: the value is not in `0..={}`",
+                    item, max
+                ),
+            }
+        }
+        res
+    }
+}
+
+/// CountMap: count the occurrences of each value.  Output of reading has type
+/// `HashMap<T, usize>`.
+///
+/// Unlike `Counts`, the values can be anything hashable.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::CountMap;
+/// # let source = AutoSource::from("4 1000000000 -1 1000000000 apple");
+///
+/// input! {
+/// #   from source,
+///     n: usize,
+///     cnt: [i64; n - 1] as CountMap,
+///     _: String,
+/// }
+///
+/// assert_eq!(cnt[&1_000_000_000], 2);
+/// assert_eq!(cnt.get(&0), None);
+/// ```
+#[cfg(feature = "std")]
+pub struct CountMap;
+
+#[cfg(feature = "std")]
+impl<T: Hash + Eq> Modifier<T> for CountMap {
+    type Output = HashMap<T, usize>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> HashMap<T, usize> {
+        let mut res = HashMap::new();
+        for item in items {
+            *res.entry(item).or_insert(0) += 1;
+        }
+        res
+    }
+}
+
+/// Padded: surround the array with the specified value.  Output of reading has type `Vec<T>` for
+/// an array, `Vec<Vec<T>>` for a 2D array.
+///
+/// This is handy for 1-indexed DP tables or grids with sentinels.  `[i64; n] as Padded(0)` has
+/// `n + 2` elements, where the read values are placed at `1..=n`.  For a 2D array `[[i64; m]; n]`,
+/// the result is `(n + 2) × (m + 2)` and the read values are placed at `[1..=n][1..=m]`.  If the
+/// rows have different lengths, every row is padded to the longest one.
+///
+/// ```
+/// # extern crate proconio;
+/// # use proconio::source::auto::AutoSource;
+/// use proconio::input;
+/// use proconio::modifier::Padded;
+/// # let source = AutoSource::from("1 2 3 4");
+///
+/// input! {
+/// #   from source,
+///     grid: [[i32; 2]; 2] as Padded(0),
+/// }
+///
+/// assert_eq!(grid, [[0, 0, 0, 0], [0, 1, 2, 0], [0, 3, 4, 0], [0, 0, 0, 0]]);
+/// ```
+pub struct Padded<T>(pub T);
+
+impl<T: Clone> Modifier<T> for Padded<T> {
+    type Output = Vec<T>;
+    fn modify<I: Iterator<Item = T>>(self, items: I) -> Vec<T> {
+        let Padded(fill) = self;
+        let mut res = vec![fill.clone()];
+        res.extend(items);
+        res.push(fill);
+        res
+    }
+}
+
+impl<T: Clone> Modifier<Vec<T>> for Padded<T> {
+    type Output = Vec<Vec<T>>;
+    fn modify<I: Iterator<Item = Vec<T>>>(self, items: I) -> Vec<Vec<T>> {
+        let Padded(fill) = self;
+        let rows: Vec<Vec<T>> = items.collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0) + 2;
+
+        let mut res = Vec::with_capacity(rows.len() + 2);
+        res.push(vec![fill.clone(); width]);
+        for row in rows {
+            let mut padded = Vec::with_capacity(width);
+            padded.push(fill.clone());
+            padded.extend(row);
+            padded.resize(width, fill.clone());
+            res.push(padded);
+        }
+        res.push(vec![fill; width]);
+        res
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines helpers for formatting the output of `output!` and `outputln!`.
+//!
+//! Answers like "print with absolute error at most 1e-6" require printing floats with fixed
+//! precision everywhere.  `Fixed` formats the value with the specified number of digits after the
+//! decimal point, and `fixed()` uses the crate-wide default precision, which can be configured
+//! once at the start of the program by `set_default_precision()`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::out::{self, Fixed};
+//!
+//! assert_eq!(Fixed(1.0 / 3.0, 4).to_string(), "0.3333");
+//!
+//! out::set_default_precision(2);
+//! assert_eq!(out::fixed(2.0 / 3.0).to_string(), "0.67");
+//! ```
+//!
+//! `output!` and `outputln!` also accept `fixed(precision)` or `fixed` in place of the format
+//! string.  In this form, the values are printed separated by a space.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::outputln;
+//!
+//! let x = 1.0 / 3.0;
+//! outputln!(fixed(10), x, 2.0 * x); // "0.3333333333 0.6666666667\n"
+//! # proconio::flush_output();
+//! ```
+//!
+//! With `fastfmt` feature, `Fast` formats the numbers by `itoa` and `ryu` instead of `core::fmt`,
+//! which is much faster when printing millions of numbers.  `output!` and `outputln!` accept
+//! `fast` in place of the format string, as `fixed`.
+//!
+//! ```
+//! # extern crate proconio;
+//! # #[cfg(feature = "fastfmt")]
+//! # {
+//! use proconio::out::Fast;
+//! use proconio::outputln;
+//!
+//! assert_eq!(Fast(-42).to_string(), "-42");
+//! assert_eq!(Fast(0.1).to_string(), "0.1");
+//! outputln!(fast, 1, 2, 3); // "1 2 3\n"
+//! # proconio::flush_output();
+//! # }
+//! ```
+//!
+//! The newline written by `outputln!`, `output_lines!` and `caseln!` is `\n` on every platform.
+//! It can be changed to `\r\n` by `set_newline()`.  `set_final_newline(true)` makes sure the
+//! output ends with a newline when it is flushed at exit by `flush_on_exit()`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::out::{self, Newline};
+//!
+//! out::set_newline(Newline::CrLf);
+//! assert_eq!(out::newline(), "\r\n");
+//! out::set_newline(Newline::Lf);
+//! out::set_final_newline(true);
+//! ```
+//!
+//! When the test cases are solved in parallel, `CaseBuffer` keeps the output of each case apart
+//! and writes them in the order of the cases at the end.  Each case is written by the output
+//! macros with `to buf.case(i),`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::caseln;
+//! use proconio::out::CaseBuffer;
+//! use std::thread;
+//!
+//! let answers = [10, 20, 30];
+//! let mut buf = CaseBuffer::new(answers.len());
+//! thread::scope(|s| {
+//!     for (i, ans) in answers.iter().enumerate().rev() {
+//!         let buf = &buf;
+//!         s.spawn(move || caseln!(to buf.case(i), i, "{}", ans));
+//!     }
+//! });
+//!
+//! let mut output = Vec::new();
+//! buf.write_to(&mut output).unwrap();
+//! assert_eq!(output, b"Case #1: 10\nCase #2: 20\nCase #3: 30\n");
+//! ```
+
+use crate::sink::Sink;
+use std::fmt;
+use std::io::{self, Write};
+use std::mem;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+static DEFAULT_PRECISION: AtomicUsize = AtomicUsize::new(10);
+static CRLF: AtomicBool = AtomicBool::new(false);
+static FINAL_NEWLINE: AtomicBool = AtomicBool::new(false);
+
+/// Formats the value with the fixed number of digits after the decimal point.
+///
+/// `Fixed(x, p)` is the same with `format!("{:.p$}", x)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fixed<T>(pub T, pub usize);
+
+impl<T: fmt::Display> fmt::Display for Fixed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}", self.1, self.0)
+    }
+}
+
+/// Formats the number by `itoa` for the integers and `ryu` for the floats, bypassing `core::fmt`.
+///
+/// The integers are printed the same as `{}`.  The floats are printed in the shortest form which
+/// reads back to the same value, like `0.1`, `1.0` or `1e-7`; use `Fixed` if the exponent is not
+/// accepted.  The width like `{:>5}` is applied.  This needs `fastfmt` feature.
+#[cfg(feature = "fastfmt")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fast<T>(pub T);
+
+/// The numbers formatted by `Fast`.
+#[cfg(feature = "fastfmt")]
+pub trait FastFormat: Copy {
+    /// Formats the number to the formatter.
+    fn fast_fmt(self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+#[cfg(feature = "fastfmt")]
+macro_rules! impl_fast_format {
+    ($buffer:ident: $($t:ty),*) => {
+        $(
+            impl FastFormat for $t {
+                fn fast_fmt(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.pad($buffer::Buffer::new().format(self))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "fastfmt")]
+impl_fast_format!(itoa: u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+#[cfg(feature = "fastfmt")]
+impl_fast_format!(ryu: f32, f64);
+
+#[cfg(feature = "fastfmt")]
+impl<T: FastFormat> fmt::Display for Fast<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fast_fmt(f)
+    }
+}
+
+/// Formats the value with the default precision.
+pub fn fixed<T>(value: T) -> Fixed<T> {
+    Fixed(value, default_precision())
+}
+
+/// Gets the default precision used by `fixed()`.  It is 10 unless configured.
+pub fn default_precision() -> usize {
+    DEFAULT_PRECISION.load(Ordering::Relaxed)
+}
+
+/// Sets the default precision used by `fixed()`.
+pub fn set_default_precision(precision: usize) {
+    DEFAULT_PRECISION.store(precision, Ordering::Relaxed);
+}
+
+/// Writes each item followed by `newline()` to the sink.
+///
+/// This is `output_lines!(iter)`.  The items are written in a single pass without joining them to
+/// a string.
+pub fn write_lines<S, I>(sink: &mut S, iter: I) -> io::Result<()>
+where
+    S: Sink + ?Sized,
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    let mut writer = crate::sink::__Writer(sink);
+    for item in iter {
+        writer.write_fmt(format_args!("{}{}", item, newline()))?;
+    }
+    Ok(())
+}
+
+/// Writes the items separated by `sep`, followed by `newline()`, to the sink.
+///
+/// This is `output_lines!(iter, sep)`.  A newline is written even if there is no item.
+pub fn write_joined<S, I>(sink: &mut S, iter: I, sep: &str) -> io::Result<()>
+where
+    S: Sink + ?Sized,
+    I: IntoIterator,
+    I::Item: fmt::Display,
+{
+    let mut writer = crate::sink::__Writer(sink);
+    let mut iter = iter.into_iter();
+    if let Some(first) = iter.next() {
+        writer.write_fmt(format_args!("{}", first))?;
+        for item in iter {
+            writer.write_fmt(format_args!("{}{}", sep, item))?;
+        }
+    }
+    writer.write_fmt(format_args!("{}", newline()))
+}
+
+/// The newline written by `outputln!`, `output_lines!` and `caseln!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`, the default.
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+/// Gets the newline written by `outputln!`, `output_lines!` and `caseln!`.
+pub fn newline() -> &'static str {
+    if CRLF.load(Ordering::Relaxed) {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Sets the newline written by `outputln!`, `output_lines!` and `caseln!`.
+///
+/// The newline is `Newline::Lf` unless configured, regardless of the platform.  The newlines in
+/// the format string, like `output!("a\nb")`, are written as is.
+pub fn set_newline(newline: Newline) {
+    CRLF.store(newline == Newline::CrLf, Ordering::Relaxed);
+}
+
+/// Checks if a newline is appended to the output at exit.  It is `false` unless configured.
+pub fn final_newline() -> bool {
+    FINAL_NEWLINE.load(Ordering::Relaxed)
+}
+
+/// Makes sure the output ends with a newline.
+///
+/// If this is set to `true` and the output written to the stdout so far does not end with a
+/// newline, the guard of `flush_on_exit()` (and `#[proconio::main]`) writes the newline of
+/// `newline()` before flushing.  Nothing is written if there is no output.
+pub fn set_final_newline(enabled: bool) {
+    FINAL_NEWLINE.store(enabled, Ordering::Relaxed);
+}
+
+/// Buffer storing the output of each test case separately.
+///
+/// The cases can be written in any order and from any thread through `case()`.  The output is
+/// written in the order of the cases by `flush()` or `write_to()`.
+#[derive(Debug, Default)]
+pub struct CaseBuffer {
+    cases: Vec<Mutex<Vec<u8>>>,
+}
+
+impl CaseBuffer {
+    /// Creates `CaseBuffer` for `t` cases.
+    pub fn new(t: usize) -> CaseBuffer {
+        CaseBuffer {
+            cases: (0..t).map(|_| Mutex::new(Vec::new())).collect(),
+        }
+    }
+
+    /// Gets the number of the cases.
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    /// Check if there is no case.
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+
+    /// Gets the writer for the output of the `i`-th (0-based) case.
+    ///
+    /// The writer locks the case, so the writers for different cases can be used at the same time.
+    /// Each output is appended to what has been written to the case.
+    pub fn case(&self, i: usize) -> CaseWriter<'_> {
+        let case = self.cases.get(i).unwrap_or_else(|| {
+            panic!(
+                "failed to get the case #{}: there are only {} cases",
+                i,
+                self.cases.len()
+            )
+        });
+        CaseWriter(case.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Writes the output of all cases in order to the sink, and clears them.
+    pub fn write_to<S: Sink + ?Sized>(&mut self, sink: &mut S) -> io::Result<()> {
+        let mut writer = crate::sink::__Writer(sink);
+        for case in &mut self.cases {
+            let case = mem::take(case.get_mut().unwrap_or_else(PoisonError::into_inner));
+            writer.write_fmt(format_args!("{}", String::from_utf8_lossy(&case)))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the output of all cases in order to the buffered stdout, and clears them.
+    pub fn flush(&mut self) {
+        self.write_to(&mut crate::stdout_writer())
+            .expect("failed to write to the stdout");
+    }
+}
+
+/// Writer for the output of a case.  Returned by `CaseBuffer::case()`.
+pub struct CaseWriter<'a>(MutexGuard<'a, Vec<u8>>);
+
+impl Write for CaseWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
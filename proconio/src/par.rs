@@ -0,0 +1,139 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Solves independent test cases in parallel.
+//!
+//! This module requires `parallel` feature enabled.  When the test cases are independent and the
+//! time limit is tight, they can be solved by multiple threads with `rayon`.  The input is still
+//! read sequentially, since where a case ends is known only by reading it; `cases_par!` first
+//! reads all cases, then solves them in parallel, and finally writes the answers in the order of
+//! the cases.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::{cases_par, input};
+//! # use proconio::harness::with_source;
+//! # use proconio::source::auto::AutoSource;
+//! # let output = proconio::capture_output(|| with_source(AutoSource::from("2\n3\n1 2 3\n1\n5\n"), || {
+//!
+//! cases_par! {
+//!     // reads a case.  This runs sequentially.
+//!     |_case| {
+//!         input! {
+//!             n: usize,
+//!             a: [i64; n],
+//!         }
+//!         a
+//!     }
+//!     // solves the case and returns the answer.  This runs in parallel.
+//!     => |_case, a: Vec<i64>| a.iter().sum::<i64>()
+//! }
+//! # }));
+//! # assert_eq!(output, "6\n5\n");
+//! ```
+//!
+//! Each answer is written by `outputln!("{}", answer)`, so anything implementing `Display` can be
+//! returned.  Don't write the output in the solving closure; it runs in an arbitrary order.  For
+//! the answers of multiple lines, return a `String`, or write them to `out::CaseBuffer`.
+//!
+//! As `input_cases!`, `prefix,` or `prefix("format string"),` at the beginning writes `Case #k: `
+//! (or the specified format with `k`) before each answer.
+
+use rayon::prelude::*;
+
+/// Solves the inputs in parallel and returns the answers in the order of the inputs.
+///
+/// `solve` takes the 0-based index of the case and its input.
+pub fn solve_cases<T, U, F>(inputs: Vec<T>, solve: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(usize, T) -> U + Sync + Send,
+{
+    inputs
+        .into_par_iter()
+        .enumerate()
+        .map(|(case, input)| solve(case, input))
+        .collect()
+}
+
+/// read the number of test cases `t` and `t` cases, then solve them in parallel.
+///
+/// basic syntax is:
+/// ```text
+/// cases_par! {
+///     |case| { input! { ... }; (values...) }  // reads a case, sequentially.
+///     => |case, (values...)| { ...; answer }  // solves a case, in parallel.
+/// }
+/// ```
+/// See the document of [`par`](par/index.html) module for details.
+#[macro_export]
+macro_rules! cases_par {
+    (prefix, $($rest:tt)*) => {
+        $crate::cases_par!(@run ["Case #{}: "] $($rest)*)
+    };
+    (prefix($format:expr), $($rest:tt)*) => {
+        $crate::cases_par!(@run [$format] $($rest)*)
+    };
+    (@run [$($format:expr)?] $read:expr => $solve:expr $(,)?) => {{
+        $crate::input! {
+            t: usize,
+        }
+        let inputs: $crate::__Vec<_> = (0..t).map($read).collect();
+        let answers = $crate::par::solve_cases(inputs, $solve);
+        for (case, answer) in answers.into_iter().enumerate() {
+            $($crate::output!($format, case + 1);)?
+            $crate::outputln!("{}", answer);
+        }
+    }};
+    ($read:expr => $solve:expr $(,)?) => {
+        $crate::cases_par!(@run [] $read => $solve)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::with_source;
+    use crate::source::auto::AutoSource;
+    use crate::{capture_output, input};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn answers_in_order() {
+        let answers = solve_cases((0..100u64).collect(), |case, x| {
+            // make the earlier cases finish later.
+            thread::sleep(Duration::from_micros(100 - x));
+            (case, x * x)
+        });
+        assert!(answers
+            .iter()
+            .enumerate()
+            .all(|(i, &(case, y))| case == i && y == (i * i) as u64));
+    }
+
+    #[test]
+    fn prefix() {
+        let output = capture_output(|| {
+            with_source(AutoSource::from("3\nab 1\ncd 2\nef 3"), || {
+                cases_par! {
+                    prefix,
+                    |_| {
+                        input!(s: String, k: usize);
+                        (s, k)
+                    }
+                    => |case, (s, k): (String, usize)| format!("{} {}", s.repeat(k), case)
+                }
+            })
+        });
+        assert_eq!(
+            output,
+            "Case #1: ab 0\nCase #2: cdcd 1\nCase #3: efefef 2\n"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Declares combinators to implement `Readable` in one line.
+//!
+//! Each combinator reads the values of the types given by the parameters of the closure, and
+//! transforms them by the closure.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::read::{read_map, read_pair_with};
+//! use proconio::source::auto::AutoSource;
+//! use proconio::source::{BufRead, Readable, Source};
+//! use std::ops::Range;
+//!
+//! // a value read as its square.
+//! struct Square;
+//! impl Readable for Square {
+//!     type Output = i64;
+//!     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> i64 {
+//!         read_map(source, |x: i64| x * x)
+//!     }
+//! }
+//!
+//! // a 1-indexed closed interval read as a half-open range.
+//! struct Interval;
+//! impl Readable for Interval {
+//!     type Output = Range<usize>;
+//!     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Range<usize> {
+//!         read_pair_with(source, |l: usize, r: usize| l - 1..r)
+//!     }
+//! }
+//!
+//! let source = AutoSource::from("-3 2 4");
+//! input! {
+//!     from source,
+//!     x: Square,
+//!     range: Interval,
+//! }
+//!
+//! assert_eq!(x, 9);
+//! assert_eq!(range, 1..4);
+//! ```
+//!
+//! The types of the parameters must be read as themselves, like the primitives and `String`.  For
+//! markers, read them by `Readable::read()` first and transform the value as usual.
+
+use crate::source::{BufRead, Readable, Source};
+
+/// Reads a value of `T` and transforms it by `f`.
+pub fn read_map<T, U, R, S, F>(source: &mut S, f: F) -> U
+where
+    T: Readable<Output = T>,
+    R: BufRead,
+    S: Source<R>,
+    F: FnOnce(T) -> U,
+{
+    f(T::read(source))
+}
+
+/// Reads a value of `T` and returns it if it satisfies `predicate`, just like `Option::filter()`.
+///
+/// The value is consumed even if it does not satisfy `predicate`.
+pub fn read_filtered<T, R, S, P>(source: &mut S, predicate: P) -> Option<T>
+where
+    T: Readable<Output = T>,
+    R: BufRead,
+    S: Source<R>,
+    P: FnOnce(&T) -> bool,
+{
+    Some(T::read(source)).filter(predicate)
+}
+
+/// Reads a value of `A` and then a value of `B`, and combines them by `f`.
+pub fn read_pair_with<A, B, U, R, S, F>(source: &mut S, f: F) -> U
+where
+    A: Readable<Output = A>,
+    B: Readable<Output = B>,
+    R: BufRead,
+    S: Source<R>,
+    F: FnOnce(A, B) -> U,
+{
+    let a = A::read(source);
+    let b = B::read(source);
+    f(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::slice::SliceSource;
+    use alloc::string::String;
+
+    #[test]
+    fn combinators() {
+        let mut source = SliceSource::from("3 -1 4 ab 5");
+        assert_eq!(read_map(&mut source, |x: u8| x * 2), 6);
+        assert_eq!(read_filtered(&mut source, |&x: &i32| x >= 0), None);
+        assert_eq!(read_filtered(&mut source, |&x: &i32| x >= 0), Some(4));
+        assert_eq!(
+            read_pair_with(&mut source, |s: String, x: u8| (s.len(), x)),
+            (2, 5)
+        );
+    }
+}
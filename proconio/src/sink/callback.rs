@@ -0,0 +1,93 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a sink forwarding the output to a callback.
+//!
+//! `CallbackSink` passes each complete line to the callback as soon as it is written, and the rest
+//! when it is flushed or dropped.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::outputln;
+//! use proconio::sink::callback::CallbackSink;
+//!
+//! let mut lines = Vec::new();
+//! let mut sink = CallbackSink::new(|text: &str| lines.push(text.to_string()));
+//! outputln!(to sink, "{}", 1);
+//! outputln!(to sink, "{} {}", 2, 3);
+//! drop(sink);
+//! assert_eq!(lines, ["1\n", "2 3\n"]);
+//! ```
+
+use std::io::{self, Write};
+
+/// Sink forwarding the output to a callback.
+pub struct CallbackSink<F: FnMut(&str)> {
+    callback: F,
+    buffer: Vec<u8>,
+}
+
+impl<F: FnMut(&str)> CallbackSink<F> {
+    /// Creates `CallbackSink` forwarding the output to the callback.
+    pub fn new(callback: F) -> CallbackSink<F> {
+        CallbackSink {
+            callback,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn forward(&mut self, text: &[u8]) {
+        if !text.is_empty() {
+            (self.callback)(&String::from_utf8_lossy(text));
+        }
+    }
+}
+
+impl<F: FnMut(&str)> Write for CallbackSink<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if let Some(last) = self.buffer.iter().rposition(|&b| b == b'\n') {
+            let rest = self.buffer.split_off(last + 1);
+            let lines = std::mem::replace(&mut self.buffer, rest);
+            self.forward(&lines);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let rest = std::mem::take(&mut self.buffer);
+        self.forward(&rest);
+        Ok(())
+    }
+}
+
+impl<F: FnMut(&str)> Drop for CallbackSink<F> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward_lines() {
+        let mut texts = Vec::new();
+        {
+            let mut sink = CallbackSink::new(|text: &str| texts.push(text.to_string()));
+            sink.write_all(b"a b").unwrap();
+            sink.write_all(b" c\nd\ne").unwrap();
+            sink.write_all("\u{3042}".as_bytes()).unwrap();
+            sink.flush().unwrap();
+            sink.write_all(b"f").unwrap();
+        }
+
+        assert_eq!(texts, ["a b c\nd\n", "e\u{3042}", "f"]);
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines `Sink`, the destination of `output!` and `outputln!`.
+//!
+//! By default, the output macros write to the buffered stdout.  Like `from` of `input!`, you can
+//! specify another destination by `to sink,` at the beginning.  The sink is borrowed mutably, so
+//! you can use it after the output.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::{output, outputln};
+//!
+//! let mut buf = Vec::new();
+//! output!(to buf, "{} ", 1);
+//! outputln!(to buf, "{}", 2);
+//! outputln!(to buf, fixed(2), 0.5);
+//! assert_eq!(buf, b"1 2\n0.50\n");
+//! ```
+//!
+//! `Sink` is implemented for every type implementing `std::io::Write`, so files, sockets, pipes
+//! of child processes and byte buffers can be used as they are.  The sink given by `to` is not
+//! buffered by `proconio`; wrap it with `BufWriter` if needed and flush it by yourself.
+//!
+//! For the environments without stdout, `callback::CallbackSink` forwards the output to a callback.
+
+use std::fmt;
+use std::io::{self, Write};
+
+pub mod callback;
+pub mod net;
+
+/// The main trait.  Types implementing this trait can be used for `to` of `output!` macro.
+///
+/// Since this is implemented for all `std::io::Write` types with the same method names, importing
+/// both traits makes the method calls (including `write!`) ambiguous.  You usually don't need to
+/// import this trait; if you do, call the methods like `Sink::flush(&mut sink)`.
+pub trait Sink {
+    /// Writes formatted output.
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()>;
+
+    /// Flushes the output written so far.
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+impl<W: Write + ?Sized> Sink for W {
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
+        Write::write_fmt(self, args)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Write::flush(self)
+    }
+}
+
+// `write!` calls `write_fmt()` method of the destination.  This has it as an inherent method, so
+// that the call is not ambiguous even if `std::io::Write` is also in scope.
+#[doc(hidden)]
+pub struct __Writer<'a, S: ?Sized>(pub &'a mut S);
+
+impl<S: Sink + ?Sized> __Writer<'_, S> {
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
+        self.0.write_fmt(args)
+    }
+}
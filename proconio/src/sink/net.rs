@@ -0,0 +1,49 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines sinks writing to sockets.
+//!
+//! See `source::net` module for the usage.
+
+use std::io::{self, LineWriter, Write};
+use std::net::TcpStream;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+/// Sink writing to a socket.
+///
+/// The output is flushed at every newline, so that the remote judge receives each line as soon as
+/// it is written.  Otherwise both sides would wait for each other forever.
+pub struct SocketSink<S: Write> {
+    writer: LineWriter<S>,
+}
+
+/// Sink writing to a TCP connection.
+pub type TcpSink = SocketSink<TcpStream>;
+
+/// Sink writing to a Unix domain socket.
+#[cfg(unix)]
+pub type UnixSink = SocketSink<UnixStream>;
+
+impl<S: Write> SocketSink<S> {
+    /// Creates a sink writing to the socket.
+    pub fn new(socket: S) -> SocketSink<S> {
+        SocketSink {
+            writer: LineWriter::new(socket),
+        }
+    }
+}
+
+impl<S: Write> Write for SocketSink<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,239 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source chosen at runtime.
+//!
+//! `Source` is object safe, and `Box<dyn Source<R>>` and `&mut dyn Source<R>` are sources as
+//! well.  However, the sources of a file, stdin and a string have different `R`, so they can't be
+//! put in the same `Box<dyn Source<R>>`.  `AnySource` hides `R`, so that any of them can be chosen
+//! at runtime, such as by a command line argument.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::any::AnySource;
+//!
+//! let mut source = match std::env::args().nth(1) {
+//!     Some(path) => AnySource::open(path).expect("failed to open the input"),
+//!     None => AnySource::stdin(),
+//! };
+//! input! {
+//!     from &mut source,
+//!     n: usize,
+//!     a: [u64; n],
+//! }
+//! ```
+
+use super::auto::AutoSource;
+use super::{Checkpoint, Position, Source};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Source hiding the type of the source and its reader.
+///
+/// This reads the same as the source given to `new()`.
+pub struct AnySource<'a> {
+    inner: Box<dyn ErasedSource + 'a>,
+}
+
+impl<'a> AnySource<'a> {
+    /// Creates `AnySource` reading `source`.
+    pub fn new<R: BufRead + 'a, S: Source<R> + 'a>(source: S) -> AnySource<'a> {
+        AnySource {
+            inner: Box::new(Erased::new(source)),
+        }
+    }
+
+    /// Creates `AnySource` reading `reader` through `AutoSource`.
+    pub fn from_reader<T: Read + 'a>(reader: T) -> AnySource<'a> {
+        AnySource::new(AutoSource::new(BufReader::new(reader)))
+    }
+
+    /// Opens the file and creates `AnySource` reading it through `AutoSource`.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<AnySource<'static>> {
+        File::open(path).map(AnySource::from_reader)
+    }
+}
+
+impl AnySource<'static> {
+    /// Creates `AnySource` reading stdin through `AutoSource`.
+    ///
+    /// This is independent of the stdin read by `input!` without `from`, so don't use both.
+    pub fn stdin() -> AnySource<'static> {
+        AnySource::from_reader(io::stdin())
+    }
+}
+
+impl<'a> From<&'a str> for AnySource<'a> {
+    fn from(s: &'a str) -> AnySource<'a> {
+        AnySource::new(AutoSource::from(s))
+    }
+}
+
+impl Source<io::Empty> for AnySource<'_> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.inner.next_token_bytes()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn end_line(&mut self) {
+        self.inner.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.inner.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.inner.rewind(checkpoint)
+    }
+
+    fn radix(&self) -> Option<u32> {
+        self.inner.radix()
+    }
+}
+
+// `Source<R>` without `R`, which is also used for the source replaced by `harness`.
+#[doc(hidden)]
+pub trait ErasedSource {
+    fn next_token(&mut self) -> Option<&str>;
+    fn next_token_bytes(&mut self) -> Option<&[u8]>;
+    fn is_empty(&mut self) -> bool;
+    fn peek_token(&mut self) -> Option<&str>;
+    fn skip_line(&mut self);
+    fn end_line(&mut self);
+    fn next_line(&mut self) -> Option<&str>;
+    fn position(&self) -> Option<Position>;
+    fn checkpoint(&mut self) -> Checkpoint;
+    fn rewind(&mut self, checkpoint: Checkpoint);
+    fn radix(&self) -> Option<u32>;
+}
+
+// Hides the type parameter `R` of `Source<R>`.
+pub(crate) struct Erased<R, S>(S, PhantomData<fn() -> R>);
+
+impl<R, S> Erased<R, S> {
+    pub(crate) fn new(source: S) -> Erased<R, S> {
+        Erased(source, PhantomData)
+    }
+}
+
+impl<R: BufRead, S: Source<R>> ErasedSource for Erased<R, S> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.0.next_token()
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.0.next_token_bytes()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.0.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.0.skip_line()
+    }
+
+    fn end_line(&mut self) {
+        self.0.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.0.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.0.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.0.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.0.rewind(checkpoint)
+    }
+
+    fn radix(&self) -> Option<u32> {
+        self.0.radix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::source::once::OnceSource;
+    use crate::source::slice::SliceSource;
+
+    #[test]
+    fn chosen_at_runtime() {
+        for i in 0..3 {
+            let mut source = match i {
+                0 => AnySource::from("2 1 2\nab"),
+                1 => AnySource::new(SliceSource::from("2 1 2\nab")),
+                _ => AnySource::from_reader(&b"2 1 2\nab"[..]),
+            };
+            input! {
+                from &mut source,
+                n: usize,
+                a: [u8; n],
+                s: crate::marker::Line,
+            }
+
+            assert_eq!(a, [1, 2]);
+            assert_eq!(s, "ab");
+            assert!(source.is_empty());
+        }
+    }
+
+    #[test]
+    fn trait_objects() {
+        let mut boxed: Box<dyn Source<_>> = Box::new(OnceSource::from("1 2 3"));
+        input! {
+            from &mut boxed,
+            a: u8,
+        }
+        let dynamic: &mut dyn Source<_> = &mut boxed;
+        input! {
+            from dynamic,
+            b: [u8; 2],
+        }
+
+        assert_eq!((a, b), (1, vec![2, 3]));
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading arbitrary bytes given by a fuzzer.
+//!
+//! `ArbitrarySource` reads the tokens from any bytes, so that a solution can be fuzzed with the
+//! data given by the fuzzer, such as `fuzz_target!(|source: ArbitrarySource| ...)` of cargo-fuzz.
+//! It implements `arbitrary::Arbitrary`, taking the rest of the data.  Unlike the other sources,
+//! it never fails on a malformed input in the source itself:
+//!
+//! - the invalid UTF-8 sequences are replaced by `U+FFFD`, and
+//! - after the end of the data, the tokens and the lines are `0`, up to a limit (1024 by default)
+//!   set by `padding()`.  After the limit the source is empty, so that a huge length read from the
+//!   data can't make the run endless.
+//!
+//! Read the values by `try_input!`, which returns the error of an invalid token or the end of the
+//! input instead of panicking:
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::source::arbitrary::ArbitrarySource;
+//! use proconio::source::ReadError;
+//! use proconio::try_input;
+//!
+//! fn solve(data: &[u8]) -> Result<u64, ReadError> {
+//!     let mut source = ArbitrarySource::new(data).padding(0);
+//!     try_input! {
+//!         from &mut source,
+//!         n: usize,
+//!         a: [u64; n],
+//!     }
+//!     Ok(a.iter().sum())
+//! }
+//!
+//! assert_eq!(solve(b"2 3 4"), Ok(7));
+//! assert!(solve(b"3 1").is_err());
+//! assert!(solve(b"2 x \xff").is_err());
+//! ```
+//!
+//! With the padding, the data too short to be a valid input is still read, and `is_padded()`
+//! tells it.  `input!` panics on an invalid token, such as `x` read as an integer, which the
+//! fuzzer reports as a crash, so use `try_input!` unless the panic should be reported.
+//!
+//! This module requires `arbitrary` feature.
+
+use super::slice::ByteTokens;
+use super::{Checkpoint, Position, Source};
+use alloc::string::String;
+use arbitrary::{Arbitrary, Unstructured};
+use core::fmt::{self, Debug, Formatter};
+
+const PADDING_TOKEN: &str = "0";
+
+/// Source reading the tokens from arbitrary bytes, padding the end of the input with `0`.
+pub struct ArbitrarySource {
+    tokens: ByteTokens<String>,
+    padding: usize,
+    padded: bool,
+}
+
+impl ArbitrarySource {
+    /// Creates `ArbitrarySource` reading `data`.
+    pub fn new(data: &[u8]) -> ArbitrarySource {
+        ArbitrarySource {
+            tokens: ByteTokens::new(String::from_utf8_lossy(data).into_owned()),
+            padding: 1024,
+            padded: false,
+        }
+    }
+
+    /// Sets the number of tokens and lines read after the end of the data.
+    pub fn padding(mut self, padding: usize) -> ArbitrarySource {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns whether a token or a line after the end of the data was read.
+    pub fn is_padded(&self) -> bool {
+        self.padded
+    }
+
+    fn pad(&mut self) -> Option<&'static str> {
+        if self.padding == 0 {
+            return None;
+        }
+
+        self.padding -= 1;
+        self.padded = true;
+        Some(PADDING_TOKEN)
+    }
+}
+
+impl Debug for ArbitrarySource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArbitrarySource")
+            .field("data", self.tokens.context())
+            .field("padding", &self.padding)
+            .finish()
+    }
+}
+
+/// Takes the bytes, or the rest of the data for the input of the fuzz target.
+impl<'a> Arbitrary<'a> for ArbitrarySource {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<ArbitrarySource> {
+        Ok(ArbitrarySource::new(<&[u8]>::arbitrary(u)?))
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> arbitrary::Result<ArbitrarySource> {
+        Ok(ArbitrarySource::new(u.take_rest()))
+    }
+}
+
+impl Source<&[u8]> for ArbitrarySource {
+    fn next_token(&mut self) -> Option<&str> {
+        if self.tokens.is_empty() {
+            return self.pad();
+        }
+        self.tokens.next_str()
+    }
+
+    /// Returns `true` after the padding, when `next_token()` returns `None`.
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty() && self.padding == 0
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        if self.tokens.is_empty() {
+            return if self.padding == 0 {
+                None
+            } else {
+                Some(PADDING_TOKEN)
+            };
+        }
+        self.tokens.peek_str()
+    }
+
+    fn skip_line(&mut self) {
+        if self.tokens.is_empty() {
+            self.pad().expect(super::NO_TOKEN_MESSAGE);
+            return;
+        }
+        self.tokens.skip_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        // an empty line may be left after the last token, so tries to read a line first.
+        let checkpoint = self.tokens.checkpoint();
+        if self.tokens.next_line().is_none() {
+            return self.pad();
+        }
+        self.tokens.rewind(checkpoint);
+        self.tokens.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.tokens.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.tokens.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.tokens.rewind(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Line;
+
+    #[test]
+    fn invalid_utf8() {
+        let mut source = ArbitrarySource::new(b"a\xffb 1");
+        assert_eq!(source.next_token(), Some("a\u{fffd}b"));
+        assert_eq!(source.next_token(), Some("1"));
+        assert!(!source.is_padded());
+    }
+
+    #[test]
+    fn padding() {
+        let mut source = ArbitrarySource::new(b"1\n\nx").padding(3);
+        input! {
+            from &mut source,
+            a: u32,
+            empty: Line,
+            x: Line,
+            rest: [u32; 2],
+            line: Line,
+        }
+
+        assert_eq!(
+            (a, empty, x, rest, line),
+            (1, "".into(), "x".into(), vec![0, 0], "0".into())
+        );
+        assert!(source.is_padded());
+        assert!(source.is_empty());
+        assert_eq!(source.peek_token(), None);
+        assert_eq!(source.next_token(), None);
+    }
+
+    #[test]
+    fn empty_after_padding() {
+        let mut source = ArbitrarySource::new(b"1").padding(1);
+        source.next_token();
+        assert!(!source.is_empty());
+        assert_eq!(source.peek_token(), Some("0"));
+        assert_eq!(source.next_token(), Some("0"));
+        assert!(source.is_empty());
+        assert_eq!(source.peek_token(), None);
+    }
+
+    #[test]
+    fn try_input() {
+        use crate::source::ReadError;
+        use crate::try_input;
+
+        fn read(source: ArbitrarySource) -> Result<Vec<(u8, String)>, ReadError> {
+            try_input! {
+                from source,
+                a: [(u8, String)],
+            }
+            Ok(a)
+        }
+
+        let u = Unstructured::new(b"2 1 a\xff 2 b");
+        let source = ArbitrarySource::arbitrary_take_rest(u).unwrap();
+        assert_eq!(
+            read(source),
+            Ok(vec![(1, "a\u{fffd}".into()), (2, "b".into())])
+        );
+
+        let source = ArbitrarySource::new(b"2 1 a x").padding(0);
+        assert_eq!(read(source).unwrap_err().token.as_deref(), Some("x"));
+        let source = ArbitrarySource::new(b"3 1 a").padding(0);
+        assert_eq!(read(source).unwrap_err().token, None);
+
+        // the padding is read as the values.
+        let source = ArbitrarySource::new(b"2 1").padding(3);
+        assert_eq!(read(source), Ok(vec![(1, "0".into()), (0, "0".into())]));
+    }
+}
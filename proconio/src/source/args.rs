@@ -0,0 +1,78 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading the command line arguments.
+//!
+//! `ArgsSource` reads the arguments as the tokens, so that `myprog 10 20` gives `10` and `20`.  An
+//! argument containing whitespaces, like `"10 20"`, is split into the tokens as well.  To make
+//! `input!` without `from` read the arguments, call `proconio::from_args()` at the beginning.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::args::ArgsSource;
+//!
+//! // usually `ArgsSource::new()`, which reads `std::env::args()`.
+//! let source = ArgsSource::from_args(&["3", "1 2", "3"]);
+//! input! {
+//!     from source,
+//!     n: usize,
+//!     a: [u32; n],
+//! }
+//! assert_eq!(a, [1, 2, 3]);
+//! ```
+
+use super::tokens::TokenSource;
+use super::Source;
+use std::env;
+use std::io;
+
+/// Source reading the command line arguments.
+#[derive(Debug, Clone)]
+pub struct ArgsSource {
+    tokens: TokenSource,
+}
+
+impl ArgsSource {
+    /// Creates `ArgsSource` reading the arguments of this program, except the program name.
+    pub fn new() -> ArgsSource {
+        ArgsSource::from_args(env::args().skip(1))
+    }
+
+    /// Creates `ArgsSource` reading `args` as the arguments.
+    pub fn from_args<I>(args: I) -> ArgsSource
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut tokens = TokenSource::new();
+        for arg in args {
+            tokens.extend(arg.as_ref().split_whitespace());
+        }
+        ArgsSource { tokens }
+    }
+}
+
+impl Default for ArgsSource {
+    fn default() -> ArgsSource {
+        ArgsSource::new()
+    }
+}
+
+impl Source<io::Empty> for ArgsSource {
+    fn next_token(&mut self) -> Option<&str> {
+        self.tokens.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.peek_token()
+    }
+}
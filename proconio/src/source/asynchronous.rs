@@ -0,0 +1,406 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading `tokio::io::AsyncBufRead`.
+//!
+//! This module requires `async` feature enabled.  `AsyncSource` reads the input line by line
+//! without blocking the thread, and `input_async!` reads the values from it as `input!` does.  So
+//! the parsing code can run inside tokio tasks, without `spawn_blocking`.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input_async;
+//! use proconio::marker::Usize1;
+//! use proconio::source::asynchronous::AsyncSource;
+//! use tokio::io::AsyncBufRead;
+//!
+//! async fn solve<R: AsyncBufRead + Unpin + Send>(source: &mut AsyncSource<R>) -> usize {
+//!     input_async! {
+//!         from &mut *source,
+//!         n: usize,
+//!         edges: [(Usize1, Usize1); n],
+//!     }
+//!     edges.iter().filter(|&&(u, v)| u == v).count()
+//! }
+//! ```
+//!
+//! `input_async!` accepts the same syntax as `input!`, except that the source must be specified.
+//! Each value of a type in `[...]` or `(...)` is awaited separately, so arrays and tuples may span
+//! lines.  The other types are read through `AsyncReadable`, which is implemented for all types
+//! implementing `Readable`: such a value is read after the line it begins with is available, so it
+//! must not span lines.  Use arrays or tuples in `input_async!` for the values spanning lines.
+
+use super::{Readable, Source};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// The boxed future returned by `AsyncReadable::read()`.
+pub type ReadFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Source reading `AsyncBufRead` line by line.
+pub struct AsyncSource<R: AsyncBufRead + Unpin> {
+    reader: R,
+    tokens: VecDeque<String>,
+    current: String,
+}
+
+impl<R: AsyncBufRead + Unpin> AsyncSource<R> {
+    /// Creates `AsyncSource` using specified reader of `AsyncBufRead`.
+    pub fn new(reader: R) -> AsyncSource<R> {
+        AsyncSource {
+            reader,
+            tokens: VecDeque::new(),
+            current: String::new(),
+        }
+    }
+
+    /// Reads lines until some token is available.
+    ///
+    /// Returns `false` if the reader reached EOF with no tokens left.
+    pub async fn fill(&mut self) -> bool {
+        let mut line = String::new();
+        while self.tokens.is_empty() {
+            line.clear();
+            let num_bytes = self
+                .reader
+                .read_line(&mut line)
+                .await
+                .expect("failed to get linel maybe an IO error.");
+
+            if num_bytes == 0 {
+                // reached EOF
+                return false;
+            }
+
+            self.tokens
+                .extend(line.split_whitespace().map(str::to_string));
+        }
+
+        true
+    }
+
+    /// Check if tokens are empty, reading lines if needed.
+    pub async fn is_empty(&mut self) -> bool {
+        !self.fill().await
+    }
+
+    /// Reads a value of `T`.
+    pub async fn read<T: AsyncReadable>(&mut self) -> T::Output
+    where
+        R: Send,
+    {
+        T::read(self).await
+    }
+
+    // the tokens already read, as a synchronous source.
+    fn buffered(&mut self) -> Buffered<'_> {
+        Buffered {
+            tokens: &mut self.tokens,
+            current: &mut self.current,
+        }
+    }
+}
+
+struct Buffered<'a> {
+    tokens: &'a mut VecDeque<String>,
+    current: &'a mut String,
+}
+
+impl Source<io::Empty> for Buffered<'_> {
+    fn next_token(&mut self) -> Option<&str> {
+        *self.current = self.tokens.pop_front()?;
+        Some(self.current)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+/// The asynchronous version of `Readable`.
+///
+/// This is implemented for all types implementing `Readable`.  See the document of
+/// [`asynchronous`](index.html) module for the limitation.
+pub trait AsyncReadable {
+    type Output;
+    fn read<'a, R>(source: &'a mut AsyncSource<R>) -> ReadFuture<'a, Self::Output>
+    where
+        Self: 'a,
+        R: AsyncBufRead + Unpin + Send + 'a;
+}
+
+impl<T: Readable> AsyncReadable for T {
+    type Output = T::Output;
+    fn read<'a, R>(source: &'a mut AsyncSource<R>) -> ReadFuture<'a, T::Output>
+    where
+        T: 'a,
+        R: AsyncBufRead + Unpin + Send + 'a,
+    {
+        Box::pin(async move {
+            source.fill().await;
+            T::read(&mut source.buffered())
+        })
+    }
+}
+
+#[doc(hidden)]
+pub trait __AsAsyncSource {
+    type Reader: AsyncBufRead + Unpin;
+    fn __as_async_source(&mut self) -> &mut AsyncSource<Self::Reader>;
+}
+
+impl<R: AsyncBufRead + Unpin> __AsAsyncSource for AsyncSource<R> {
+    type Reader = R;
+    fn __as_async_source(&mut self) -> &mut AsyncSource<R> {
+        self
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> __AsAsyncSource for &'_ mut AsyncSource<R> {
+    type Reader = R;
+    fn __as_async_source(&mut self) -> &mut AsyncSource<R> {
+        self
+    }
+}
+
+/// Reads input from `AsyncSource`, awaiting the lines to be available.
+///
+/// This is the asynchronous version of `input!`, and must be used in an async context.  The source
+/// is required, and it is `AsyncSource` or `&mut AsyncSource`:
+///
+/// ```text
+/// input_async! {
+///     from source,
+///     (mut) variable: type,
+///     ...
+/// }
+/// ```
+///
+/// The syntax of the variables is the same as `input!`.  See the document of
+/// [`asynchronous`](source/asynchronous/index.html) module for how the values are awaited.
+#[macro_export]
+macro_rules! input_async {
+    // terminator
+    (@from [$source:expr] @rest) => {};
+
+    // parse mutability
+    (@from [$source:expr] @rest mut $($rest:tt)*) => {
+        $crate::input_async! {
+            @from [$source]
+            @mut [mut]
+            @rest $($rest)*
+        }
+    };
+    (@from [$source:expr] @rest $($rest:tt)*) => {
+        $crate::input_async! {
+            @from [$source]
+            @mut []
+            @rest $($rest)*
+        }
+    };
+
+    // parse variable pattern
+    (@from [$source:expr] @mut [$($mut:tt)?] @rest $var:tt: $($rest:tt)*) => {
+        $crate::input_async! {
+            @from [$source]
+            @mut [$($mut)*]
+            @var $var
+            @kind []
+            @rest $($rest)*
+        }
+    };
+
+    // parse kind (type)
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest) => {
+        let $($mut)* $var = $crate::read_value_async!(@source [$source] @kind [$($kind)*]);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest, $($rest:tt)*) => {
+        $crate::input_async!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest);
+        $crate::input_async!(@from [$source] @rest $($rest)*);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest as $modifier:expr) => {
+        let $($mut)* $var = $crate::read_value_async!(@source [$source] @kind [$($kind)*] @modifier [$modifier]);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest as $modifier:expr, $($rest:tt)*) => {
+        $crate::input_async!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)*] @rest as $modifier);
+        $crate::input_async!(@from [$source] @rest $($rest)*);
+    };
+    (@from [$source:expr] @mut [$($mut:tt)?] @var $var:tt @kind [$($kind:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::input_async!(@from [$source] @mut [$($mut)*] @var $var @kind [$($kind)* $tt] @rest $($rest)*);
+    };
+
+    (from $source:expr, $($rest:tt)*) => {
+        #[allow(unused_variables, unused_mut)]
+        let mut s = $source;
+        #[allow(unused_variables)]
+        let s = $crate::source::asynchronous::__AsAsyncSource::__as_async_source(&mut s);
+        $crate::input_async! {
+            @from [&mut *s]
+            @rest $($rest)*
+        }
+    };
+    ($($rest:tt)*) => {
+        compile_error!("`input_async!` requires the source: `input_async! { from source, ... }`.");
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! read_value_async {
+    // array and variable length array
+    (@source [$source:expr] @kind [[$($kind:tt)*]]) => {
+        $crate::read_value_async!(@array @source [$source] @kind [] @modifier [] @rest $($kind)*)
+    };
+    (@source [$source:expr] @kind [[$($kind:tt)*]] @modifier [$modifier:expr]) => {
+        $crate::read_value_async!(@array @source [$source] @kind [] @modifier [$modifier] @rest $($kind)*)
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest) => {{
+        let len = <usize as $crate::source::asynchronous::AsyncReadable>::read($source).await;
+        $crate::read_value_async!(@array @source [$source] @kind [$($kind)*] @modifier [$($modifier)?] @len [len])
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest ; $($rest:tt)*) => {
+        $crate::read_value_async!(@array @source [$source] @kind [$($kind)*] @modifier [$($modifier)?] @len [$($rest)*])
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$($modifier:expr)?] @rest $tt:tt $($rest:tt)*) => {
+        $crate::read_value_async!(@array @source [$source] @kind [$($kind)* $tt] @modifier [$($modifier)?] @rest $($rest)*)
+    };
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [] @len [$($len:tt)*]) => {{
+        let len = $($len)*;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push($crate::read_value_async!(@source [$source] @kind [$($kind)*]));
+        }
+        values
+    }};
+    (@array @source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr] @len [$($len:tt)*]) => {{
+        let values = $crate::read_value_async!(@array @source [$source] @kind [$($kind)*] @modifier [] @len [$($len)*]);
+        $crate::modifier::Modifier::modify($modifier, values.into_iter())
+    }};
+
+    // modifier for non-array types
+    (@source [$source:expr] @kind [$($kind:tt)*] @modifier [$modifier:expr]) => {
+        compile_error!("modifiers (`as ...`) can only be applied to arrays.")
+    };
+
+    // tuple
+    (@source [$source:expr] @kind [($($kinds:tt)*)]) => {
+        $crate::read_value_async!(@tuple @source [$source] @kinds [] @current [] @rest $($kinds)*)
+    };
+    (@tuple @source [$source:expr] @kinds [$([$($kind:tt)*])*] @current [] @rest) => {
+        (
+            $($crate::read_value_async!(@source [$source] @kind [$($kind)*]),)*
+        )
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest) => {
+        $crate::read_value_async!(@tuple @source [$source] @kinds [$($kinds)* [$($curr)*]] @current [] @rest)
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest, $($rest:tt)*) => {
+        $crate::read_value_async!(@tuple @source [$source] @kinds [$($kinds)* [$($curr)*]] @current [] @rest $($rest)*)
+    };
+    (@tuple @source [$source:expr] @kinds [$($kinds:tt)*] @current [$($curr:tt)*] @rest $tt:tt $($rest:tt)*) => {
+        $crate::read_value_async!(@tuple @source [$source] @kinds [$($kinds)*] @current [$($curr)* $tt] @rest $($rest)*)
+    };
+
+    // escaped fixed-size array: each element is awaited, so it may span lines
+    (@source [$source:expr] @kind [{[$kind:ty; $len:expr]}]) => {{
+        let mut values = Vec::with_capacity($len);
+        for _ in 0..$len {
+            values.push(<$kind as $crate::source::asynchronous::AsyncReadable>::read($source).await);
+        }
+        let array: [_; $len] = ::std::convert::TryInto::try_into(values)
+            .unwrap_or_else(|_| unreachable!("the length is checked"));
+        array
+    }};
+
+    // escaped type: any type in braces, typically generic types with multiple parameters
+    (@source [$source:expr] @kind [{$kind:ty}]) => {
+        <$kind as $crate::source::asynchronous::AsyncReadable>::read($source).await
+    };
+
+    // unreachable
+    (@source [$source:expr] @kind []) => {
+        compile_error!(concat!("Reached unreachable statement while parsing macro input.  ", "This is a bug in `proconio`.  ", "Please report this issue from ", "<https://github.com/statiolake/proconio-rs/issues>."));
+    };
+
+    // normal other
+    (@source [$source:expr] @kind [$kind:ty]) => {
+        <$kind as $crate::source::asynchronous::AsyncReadable>::read($source).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::{Chars, Usize1};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    // `&[u8]` is always ready, so polling in a loop is enough.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Noop;
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Waker::from(Arc::new(Noop));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn input_async() {
+        block_on(async {
+            let mut source = AsyncSource::new("3\n1 2\n2\n3 3 3\nabc\n3 1 3 2\n".as_bytes());
+            input_async! {
+                from &mut source,
+                n: usize,
+                edges: [(Usize1, Usize1); n],
+                s: Chars,
+                mut sorted: [u32] as crate::modifier::Sorted,
+            }
+
+            assert_eq!(edges, [(0, 1), (1, 2), (2, 2)]);
+            assert_eq!(s, ['a', 'b', 'c']);
+            sorted.push(4);
+            assert_eq!(sorted, [1, 2, 3, 4]);
+            assert!(source.is_empty().await);
+        });
+    }
+
+    #[test]
+    fn fixed_size_array_across_lines() {
+        block_on(async {
+            let source = AsyncSource::new("1 2\n3\n\n4 x".as_bytes());
+            input_async! {
+                from source,
+                grid: {[u8; 2]},
+                (a, b): (u8, u8),
+                _: String,
+            }
+
+            assert_eq!(grid, [1, 2]);
+            assert_eq!((a, b), (3, 4));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to get the next token")]
+    fn readable_across_lines() {
+        block_on(async {
+            let mut source = AsyncSource::new("1\n2\n".as_bytes());
+            source.read::<crate::marker::ArrayOf<u8, 2>>().await;
+        });
+    }
+}
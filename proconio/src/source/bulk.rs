@@ -0,0 +1,307 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines the bulk reading of the arrays of the integers.
+//!
+//! Reading `[u32; n]` one by one parses each token through `FromStr`.  For the primitive
+//! integers, `input!` reads the array by `read_vec_fast()` instead: it allocates the vector once
+//! and parses the bytes of the tokens in a tight loop.  This is chosen automatically when the
+//! element type is written as a single name such as `u32` or `usize` (not `std::primitive::u32`)
+//! and the array has no `as` modifier; other element types are read one by one as before.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::source::auto::AutoSource;
+//! use proconio::source::bulk::read_vec_fast;
+//!
+//! let mut source = AutoSource::from("-1 +20 300");
+//! let a: Vec<i64> = read_vec_fast(&mut source, 3);
+//! assert_eq!(a, [-1, 20, 300]);
+//! ```
+//!
+//! A single integer such as `n: u128` is parsed in the same way.  128-bit integers especially
+//! benefit from this, since their digits are accumulated in `u64` before the 128-bit arithmetic.
+//!
+//! The values are the same as the ones read by `FromStr`: a token the fast path can't parse is
+//! parsed again by `FromStr`, which reports the error as usual.
+
+use super::{config, parse_token, BufRead, Readable, Source};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::num::ParseIntError;
+use core::str::FromStr;
+
+/// Primitive integers parsed by `read_vec_fast()`.
+pub trait FastInt: Sized + FromStr<Err = ParseIntError> {
+    /// Parses the decimal integer with an optional sign.  Returns `None` for anything else,
+    /// including the overflow.
+    fn parse_bytes(token: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_fast_int {
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl FastInt for $t {
+                fn parse_bytes(token: &[u8]) -> Option<$t> {
+                    let digits = token.strip_prefix(b"+").unwrap_or(token);
+                    if digits.is_empty() {
+                        return None;
+                    }
+
+                    let mut value: $t = 0;
+                    for &b in digits {
+                        let digit = b.wrapping_sub(b'0');
+                        if digit >= 10 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?.checked_add(digit as $t)?;
+                    }
+                    Some(value)
+                }
+            }
+        )*
+    };
+    (signed: $($t:ty),*) => {
+        $(
+            impl FastInt for $t {
+                fn parse_bytes(token: &[u8]) -> Option<$t> {
+                    let (negative, digits) = match token.first() {
+                        Some(b'-') => (true, &token[1..]),
+                        Some(b'+') => (false, &token[1..]),
+                        _ => (false, token),
+                    };
+                    if digits.is_empty() {
+                        return None;
+                    }
+
+                    // accumulates toward the sign so that `MIN` does not overflow.
+                    let mut value: $t = 0;
+                    for &b in digits {
+                        let digit = b.wrapping_sub(b'0');
+                        if digit >= 10 {
+                            return None;
+                        }
+                        value = value.checked_mul(10)?;
+                        value = if negative {
+                            value.checked_sub(digit as $t)?
+                        } else {
+                            value.checked_add(digit as $t)?
+                        };
+                    }
+                    Some(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_fast_int!(unsigned: u8, u16, u32, u64, usize);
+impl_fast_int!(signed: i8, i16, i32, i64, isize);
+
+// the arithmetic of 128-bit integers is slow, so the digits are parsed 19 at a time in `u64`.
+fn parse_u128_digits(digits: &[u8]) -> Option<u128> {
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: u128 = 0;
+    for chunk in digits.chunks(19) {
+        let mut part: u64 = 0;
+        for &b in chunk {
+            let digit = b.wrapping_sub(b'0');
+            if digit >= 10 {
+                return None;
+            }
+            part = part * 10 + digit as u64;
+        }
+        value = value
+            .checked_mul(10u128.pow(chunk.len() as u32))?
+            .checked_add(part as u128)?;
+    }
+    Some(value)
+}
+
+impl FastInt for u128 {
+    fn parse_bytes(token: &[u8]) -> Option<u128> {
+        parse_u128_digits(token.strip_prefix(b"+").unwrap_or(token))
+    }
+}
+
+impl FastInt for i128 {
+    fn parse_bytes(token: &[u8]) -> Option<i128> {
+        match token.first() {
+            Some(b'-') => {
+                let magnitude = parse_u128_digits(&token[1..])?;
+                if magnitude > i128::MIN.unsigned_abs() {
+                    return None;
+                }
+                // `MIN` is cast to itself and stays the same.
+                Some((magnitude as i128).wrapping_neg())
+            }
+            Some(b'+') => i128::try_from(parse_u128_digits(&token[1..])?).ok(),
+            _ => i128::try_from(parse_u128_digits(token)?).ok(),
+        }
+    }
+}
+
+/// Reads `len` integers at once.
+///
+/// This returns the same values as reading `T` `len` times, but faster.
+pub fn read_vec_fast<T: FastInt, R: BufRead, S: Source<R>>(source: &mut S, len: usize) -> Vec<T> {
+    read_fast(source, 0..len)
+}
+
+// reads an integer for each item of `indices`, which is the range of the length in `input!`.
+fn read_fast<T: FastInt, R: BufRead, S: Source<R>, I: Iterator>(
+    source: &mut S,
+    indices: I,
+) -> Vec<T> {
+    let mut res = Vec::with_capacity(indices.size_hint().0);
+    for _ in indices {
+        res.push(read_fast_one(source));
+    }
+    res
+}
+
+// reads a primitive integer, in the radix of the source if it is set.
+pub(crate) fn read_fast_one<T: FastInt, R: BufRead, S: Source<R>>(source: &mut S) -> T {
+    if let Some(radix) = source.radix() {
+        return parse_token(&config::to_decimal(source.next_token_unwrap(), radix));
+    }
+
+    let token = source.next_token_bytes_unwrap();
+    match T::parse_bytes(token) {
+        Some(value) => value,
+        None => parse_token(&String::from_utf8_lossy(token)),
+    }
+}
+
+// `input!` chooses `read_vec_fast()` by the autoref specialization: `(&bulk::<T>()).read_vec(..)`
+// finds `ReadFast` first if `T` is `FastInt`, and `ReadGeneric` after auto-referencing otherwise.
+// `read_one()` does the same for a single value.
+
+#[doc(hidden)]
+pub struct Bulk<T>(PhantomData<fn() -> T>);
+
+#[doc(hidden)]
+pub fn bulk<T>() -> Bulk<T> {
+    Bulk(PhantomData)
+}
+
+#[doc(hidden)]
+pub trait ReadFast {
+    type Output;
+    fn read_one<R: BufRead, S: Source<R>>(&self, source: &mut S) -> Self::Output;
+    fn read_vec<R: BufRead, S: Source<R>, I: Iterator>(
+        &self,
+        source: &mut S,
+        indices: I,
+    ) -> Vec<Self::Output>;
+}
+
+impl<T: FastInt> ReadFast for Bulk<T> {
+    type Output = T;
+    fn read_one<R: BufRead, S: Source<R>>(&self, source: &mut S) -> T {
+        read_fast_one(source)
+    }
+    fn read_vec<R: BufRead, S: Source<R>, I: Iterator>(
+        &self,
+        source: &mut S,
+        indices: I,
+    ) -> Vec<T> {
+        read_fast(source, indices)
+    }
+}
+
+#[doc(hidden)]
+pub trait ReadGeneric {
+    type Output;
+    fn read_one<R: BufRead, S: Source<R>>(&self, source: &mut S) -> Self::Output;
+    fn read_vec<R: BufRead, S: Source<R>, I: Iterator>(
+        &self,
+        source: &mut S,
+        indices: I,
+    ) -> Vec<Self::Output>;
+}
+
+impl<T: Readable> ReadGeneric for &Bulk<T> {
+    type Output = T::Output;
+    fn read_one<R: BufRead, S: Source<R>>(&self, source: &mut S) -> T::Output {
+        T::read(source)
+    }
+    fn read_vec<R: BufRead, S: Source<R>, I: Iterator>(
+        &self,
+        source: &mut S,
+        indices: I,
+    ) -> Vec<T::Output> {
+        indices.map(|_| T::read(source)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::slice::SliceSource;
+    use alloc::string::ToString;
+
+    #[test]
+    fn parse_bytes() {
+        assert_eq!(u8::parse_bytes(b"255"), Some(255));
+        assert_eq!(u8::parse_bytes(b"+7"), Some(7));
+        assert_eq!(u8::parse_bytes(b"256"), None);
+        assert_eq!(u8::parse_bytes(b"-0"), None);
+        assert_eq!(i8::parse_bytes(b"-128"), Some(-128));
+        assert_eq!(i8::parse_bytes(b"128"), None);
+        assert_eq!(i64::parse_bytes(b"-"), None);
+        assert_eq!(i64::parse_bytes(b"1x"), None);
+        assert_eq!(u64::parse_bytes(b""), None);
+    }
+
+    #[test]
+    fn parse_bytes_128bit() {
+        let max = u128::MAX.to_string();
+        assert_eq!(u128::parse_bytes(max.as_bytes()), Some(u128::MAX));
+        assert_eq!(
+            u128::parse_bytes(b"340282366920938463463374607431768211456"),
+            None
+        );
+        assert_eq!(
+            u128::parse_bytes(b"+12345678901234567890"),
+            Some(12345678901234567890)
+        );
+        assert_eq!(u128::parse_bytes(b"1234567890123456789x"), None);
+        assert_eq!(u128::parse_bytes(b"-1"), None);
+
+        let min = i128::MIN.to_string();
+        assert_eq!(i128::parse_bytes(min.as_bytes()), Some(i128::MIN));
+        assert_eq!(i128::parse_bytes(&min.as_bytes()[1..]), None);
+        let max = i128::MAX.to_string();
+        assert_eq!(i128::parse_bytes(max.as_bytes()), Some(i128::MAX));
+        assert_eq!(
+            i128::parse_bytes(b"-170141183460469231731687303715884105729"),
+            None
+        );
+        assert_eq!(i128::parse_bytes(b"-0"), Some(0));
+        assert_eq!(i128::parse_bytes(b"+"), None);
+    }
+
+    #[test]
+    fn read_vec() {
+        let mut source = SliceSource::from("1 +2 3");
+        let a: Vec<u32> = read_vec_fast(&mut source, 3);
+        assert_eq!(a, [1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse the input `-1`")]
+    fn read_vec_fast_invalid() {
+        let mut source = SliceSource::from("1 -1");
+        let _: Vec<u32> = read_vec_fast(&mut source, 2);
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use super::slice::{ByteTokens, Delimiters};
+use super::{Checkpoint, Position, Source};
+use std::io::{BufRead, BufReader, Cursor};
+use std::marker::PhantomData;
+
+/// Source reading entire content as raw bytes.
+///
+/// Like `OnceSource`, the whole input is read at first.  The difference is that the input is never
+/// validated as a UTF-8 string as a whole; tokens are splitted by ASCII whitespaces, and only the
+/// tokens requested as `&str` are validated.  Readers asking for bytes (such as `marker::Bytes`)
+/// get the raw slice through `Source::next_token_bytes()` without any validation.
+pub struct ByteSource<R: BufRead> {
+    tokens: ByteTokens<Box<[u8]>>,
+
+    // to consume `R`, for the consistency with `OnceSource`.
+    _read: PhantomData<R>,
+}
+
+impl<R: BufRead> ByteSource<R> {
+    /// Creates `Source` using specified reader of `BufRead`.
+    pub fn new(mut source: R) -> ByteSource<R> {
+        let mut context = Vec::new();
+        source
+            .read_to_end(&mut context)
+            .expect("failed to read from source; maybe an IO error.");
+
+        ByteSource::from_context(context)
+    }
+
+    /// Creates `Source` splitting tokens by the specified characters instead of whitespaces.
+    ///
+    /// Every character in `delimiters` must be ASCII.  Consecutive delimiters are treated as one,
+    /// like whitespaces, so an empty field between two commas is skipped.  Include whitespaces in
+    /// `delimiters` if tokens are also separated by them.
+    ///
+    /// ```
+    /// # extern crate proconio;
+    /// use proconio::input;
+    /// use proconio::source::bytes::ByteSource;
+    ///
+    /// let source = ByteSource::with_delimiters("3,14,15\n9;2".as_bytes(), ",;\n");
+    /// input! {
+    ///     from source,
+    ///     a: [u32; 5],
+    /// }
+    /// assert_eq!(a, [3, 14, 15, 9, 2]);
+    /// ```
+    pub fn with_delimiters(source: R, delimiters: &str) -> ByteSource<R> {
+        let delimiters = Delimiters::new(delimiters);
+        let mut res = ByteSource::new(source);
+        res.tokens.delimiters = delimiters;
+        res
+    }
+
+    fn from_context(context: Vec<u8>) -> ByteSource<R> {
+        ByteSource {
+            tokens: ByteTokens::new(context.into_boxed_slice()),
+            _read: PhantomData,
+        }
+    }
+}
+
+impl<R: BufRead> Source<R> for ByteSource<R> {
+    /// Gets a next token.
+    fn next_token(&mut self) -> Option<&str> {
+        self.tokens.next_str()
+    }
+
+    /// Gets a next token as raw bytes.
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.tokens.next()
+    }
+
+    /// Check if tokens are empty
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Gets a next token without consuming it.
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.peek_str()
+    }
+
+    /// Skips the next token and the rest of the line.
+    fn skip_line(&mut self) {
+        self.tokens.skip_line()
+    }
+
+    /// Gets the next line as is.
+    fn next_line(&mut self) -> Option<&str> {
+        self.tokens.next_line()
+    }
+
+    /// Gets the position of the last token or line read.
+    fn position(&self) -> Option<Position> {
+        self.tokens.position()
+    }
+
+    /// Saves the current point of the input.
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.tokens.checkpoint()
+    }
+
+    /// Goes back to the saved point of the input.
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.tokens.rewind(checkpoint)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ByteSource<BufReader<&'a [u8]>> {
+    fn from(s: &'a [u8]) -> ByteSource<BufReader<&'a [u8]>> {
+        ByteSource::from_context(s.to_vec())
+    }
+}
+
+impl<'a> From<&'a str> for ByteSource<BufReader<&'a [u8]>> {
+    fn from(s: &'a str) -> ByteSource<BufReader<&'a [u8]>> {
+        ByteSource::from(s.as_bytes())
+    }
+}
+
+impl From<Vec<u8>> for ByteSource<Cursor<Vec<u8>>> {
+    fn from(s: Vec<u8>) -> ByteSource<Cursor<Vec<u8>>> {
+        // the vector is taken as is; no copy happens.
+        ByteSource::from_context(s)
+    }
+}
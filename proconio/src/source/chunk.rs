@@ -0,0 +1,184 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source pulling the input chunk by chunk from a callback.
+//!
+//! This is for the environments without a reader, such as the input supplied by JavaScript.  The
+//! callback returns the next chunk, or `None` at the end of the input.  Chunks are concatenated as
+//! a stream, so a token may be split across chunks.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::chunk::ChunkSource;
+//!
+//! let mut chunks = vec!["3\n1", "2 3", "4 5\n"].into_iter();
+//! let source = ChunkSource::new(move || chunks.next().map(str::to_string));
+//! input! {
+//!     from source,
+//!     n: usize,
+//!     a: [u32; n],
+//! }
+//! assert_eq!(a, [12, 34, 5]);
+//! ```
+
+use super::Source;
+use std::io;
+
+/// Source pulling the input chunk by chunk from a callback.
+pub struct ChunkSource<F: FnMut() -> Option<String>> {
+    callback: F,
+    buffer: String,
+    pos: usize,
+    eof: bool,
+}
+
+impl<F: FnMut() -> Option<String>> ChunkSource<F> {
+    /// Creates `ChunkSource` pulling the chunks from the callback.
+    ///
+    /// The callback is not called after it returns `None`.
+    pub fn new(callback: F) -> ChunkSource<F> {
+        ChunkSource {
+            callback,
+            buffer: String::new(),
+            pos: 0,
+            eof: false,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.buffer[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    // skips the whitespaces and returns the length of the next token, pulling the chunks until the
+    // token is complete: it is followed by a whitespace or the end of the input.
+    fn token_len(&mut self) -> usize {
+        loop {
+            self.skip_whitespace();
+            let rest = &self.buffer[self.pos..];
+            if let Some(len) = rest.find(char::is_whitespace) {
+                return len;
+            }
+            if self.eof {
+                return rest.len();
+            }
+            self.pull();
+        }
+    }
+
+    fn pull(&mut self) {
+        // the consumed part is no longer needed.
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+        match (self.callback)() {
+            Some(chunk) => self.buffer.push_str(&chunk),
+            None => self.eof = true,
+        }
+    }
+}
+
+impl<F: FnMut() -> Option<String>> Source<io::Empty> for ChunkSource<F> {
+    fn next_token(&mut self) -> Option<&str> {
+        let len = self.token_len();
+        if len == 0 {
+            return None;
+        }
+
+        let start = self.pos;
+        self.pos += len;
+        Some(&self.buffer[start..self.pos])
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        let len = self.token_len();
+        if len == 0 {
+            return None;
+        }
+
+        Some(&self.buffer[self.pos..self.pos + len])
+    }
+
+    fn skip_line(&mut self) {
+        self.next_token_unwrap();
+        loop {
+            if let Some(len) = self.buffer[self.pos..].find('\n') {
+                self.pos += len;
+                return;
+            }
+            self.pos = self.buffer.len();
+            if self.eof {
+                return;
+            }
+            self.pull();
+        }
+    }
+
+    fn is_empty(&mut self) -> bool {
+        loop {
+            self.skip_whitespace();
+            if self.pos < self.buffer.len() {
+                return false;
+            }
+            if self.eof {
+                return true;
+            }
+            self.pull();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    fn chunks(chunks: &'static [&'static str]) -> impl FnMut() -> Option<String> {
+        let mut chunks = chunks.iter();
+        move || chunks.next().map(|chunk| chunk.to_string())
+    }
+
+    #[test]
+    fn split_tokens() {
+        let mut source = ChunkSource::new(chunks(&["ab", "", "c d", " \n", "e", "f"]));
+        input! {
+            from &mut source,
+            s: String,
+            t: String,
+        }
+
+        assert_eq!(s, "abc");
+        assert_eq!(t, "d");
+        assert!(!source.is_empty());
+        assert_eq!(source.next_token(), Some("ef"));
+        assert!(source.is_empty());
+        assert_eq!(source.next_token(), None);
+    }
+
+    #[test]
+    fn skip_line() {
+        let mut source = ChunkSource::new(chunks(&["1 2", "3 4", " 5\n6", "7 8\n9"]));
+        source.skip_line();
+        assert_eq!(source.peek_token(), Some("67"));
+        assert_eq!(source.next_token(), Some("67"));
+        source.skip_line();
+        assert_eq!(source.next_token(), Some("9"));
+    }
+
+    #[test]
+    fn no_call_after_end() {
+        let mut calls = 0;
+        let mut source = ChunkSource::new(|| {
+            calls += 1;
+            None
+        });
+        assert!(source.is_empty());
+        assert_eq!(source.next_token(), None);
+        drop(source);
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines sources reading compressed input.
+//!
+//! This module requires `compress` feature enabled.  Big local test cases are often archived in
+//! compressed form; you can run your solution directly on them.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::compressed::GzSource;
+//!
+//! input! {
+//!     from GzSource::open("in.txt.gz"),
+//!     n: usize,
+//!     a: [i64; n],
+//! }
+//! ```
+
+use super::once::OnceSource;
+use super::{Checkpoint, Position, Source};
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// Source reading gzip-compressed stream.
+///
+/// The entire content is decompressed and read at first, as `OnceSource` does.
+pub struct GzSource<R: Read> {
+    inner: OnceSource<BufReader<MultiGzDecoder<R>>>,
+}
+
+impl<R: Read> GzSource<R> {
+    /// Creates `GzSource` using specified reader of gzip-compressed data.
+    pub fn new(reader: R) -> GzSource<R> {
+        GzSource {
+            inner: OnceSource::new(BufReader::new(MultiGzDecoder::new(reader))),
+        }
+    }
+}
+
+impl GzSource<File> {
+    /// Opens the gzip-compressed file.
+    pub fn open<P: AsRef<Path>>(path: P) -> GzSource<File> {
+        GzSource::new(open(path.as_ref()))
+    }
+}
+
+impl<R: Read> Source<BufReader<MultiGzDecoder<R>>> for GzSource<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.inner.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.inner.rewind(checkpoint)
+    }
+}
+
+type ZstdDecoder<R> = zstd::stream::read::Decoder<'static, BufReader<R>>;
+
+/// Source reading zstd-compressed stream.
+///
+/// The entire content is decompressed and read at first, as `OnceSource` does.
+pub struct ZstdSource<R: Read> {
+    inner: OnceSource<BufReader<ZstdDecoder<R>>>,
+}
+
+impl<R: Read> ZstdSource<R> {
+    /// Creates `ZstdSource` using specified reader of zstd-compressed data.
+    pub fn new(reader: R) -> ZstdSource<R> {
+        let decoder = ZstdDecoder::new(reader)
+            .expect("failed to initialize zstd decoder; maybe an IO error.");
+        ZstdSource {
+            inner: OnceSource::new(BufReader::new(decoder)),
+        }
+    }
+}
+
+impl ZstdSource<File> {
+    /// Opens the zstd-compressed file.
+    pub fn open<P: AsRef<Path>>(path: P) -> ZstdSource<File> {
+        ZstdSource::new(open(path.as_ref()))
+    }
+}
+
+impl<R: Read> Source<BufReader<ZstdDecoder<R>>> for ZstdSource<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.inner.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.inner.rewind(checkpoint)
+    }
+}
+
+fn open(path: &Path) -> File {
+    File::open(path)
+        .unwrap_or_else(|e| panic!("failed to open the file `{}`: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GzSource, ZstdSource};
+    use crate::input;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    #[test]
+    fn gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"3\n1 2 3\n").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        input! {
+            from GzSource::new(&compressed[..]),
+            n: usize,
+            a: [i32; n],
+        }
+        assert_eq!(a, [1, 2, 3]);
+    }
+
+    #[test]
+    fn zstd() {
+        let compressed = zstd::encode_all(&b"2\nhello world\n"[..], 0).unwrap();
+
+        input! {
+            from ZstdSource::new(&compressed[..]),
+            n: usize,
+            a: [String; n],
+        }
+        assert_eq!(a, ["hello", "world"]);
+    }
+}
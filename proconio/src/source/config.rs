@@ -0,0 +1,357 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines the settings of an `input!` invocation.
+//!
+//! `input!` accepts `with { key = value, ... },` after `from source,` (or at the beginning for
+//! stdin).  The settings are applied to the tokens of the source while the invocation reads them,
+//! by wrapping the source with `ConfiguredSource`.  The keys are the methods of `Config`:
+//!
+//! - `sep = ","`: the tokens are further splitted by any of the characters.
+//! - `radix = 16`: the integers are written in the radix.  An optional prefix `0x`, `0o` or `0b`
+//!   of the radix is accepted.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::auto::AutoSource;
+//!
+//! let mut source = AutoSource::from("3\n1,2,3\nff -0x10\nok");
+//! input! {
+//!     from &mut source,
+//!     n: usize,
+//! }
+//! input! {
+//!     from &mut source,
+//!     with { sep = "," },
+//!     a: [u32; n],
+//! }
+//! input! {
+//!     from &mut source,
+//!     with { radix = 16 },
+//!     b: (u8, i32),
+//! }
+//! input! {
+//!     from &mut source,
+//!     s: String,
+//! }
+//!
+//! assert_eq!(a, [1, 2, 3]);
+//! assert_eq!(b, (255, -16));
+//! assert_eq!(s, "ok");
+//! ```
+//!
+//! `radix` applies to the primitive integers given by their names, including the elements of arrays
+//! and tuples, and to `Usize1` and `Isize1`.  The other values, such as strings, are read as
+//! written; so are the integers inside the types in braces or of `#[derive_readable]` structs,
+//! since they are read by `Readable` of `FromStr` types.  `sep` splits every token, strings as
+//! well.  The pieces of a token split by `sep` can't be returned to the source, so all of them must
+//! be read by the end of the invocation; otherwise it panics.
+
+use super::{BufRead, Checkpoint, Position, Source};
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// The settings given by `with { ... }` of `input!`.
+///
+/// Each key of `with` calls the method of the same name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    separators: Vec<char>,
+    radix: Option<u32>,
+}
+
+impl Config {
+    /// Creates `Config` with no settings.
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    /// Splits the tokens by any of the characters in `separators`, in addition to whitespaces.
+    pub fn sep(mut self, separators: &str) -> Config {
+        self.separators = separators.chars().collect();
+        self
+    }
+
+    /// Reads the integers in the radix, which must be in `2..=36`.
+    pub fn radix(mut self, radix: u32) -> Config {
+        assert!(
+            (2..=36).contains(&radix),
+            "the radix {} is not in `2..=36`",
+            radix
+        );
+        self.radix = Some(radix);
+        self
+    }
+}
+
+// converts the token to the decimal if it is an integer in the radix.  Otherwise the token is
+// returned as is, so that the error shows it.
+pub(crate) fn to_decimal(token: &str, radix: u32) -> String {
+    let (sign, digits) = match token.as_bytes().first() {
+        Some(b'+') | Some(b'-') => token.split_at(1),
+        _ => ("", token),
+    };
+    let prefix = match radix {
+        16 => Some("0x"),
+        8 => Some("0o"),
+        2 => Some("0b"),
+        _ => None,
+    };
+    let digits = prefix
+        .and_then(|prefix| {
+            // `get()`, since the token may have a multibyte character here.
+            let head = digits.get(..prefix.len())?;
+            if head.eq_ignore_ascii_case(prefix) {
+                Some(&digits[prefix.len()..])
+            } else {
+                None
+            }
+        })
+        .unwrap_or(digits);
+
+    // the sign is checked above, so `+` or `-` in `digits` makes it invalid.
+    if digits.starts_with(['+', '-']) {
+        return token.to_string();
+    }
+    match u128::from_str_radix(digits, radix) {
+        Ok(value) => alloc::format!("{}{}", sign, value),
+        Err(_) => token.to_string(),
+    }
+}
+
+/// Source applying `Config` to the tokens of another source.
+///
+/// This is created by `with { ... }` of `input!`.
+pub struct ConfiguredSource<S> {
+    inner: S,
+    config: Config,
+    pending: VecDeque<String>,
+    current: String,
+}
+
+impl<S> ConfiguredSource<S> {
+    /// Creates `ConfiguredSource` applying `config` to `source`.
+    pub fn new(source: S, config: Config) -> ConfiguredSource<S> {
+        ConfiguredSource {
+            inner: source,
+            config,
+            pending: VecDeque::new(),
+            current: String::new(),
+        }
+    }
+
+    /// Gets the wrapped source.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Checks that all the pieces of the tokens split by `sep` are read.
+    ///
+    /// `input!` calls this at the end of the invocation, since the pieces left are lost.
+    pub fn finish(&self) {
+        if !self.pending.is_empty() {
+            let pieces: Vec<&str> = self.pending.iter().map(String::as_str).collect();
+            panic!(
+                "failed to finish the input: the rest of the token split by `sep` is not consumed: \
+                 `{}`",
+                pieces.join(" ")
+            );
+        }
+    }
+
+    // reads the tokens of the inner source until a token is pending.  Returns `false` at the end.
+    fn fill<R: BufRead>(&mut self) -> bool
+    where
+        S: Source<R>,
+    {
+        while self.pending.is_empty() {
+            let token = match self.inner.next_token() {
+                Some(token) => token,
+                None => return false,
+            };
+
+            let separators = &self.config.separators;
+            if separators.is_empty() {
+                self.pending.push_back(token.to_string());
+            } else {
+                self.pending.extend(
+                    token
+                        .split(|c| separators.contains(&c))
+                        .filter(|piece| !piece.is_empty())
+                        .map(str::to_string),
+                );
+            }
+        }
+
+        true
+    }
+}
+
+impl<R: BufRead, S: Source<R>> Source<R> for ConfiguredSource<S> {
+    fn next_token(&mut self) -> Option<&str> {
+        if !self.fill() {
+            return None;
+        }
+
+        self.current = self.pending.pop_front()?;
+        Some(&self.current)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        !self.fill()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        if !self.fill() {
+            return None;
+        }
+
+        self.pending.front().map(String::as_str)
+    }
+
+    fn skip_line(&mut self) {
+        assert!(
+            self.pending.is_empty(),
+            "failed to skip the line: the rest of the token split by `sep` is not consumed"
+        );
+        self.inner.skip_line()
+    }
+
+    fn end_line(&mut self) {
+        assert!(
+            self.pending.is_empty(),
+            "failed to end the line: the rest of the token split by `sep` is not consumed"
+        );
+        self.inner.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        assert!(
+            self.pending.is_empty(),
+            "failed to read the line: the rest of the token split by `sep` is not consumed"
+        );
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        assert!(
+            self.pending.is_empty(),
+            "failed to save a checkpoint: the rest of the token split by `sep` is not consumed"
+        );
+        self.inner.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.pending.clear();
+        self.inner.rewind(checkpoint)
+    }
+
+    fn radix(&self) -> Option<u32> {
+        self.config.radix.or_else(|| self.inner.radix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::source::slice::SliceSource;
+
+    #[test]
+    fn separators() {
+        let mut source =
+            ConfiguredSource::new(SliceSource::from("a,b;;c ,\nd; 1"), Config::new().sep(",;"));
+        assert_eq!(source.peek_token(), Some("a"));
+        assert_eq!(source.next_token(), Some("a"));
+        assert_eq!(source.next_token(), Some("b"));
+        assert_eq!(source.next_token(), Some("c"));
+        assert_eq!(source.next_token(), Some("d"));
+        assert!(!source.is_empty());
+        assert_eq!(source.next_token(), Some("1"));
+        assert!(source.is_empty());
+        assert_eq!(source.next_token(), None);
+    }
+
+    #[test]
+    fn radix() {
+        assert_eq!(to_decimal("ff", 16), "255");
+        assert_eq!(to_decimal("-0XfF", 16), "-255");
+        assert_eq!(to_decimal("+0x", 16), "+0x");
+        assert_eq!(to_decimal("--1", 16), "--1");
+        assert_eq!(to_decimal("xyz", 16), "xyz");
+        assert_eq!(to_decimal("a\u{e9}", 16), "a\u{e9}");
+        assert_eq!(to_decimal("0b101", 2), "5");
+        assert_eq!(to_decimal("z", 36), "35");
+    }
+
+    #[test]
+    fn radix_only_integers() {
+        let source = SliceSource::from("ff a\u{e9} 10 cafe 10 1.5 -0x10 10");
+        input! {
+            from source,
+            with { radix = 16 },
+            x: u8,
+            s: String,
+            t: String,
+            c: crate::marker::Chars,
+            i: crate::marker::Usize1,
+            f: f64,
+            y: (i32,),
+            z: {u8},
+        }
+
+        assert_eq!(x, 255);
+        assert_eq!(s, "a\u{e9}");
+        assert_eq!(t, "10");
+        assert_eq!(c, ['c', 'a', 'f', 'e']);
+        assert_eq!(i, 15);
+        assert_eq!(f, 1.5);
+        assert_eq!(y, (-16,));
+        assert_eq!(z, 10);
+    }
+
+    #[test]
+    fn input_with() {
+        let source = SliceSource::from("2 1:10 0o11:77\nx y\n0");
+        input! {
+            from source,
+            with { sep = ":", radix = 8 },
+            n: usize,
+            a: [(u8, u8); n],
+            _: skip_line,
+            b: u8,
+        }
+
+        assert_eq!(a, [(1, 8), (9, 63)]);
+        assert_eq!(b, 0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to finish the input: the rest of the token split by `sep` is not consumed: `c d`"
+    )]
+    fn pieces_left() {
+        let source = SliceSource::from("a,b,c,d");
+        input! {
+            from source,
+            with { sep = "," },
+            _s: [String; 2],
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the radix 1 is not in `2..=36`")]
+    fn invalid_radix() {
+        Config::new().radix(1);
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading CSV or TSV records.
+//!
+//! `CsvSource` reads the input record by record, and the fields of the current record are the
+//! tokens.  Reading is scoped to a record: once the fields of the current record run out, no
+//! token is available until the source moves to the next record by `next_row()`.  So a value
+//! never spans two records, and a record with too few fields is reported as an error.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::csv::CsvSource;
+//!
+//! let mut source = CsvSource::new("name,score\nalice,80\n\"bob, jr.\",95\n".as_bytes());
+//! assert_eq!(source.skip_header(), ["name", "score"]);
+//!
+//! let mut total = 0;
+//! while source.next_row() {
+//!     input! {
+//!         from &mut source,
+//!         _name: String,
+//!         score: u32,
+//!     }
+//!     total += score;
+//! }
+//! assert_eq!(total, 175);
+//! ```
+//!
+//! With `#[derive_readable]`, `rows()` reads each record as a struct:
+//!
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! # extern crate proconio;
+//! use proconio::derive_readable;
+//! use proconio::marker::Usize1;
+//! use proconio::source::csv::CsvSource;
+//!
+//! #[derive_readable]
+//! struct Edge {
+//!     from: Usize1,
+//!     to: Usize1,
+//!     cost: i64,
+//! }
+//!
+//! let mut source = CsvSource::tsv("1\t2\t10\n2\t3\t-5\n".as_bytes());
+//! let edges: Vec<_> = source.rows::<Edge>().collect();
+//! assert_eq!(edges[1].from, 1);
+//! assert_eq!(edges[1].cost, -5);
+//! # }
+//! ```
+//!
+//! A field may be quoted by `"` to contain the delimiter, and `""` in a quoted field stands for
+//! `"`.  Unquoted fields are trimmed.  A quoted field spanning multiple lines is not supported.
+
+use super::{Readable, Source};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::marker::PhantomData;
+
+/// Source reading CSV or TSV records.
+pub struct CsvSource<R: BufRead> {
+    reader: R,
+    delimiter: char,
+
+    // the fields of the current record.  `None` before the first record.
+    fields: Option<VecDeque<String>>,
+    current: String,
+
+    // 1-indexed number of the current line, for error messages.
+    line: usize,
+}
+
+impl<R: BufRead> CsvSource<R> {
+    /// Creates `CsvSource` reading comma-separated records.
+    pub fn new(reader: R) -> CsvSource<R> {
+        CsvSource::with_delimiter(reader, ',')
+    }
+
+    /// Creates `CsvSource` reading tab-separated records.
+    pub fn tsv(reader: R) -> CsvSource<R> {
+        CsvSource::with_delimiter(reader, '\t')
+    }
+
+    /// Creates `CsvSource` reading records separated by the specified delimiter.
+    pub fn with_delimiter(reader: R, delimiter: char) -> CsvSource<R> {
+        CsvSource {
+            reader,
+            delimiter,
+            fields: None,
+            current: String::new(),
+            line: 0,
+        }
+    }
+
+    /// Reads the first record as the header and returns its fields.
+    ///
+    /// This must be called before reading any record.
+    pub fn skip_header(&mut self) -> Vec<String> {
+        assert!(
+            self.fields.is_none(),
+            "the header must be skipped before reading any record"
+        );
+
+        match self.read_record() {
+            Some(header) => header.into_iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Moves to the next record, discarding the rest of the current one.
+    ///
+    /// Returns `false` if there are no more records.
+    pub fn next_row(&mut self) -> bool {
+        self.fields = self.read_record();
+        self.fields.is_some()
+    }
+
+    /// Returns an iterator reading each of the remaining records as `T`.
+    ///
+    /// Panics if a record has fields not consumed by `T`.
+    pub fn rows<T: Readable>(&mut self) -> Rows<'_, R, T> {
+        Rows {
+            source: self,
+            _marker: PhantomData,
+        }
+    }
+
+    fn read_record(&mut self) -> Option<VecDeque<String>> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .expect("failed to get linel maybe an IO error.");
+            if read == 0 {
+                return None;
+            }
+
+            self.line += 1;
+            // blank lines are not records.
+            if !line.trim().is_empty() {
+                return Some(split_record(&line, self.delimiter, self.line));
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Source<R> for CsvSource<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        if self.fields.is_none() {
+            self.fields = self.read_record();
+        }
+
+        self.current = self.fields.as_mut()?.pop_front()?;
+        Some(&self.current)
+    }
+
+    /// Check if the fields of the current record are exhausted.
+    fn is_empty(&mut self) -> bool {
+        if self.fields.is_none() {
+            self.fields = self.read_record();
+        }
+
+        match &self.fields {
+            Some(fields) => fields.is_empty(),
+            None => true,
+        }
+    }
+
+    /// Gets the next field of the current record without consuming it.
+    fn peek_token(&mut self) -> Option<&str> {
+        if self.fields.is_none() {
+            self.fields = self.read_record();
+        }
+
+        self.fields.as_ref()?.front().map(String::as_str)
+    }
+
+    /// Skips the next field and the rest of the current record.
+    fn skip_line(&mut self) {
+        self.next_token_unwrap();
+        if let Some(fields) = &mut self.fields {
+            fields.clear();
+        }
+    }
+}
+
+/// Iterator reading each record as `T`.  Created by `CsvSource::rows()`.
+pub struct Rows<'a, R: BufRead, T> {
+    source: &'a mut CsvSource<R>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, R: BufRead, T: Readable> Iterator for Rows<'a, R, T> {
+    type Item = T::Output;
+
+    fn next(&mut self) -> Option<T::Output> {
+        if !self.source.next_row() {
+            return None;
+        }
+
+        let value = T::read(self.source);
+        let left = self.source.fields.as_ref().map(VecDeque::len).unwrap_or(0);
+        if left > 0 {
+            panic!(
+                "failed to read the record at line {}: {} fields are left unread",
+                self.source.line, left
+            );
+        }
+
+        Some(value)
+    }
+}
+
+fn split_record(line: &str, delimiter: char, line_no: usize) -> VecDeque<String> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let mut fields = VecDeque::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        // skip the leading spaces of the field, unless they are the delimiter itself.
+        while matches!(chars.peek(), Some(&c) if c != delimiter && c.is_whitespace()) {
+            chars.next();
+        }
+
+        let mut field = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => panic!(
+                        "failed to read the record at line {}: the quoted field is not closed",
+                        line_no
+                    ),
+                }
+            }
+
+            while matches!(chars.peek(), Some(&c) if c != delimiter && c.is_whitespace()) {
+                chars.next();
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == delimiter {
+                    break;
+                }
+                field.push(c);
+                chars.next();
+            }
+            let trimmed = field.trim_end().len();
+            field.truncate(trimmed);
+        }
+
+        fields.push_back(field);
+        match chars.next() {
+            Some(c) if c == delimiter => continue,
+            Some(c) => panic!(
+                "failed to read the record at line {}: unexpected `{}` after the quoted field",
+                line_no, c
+            ),
+            None => return fields,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    enum Point {}
+
+    impl Readable for Point {
+        type Output = (i32, i32);
+        fn read<R: BufRead, S: Source<R>>(source: &mut S) -> (i32, i32) {
+            (i32::read(source), i32::read(source))
+        }
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let fields = split_record(" a , \"b, \"\"c\"\"\" ,,d\r\n", ',', 1);
+        assert_eq!(fields, ["a", "b, \"c\"", "", "d"]);
+    }
+
+    #[test]
+    fn rows() {
+        let mut source = CsvSource::new("x,y\n1,2\n\n-3, 4\n".as_bytes());
+        assert_eq!(source.skip_header(), ["x", "y"]);
+
+        let points: Vec<_> = source.rows::<Point>().collect();
+        assert_eq!(points, [(1, 2), (-3, 4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the record at line 2: 1 fields are left unread")]
+    fn rows_with_extra_fields() {
+        let mut source = CsvSource::new("1,2\n3,4,5\n".as_bytes());
+        source.rows::<Point>().for_each(drop);
+    }
+
+    #[test]
+    fn skip_line() {
+        let mut source = CsvSource::new("a,b,c\n1,2\n".as_bytes());
+        input! {
+            from &mut source,
+            _: skip_line,
+        }
+        assert!(source.is_empty());
+        assert!(source.next_row());
+        assert_eq!(source.next_token(), Some("1"));
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to get the next token")]
+    fn record_scoped() {
+        let mut source = CsvSource::tsv("1\n2\t3\n".as_bytes());
+        input! {
+            from &mut source,
+            _p: Point,
+        }
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading JSON input.
+//!
+//! This module requires `json` feature enabled.  Some platforms deliver the test data as JSON
+//! arrays.  `JsonSource` flattens the array into tokens, so that the existing solutions can read
+//! it as is.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::json::JsonSource;
+//!
+//! let source = JsonSource::from(r#"[3, [1, 2, 3], "abc", 1.5]"#);
+//! input! {
+//!     from source,
+//!     n: usize,
+//!     a: [u32; n],
+//!     s: String,
+//!     x: f64,
+//! }
+//!
+//! assert_eq!(a, [1, 2, 3]);
+//! assert_eq!(s, "abc");
+//! assert_eq!(x, 1.5);
+//! ```
+//!
+//! Nested arrays are flattened in order.  Numbers become the tokens as written, so big integers
+//! such as `123456789012345678901234567890` can be read as `u128` without losing the digits.
+//! Strings become the tokens as is (even if they contain whitespaces), and booleans become `true`
+//! or `false`.  `null` and objects are rejected.
+
+use super::Source;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::marker::PhantomData;
+
+/// Source reading a JSON value as the sequence of tokens.
+///
+/// The entire content is read at first, as `OnceSource` does.
+pub struct JsonSource<R: BufRead> {
+    tokens: VecDeque<String>,
+    current: String,
+
+    // to consume `R`, for the consistency with `OnceSource`.
+    _read: PhantomData<R>,
+}
+
+impl<R: BufRead> JsonSource<R> {
+    /// Creates `Source` using specified reader of `BufRead`.
+    pub fn new(mut source: R) -> JsonSource<R> {
+        let mut input = String::new();
+        source
+            .read_to_string(&mut input)
+            .expect("failed to read from source; maybe an IO error.");
+
+        let mut parser = Parser {
+            input: input.as_bytes(),
+            pos: 0,
+            tokens: VecDeque::new(),
+        };
+        if let Err(reason) = parser.parse() {
+            panic!("failed to read the input as JSON: {}", reason);
+        }
+
+        JsonSource {
+            tokens: parser.tokens,
+            current: String::new(),
+            _read: PhantomData,
+        }
+    }
+}
+
+// flattens a JSON value into the tokens.
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    tokens: VecDeque<String>,
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<(), String> {
+        self.value()?;
+        self.skip_whitespaces();
+        match self.input.get(self.pos) {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("the end of the input")),
+        }
+    }
+
+    fn value(&mut self) -> Result<(), String> {
+        self.skip_whitespaces();
+        match self.input.get(self.pos) {
+            Some(b'[') => self.array(),
+            Some(b'"') => {
+                let string = self.string()?;
+                self.tokens.push_back(string);
+                Ok(())
+            }
+            Some(b'-') | Some(b'0'..=b'9') => self.number(),
+            Some(b't') => self.literal("true", true),
+            Some(b'f') => self.literal("false", true),
+            Some(b'n') => {
+                self.literal("null", false)?;
+                panic!("failed to read the JSON input: `null` is not a token")
+            }
+            Some(b'{') => panic!("failed to read the JSON input: objects are not supported"),
+            _ => Err(self.unexpected("a value")),
+        }
+    }
+
+    fn array(&mut self) -> Result<(), String> {
+        self.pos += 1;
+        self.skip_whitespaces();
+        if self.input.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        loop {
+            self.value()?;
+            self.skip_whitespaces();
+            match self.input.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.unexpected("`,` or `]`")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut res = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.input.get(self.pos), None | Some(b'"') | Some(b'\\')) {
+                self.pos += 1;
+            }
+            // the input is a `str` and the boundaries are ASCII, so this is a valid UTF-8.
+            res.push_str(std::str::from_utf8(&self.input[start..self.pos]).unwrap());
+
+            match self.input.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(res);
+                }
+                Some(_) => res.push(self.escape()?),
+                None => return Err(self.unexpected("`\"`")),
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<char, String> {
+        self.pos += 1;
+        let escaped = self.input.get(self.pos).copied();
+        self.pos += 1;
+        let c = match escaped {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                let high = self.hex4()?;
+                if !(0xd800..0xdc00).contains(&high) {
+                    return char::from_u32(high).ok_or_else(|| self.invalid_escape());
+                }
+                // a surrogate pair.
+                if self.input.get(self.pos..self.pos + 2) != Some(b"\\u") {
+                    return Err(self.invalid_escape());
+                }
+                self.pos += 2;
+                let low = self.hex4()?;
+                if !(0xdc00..0xe000).contains(&low) {
+                    return Err(self.invalid_escape());
+                }
+                let c = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+                return char::from_u32(c).ok_or_else(|| self.invalid_escape());
+            }
+            _ => return Err(self.invalid_escape()),
+        };
+        Ok(c)
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.invalid_escape())?;
+        self.pos += 4;
+        Ok(digits)
+    }
+
+    fn number(&mut self) -> Result<(), String> {
+        let start = self.pos;
+        self.eat(b'-');
+        match self.input.get(self.pos) {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.unexpected("a digit")),
+        }
+        if self.eat(b'.') {
+            self.require_digits()?;
+        }
+        if self.eat(b'e') || self.eat(b'E') {
+            let _ = self.eat(b'+') || self.eat(b'-');
+            self.require_digits()?;
+        }
+
+        let number = std::str::from_utf8(&self.input[start..self.pos]).unwrap();
+        self.tokens.push_back(number.to_string());
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &str, push: bool) -> Result<(), String> {
+        if !self.input[self.pos..].starts_with(literal.as_bytes()) {
+            return Err(self.unexpected("a value"));
+        }
+        self.pos += literal.len();
+        if push {
+            self.tokens.push_back(literal.to_string());
+        }
+        Ok(())
+    }
+
+    fn require_digits(&mut self) -> Result<(), String> {
+        match self.input.get(self.pos) {
+            Some(b'0'..=b'9') => {
+                self.digits();
+                Ok(())
+            }
+            _ => Err(self.unexpected("a digit")),
+        }
+    }
+
+    fn digits(&mut self) {
+        while matches!(self.input.get(self.pos), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let matched = self.input.get(self.pos) == Some(&b);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while matches!(
+            self.input.get(self.pos),
+            Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r')
+        ) {
+            self.pos += 1;
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        match self.input.get(self.pos) {
+            Some(_) => {
+                let found = String::from_utf8_lossy(&self.input[self.pos..]);
+                let found = found.chars().next().unwrap();
+                format!(
+                    "expected {} at byte {}, found `{}`",
+                    expected, self.pos, found
+                )
+            }
+            None => format!("expected {}, found the end of the input", expected),
+        }
+    }
+
+    fn invalid_escape(&self) -> String {
+        format!("invalid escape sequence before byte {}", self.pos)
+    }
+}
+
+impl<R: BufRead> Source<R> for JsonSource<R> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.current = self.tokens.pop_front()?;
+        Some(&self.current)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.front().map(String::as_str)
+    }
+}
+
+impl<'a> From<&'a str> for JsonSource<BufReader<&'a [u8]>> {
+    fn from(s: &'a str) -> JsonSource<BufReader<&'a [u8]>> {
+        JsonSource::new(BufReader::new(s.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Chars;
+
+    #[test]
+    fn flatten_nested() {
+        let source = JsonSource::from("[[2, -3], [[true]], \"a b\", 12345678901234567890]");
+        input! {
+            from source,
+            pair: (i32, i32),
+            flag: bool,
+            s: Chars,
+            big: u64,
+        }
+
+        assert_eq!(pair, (2, -3));
+        assert!(flag);
+        assert_eq!(s, ['a', ' ', 'b']);
+        assert_eq!(big, 12_345_678_901_234_567_890);
+    }
+
+    #[test]
+    fn keep_tokens_as_written() {
+        let source = JsonSource::from(
+            r#" [123456789012345678901234567890, -0.50, 1e-3, "\"\u00e9\ud83d\ude00\n"] "#,
+        );
+        input! {
+            from source,
+            big: u128,
+            tokens: [String; 3],
+        }
+
+        assert_eq!(big, 123_456_789_012_345_678_901_234_567_890);
+        assert_eq!(tokens, ["-0.50", "1e-3", "\"\u{e9}\u{1f600}\n"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the JSON input: objects are not supported")]
+    fn reject_objects() {
+        let _ = JsonSource::from(r#"[1, {"n": 2}]"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the input as JSON")]
+    fn reject_invalid_json() {
+        let _ = JsonSource::from("[1, 2");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "failed to read the input as JSON: expected a digit at byte 2, found `]`"
+    )]
+    fn reject_invalid_number() {
+        let _ = JsonSource::from("[-]");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to read the input as JSON: expected the end of the input")]
+    fn reject_trailing_characters() {
+        let _ = JsonSource::from("[1] 2");
+    }
+}
@@ -5,7 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
 // distributed except according to those terms.
 
-use super::Source;
+use super::{trim_line_end, Position, Source};
 use std::io::BufRead;
 use std::iter::Peekable;
 use std::str::SplitWhitespace;
@@ -20,10 +20,24 @@ pub struct LineSource<R: BufRead> {
     // separate context and tokens since they are private field, this is safe.
     tokens: Peekable<SplitWhitespace<'static>>,
 
-    // context `tokens` reffering to
-    current_context: Box<str>,
+    // context `tokens` reffering to.  The buffer is reused for every line, so that reading many
+    // lines does not allocate each time.
+    current_context: String,
 
     reader: R,
+
+    // the number of the lines read so far, which is the 1-indexed number of the current line.
+    line: usize,
+
+    // the line of the first token read since the previous `end_line()`.
+    group_start: Option<usize>,
+
+    // whether no token of the current line is read yet.
+    fresh: bool,
+
+    // the number of the tokens read from the current line, and the position of the last one.
+    read_in_line: usize,
+    last: Option<Position>,
 }
 
 impl<R: BufRead> LineSource<R> {
@@ -31,31 +45,57 @@ impl<R: BufRead> LineSource<R> {
     pub fn new(reader: R) -> LineSource<R> {
         // dummy values.
         LineSource {
-            current_context: "".to_string().into_boxed_str(),
+            current_context: String::new(),
             tokens: "".split_whitespace().peekable(),
             reader,
+            line: 0,
+            group_start: None,
+            fresh: false,
+            read_in_line: 0,
+            last: None,
         }
     }
 
     fn prepare(&mut self) {
         while self.tokens.peek().is_none() {
-            let mut line = String::new();
-            let num_bytes = self
-                .reader
-                .read_line(&mut line)
-                .expect("failed to get linel maybe an IO error.");
-
-            if num_bytes == 0 {
+            if !self.read_line() {
                 // reached EOF
+                self.fresh = false;
                 return;
             }
 
-            self.current_context = line.into_boxed_str();
-            self.tokens = unsafe { std::mem::transmute::<_, &'static str>(&*self.current_context) }
-                .split_whitespace()
-                .peekable();
+            self.fresh = true;
+            self.read_in_line = 0;
+            self.tokens =
+                unsafe { std::mem::transmute::<&str, &'static str>(&*self.current_context) }
+                    .split_whitespace()
+                    .peekable();
         }
     }
+
+    // gets the next token of the line read already, without reading a new line even if the line
+    // is over.  `peek_token()` would block on stdin waiting for the next line.
+    #[cfg_attr(not(debug_assertions), allow(dead_code))]
+    pub(crate) fn buffered_token(&mut self) -> Option<&str> {
+        self.tokens.peek().copied()
+    }
+
+    // reads the next line into the buffer, reusing its allocation.  Returns `false` at EOF.
+    fn read_line(&mut self) -> bool {
+        // `tokens` refers to the buffer, so it must be dropped before the buffer is modified.
+        self.tokens = "".split_whitespace().peekable();
+        self.current_context.clear();
+        let num_bytes = self
+            .reader
+            .read_line(&mut self.current_context)
+            .expect("failed to get linel maybe an IO error.");
+
+        if num_bytes == 0 {
+            return false;
+        }
+        self.line += 1;
+        true
+    }
 }
 
 impl<R: BufRead> Source<R> for LineSource<R> {
@@ -63,7 +103,19 @@ impl<R: BufRead> Source<R> for LineSource<R> {
     fn next_token(&mut self) -> Option<&str> {
         // while tokens are empty, reads a new line.
         self.prepare();
-        self.tokens.next()
+        let token = self.tokens.next();
+        if token.is_some() {
+            self.fresh = false;
+            self.read_in_line += 1;
+            self.last = Some(Position {
+                line: self.line,
+                token: self.read_in_line,
+            });
+            if self.group_start.is_none() {
+                self.group_start = Some(self.line);
+            }
+        }
+        token
     }
 
     /// Check if tokens are empty
@@ -71,6 +123,70 @@ impl<R: BufRead> Source<R> for LineSource<R> {
         self.prepare();
         self.tokens.peek().is_none()
     }
+
+    /// Gets a next token without consuming it.
+    fn peek_token(&mut self) -> Option<&str> {
+        self.prepare();
+        self.tokens.peek().copied()
+    }
+
+    /// Skips the next token and the rest of the line.
+    fn skip_line(&mut self) {
+        self.next_token_unwrap();
+        self.tokens = "".split_whitespace().peekable();
+        self.group_start = None;
+    }
+
+    /// Gets the next line as is.
+    fn next_line(&mut self) -> Option<&str> {
+        // the line read by `prepare()` for peeking is not consumed yet.
+        if !self.fresh && !self.read_line() {
+            return None;
+        }
+
+        self.fresh = false;
+        self.tokens = "".split_whitespace().peekable();
+        self.group_start = None;
+        self.last = Some(Position {
+            line: self.line,
+            token: 0,
+        });
+        Some(trim_line_end(&self.current_context))
+    }
+
+    /// Gets the position of the last token or line read.
+    fn position(&self) -> Option<Position> {
+        self.last
+    }
+
+    /// Checks that the tokens since the previous call came from one line and the line is over.
+    fn end_line(&mut self) {
+        // `peek_token()` and `is_empty()` may have read the next line already, so the line is the
+        // one of the last token.
+        let line = self.last.map_or(self.line, |last| last.line);
+        if let Some(start) = self.group_start.take() {
+            if start != line {
+                panic!(
+                    concat!(
+                        "failed to end the line {}: the values are read from the lines {} to {}; ",
+                        "maybe the line {} has fewer tokens than expected"
+                    ),
+                    line, start, line, start
+                );
+            }
+        }
+
+        // the tokens of the next line read for peeking are not the rest of the line.
+        if self.fresh {
+            return;
+        }
+        if let Some(token) = self.tokens.peek() {
+            panic!(
+                "failed to end the line {}: the line has more tokens than expected, from `{}`",
+                line, token
+            );
+        }
+    }
 }
 
 use std::io::BufReader;
@@ -82,3 +198,9 @@ impl<'a> From<&'a str> for LineSource<BufReader<&'a [u8]>> {
         LineSource::new(BufReader::new(s.as_bytes()))
     }
 }
+
+impl<'a> From<&'a [u8]> for LineSource<BufReader<&'a [u8]>> {
+    fn from(s: &'a [u8]) -> LineSource<BufReader<&'a [u8]>> {
+        LineSource::new(BufReader::new(s))
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading memory-mapped input.
+//!
+//! This module requires `mmap` feature enabled.  For inputs of hundreds of megabytes, copying the
+//! whole input into a `String` is not negligible.  `MmapSource` maps the file into memory instead
+//! and tokenizes it in place, like `bytes::ByteSource` does.  If the input cannot be mapped (stdin
+//! connected to a pipe or a terminal, for example), it falls back to reading the entire input at
+//! first.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::mmap::MmapSource;
+//!
+//! // maps stdin if it is redirected from a file: `./solution < in.txt`
+//! input! {
+//!     from MmapSource::stdin(),
+//!     n: usize,
+//!     a: [i64; n],
+//! }
+//! ```
+//!
+//! Note that the content of a mapped file must not be modified while the source is alive.
+
+use super::slice::ByteTokens;
+use super::{Checkpoint, Position, Source};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+enum Storage {
+    Mapped(Mmap),
+    Read(Box<[u8]>),
+}
+
+impl AsRef<[u8]> for Storage {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Storage::Mapped(map) => map,
+            Storage::Read(context) => context,
+        }
+    }
+}
+
+/// Source reading memory-mapped file or stdin.
+pub struct MmapSource {
+    tokens: ByteTokens<Storage>,
+}
+
+impl MmapSource {
+    /// Maps the file at the path.
+    pub fn open<P: AsRef<Path>>(path: P) -> MmapSource {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .unwrap_or_else(|e| panic!("failed to open the file `{}`: {}", path.display(), e));
+        MmapSource::from_file(file)
+    }
+
+    /// Maps the opened file.
+    pub fn from_file(file: File) -> MmapSource {
+        // Safety: the file is assumed not to be modified while it is mapped, as documented.
+        let map = unsafe { Mmap::map(&file) };
+        MmapSource::from_map_or_read(map, file)
+    }
+
+    /// Maps stdin if it is redirected from a file.  Otherwise, reads entire stdin at first.
+    pub fn stdin() -> MmapSource {
+        let stdin = io::stdin();
+        // Safety: same as `from_file()`.
+        let map = unsafe { Mmap::map(&stdin) };
+        MmapSource::from_map_or_read(map, stdin.lock())
+    }
+
+    /// Checks if the input is actually memory-mapped.
+    pub fn is_mapped(&self) -> bool {
+        match self.tokens.context() {
+            Storage::Mapped(_) => true,
+            Storage::Read(_) => false,
+        }
+    }
+
+    fn from_map_or_read<R: Read>(map: io::Result<Mmap>, mut source: R) -> MmapSource {
+        let storage = match map {
+            Ok(map) => Storage::Mapped(map),
+            Err(_) => {
+                let mut context = Vec::new();
+                source
+                    .read_to_end(&mut context)
+                    .expect("failed to read from source; maybe an IO error.");
+                Storage::Read(context.into_boxed_slice())
+            }
+        };
+
+        MmapSource {
+            tokens: ByteTokens::new(storage),
+        }
+    }
+}
+
+// `MmapSource` does not use a reader; the type parameter is fixed to the one for files.
+impl Source<BufReader<File>> for MmapSource {
+    fn next_token(&mut self) -> Option<&str> {
+        self.tokens.next_str()
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.tokens.next()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.peek_str()
+    }
+
+    fn skip_line(&mut self) {
+        self.tokens.skip_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.tokens.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.tokens.position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.tokens.checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.tokens.rewind(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MmapSource;
+    use crate::input;
+    use crate::marker::Bytes;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn mapped_file() {
+        let path = env::temp_dir().join(format!("proconio-mmap-test-{}", std::process::id()));
+        fs::write(&path, "3\n1 2 3\nabc\n").unwrap();
+
+        let source = MmapSource::open(&path);
+        assert!(source.is_mapped());
+        input! {
+            from source,
+            n: usize,
+            a: [u32; n],
+            s: Bytes,
+        }
+        assert_eq!(a, [1, 2, 3]);
+        assert_eq!(s, b"abc");
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -47,19 +47,105 @@
 //! directly from the value of type implementing `BufRead` by using `OnceSource::new()` and
 //! `LineSource::new()`.
 //!
+//! For large ASCII inputs, `bytes::ByteSource` reads the input as raw bytes and skips UTF-8
+//! validation of the whole input.  `input!` without `from` reads stdin by it after
+//! `read_stdin_as_bytes()`.  `OnceSource`, `LineSource` and `ByteSource` can also be created from
+//! `&[u8]`.
+//! `ByteSource::with_delimiters()` splits tokens by other characters, such as commas, so that
+//! comma-separated inputs can be read without preprocessing.
+//! `chunk::ChunkSource` pulls the input chunk by chunk from a callback, for the environments
+//! without a reader.
+//! For CSV or TSV records, `csv::CsvSource` reads the fields record by record.
+//! With `json` feature, `json::JsonSource` reads the tokens from a JSON array.
+//! With `async` feature, `asynchronous::AsyncSource` reads `tokio::io::AsyncBufRead` without
+//! blocking, by `input_async!`.
+//!
+//! `slice::SliceSource` reads the tokens from a byte slice without copying it.  This is the source
+//! available without `std` feature: the core of this crate, `Source`, `Readable`, markers and
+//! `input!` with `from`, works with `no_std` and `alloc` if `std` feature is disabled.
+//!
+//! For the input terminated by a sentinel, `Source::peek_token()` looks ahead the next token
+//! without consuming it, and `Source::next_if()` consumes it only if it is the sentinel.  For
+//! stdin, `peek_stdin_token()` does the same as `peek_token()`.
+//!
+//! `Source::take_tokens()` takes the next tokens out as `tokens::TokenSource`, an independent
+//! source owning them.  For example, test cases of known sizes can be taken out and read by other
+//! threads.
+//!
+//! To read the input twice, `Source::checkpoint()` saves the current point and `Source::rewind()`
+//! goes back to it.  This needs the source keeping the entire input, such as `OnceSource`;
+//! `LineSource`, and thus `AutoSource` in debug build, panics.
+//!
+//! To choose the source at runtime, such as a file or stdin by a command line argument, use
+//! `any::AnySource`.  `Source` is object safe, so `Box<dyn Source<R>>` is also a source.
+//!
+//! `args::ArgsSource` reads the command line arguments as the tokens.  For fuzzing, with
+//! `arbitrary` feature, `arbitrary::ArbitrarySource` reads any bytes given by a fuzzer, padding
+//! the end of the input, and `try_input!` reads them without panicking.
+//!
+//! To see which tokens were consumed, wrap the source with `tee::TeeSource`.  It records each token
+//! read through it into a log.  To save the input itself, wrap the reader with
+//! `record::RecordReader`, which writes the bytes read through it into a writer.
+//!
 //! If you use `input!` macro with no source specified then it uses `AutoSource` with stdin.  So,
 //! locally `LineSource` are used, in the server `OnceSource` are used.  `OnceSource` and
 //! `LineSource` behaves samely in point of the read result, but, unintentionally, it may differ in
 //! a bare possibility. If it should differ, you can manually specify `LineSource` as `source` of
 //! `input!`.
-use std::any::type_name;
-use std::fmt::Debug;
-use std::io::BufRead;
-use std::str::FromStr;
+use alloc::string::{String, ToString};
+use core::any::type_name;
+use core::fmt::{self, Debug, Display};
+use core::str::FromStr;
 
+#[cfg(feature = "std")]
+pub mod any;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "std")]
+pub mod args;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod bulk;
+#[cfg(feature = "std")]
+pub mod bytes;
+#[cfg(feature = "std")]
+pub mod chunk;
+#[cfg(feature = "compress")]
+pub mod compressed;
+pub mod config;
+#[cfg(feature = "std")]
+pub mod csv;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "std")]
 pub mod line;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "std")]
+pub mod net;
+#[cfg(feature = "std")]
 pub mod once;
+#[cfg(feature = "std")]
+pub mod record;
+pub mod slice;
+#[cfg(feature = "std")]
+pub mod tee;
+#[cfg(feature = "std")]
+pub mod tokens;
+
+/// The type parameter of `Source`, which is `std::io::BufRead`.
+#[cfg(feature = "std")]
+pub use std::io::BufRead;
+
+/// The type parameter of `Source`.  Without `std` feature, `std::io::BufRead` is unavailable and
+/// this trait takes its place.  It is implemented for `&[u8]`, the reader of `slice::SliceSource`.
+#[cfg(not(feature = "std"))]
+pub trait BufRead {}
 
+#[cfg(not(feature = "std"))]
+impl BufRead for &[u8] {}
+
+#[cfg(feature = "std")]
 pub mod auto {
     //! Defines `AutoSource`.
     //!
@@ -82,24 +168,365 @@ pub trait Source<R: BufRead> {
 
     /// Force gets a whitespace-splitted next token.
     fn next_token_unwrap(&mut self) -> &str {
-        self.next_token().expect(concat!(
-            "failed to get the next token; ",
-            "maybe reader reached an end of input. ",
-            "ensure that arguments for `input!` macro is correctly ",
-            "specified to match the problem input."
-        ))
+        self.next_token().expect(NO_TOKEN_MESSAGE)
+    }
+
+    /// Gets a whitespace-splitted next token as raw bytes.
+    ///
+    /// Sources holding the input as bytes (like `bytes::ByteSource`) override this to skip UTF-8
+    /// validation.
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.next_token().map(str::as_bytes)
+    }
+
+    /// Force gets a whitespace-splitted next token as raw bytes.
+    fn next_token_bytes_unwrap(&mut self) -> &[u8] {
+        self.next_token_bytes().expect(NO_TOKEN_MESSAGE)
+    }
+
+    /// Gets the next token without consuming it.
+    ///
+    /// The next `next_token()` returns the same token.  Sources not supporting the lookahead panic.
+    fn peek_token(&mut self) -> Option<&str> {
+        panic!("this source does not support peeking a token");
+    }
+
+    /// Consumes and returns the next token only if it satisfies the predicate.
+    ///
+    /// This is useful to stop at a sentinel, such as `0` terminating the datasets:
+    ///
+    /// ```
+    /// # extern crate proconio;
+    /// use proconio::input;
+    /// use proconio::source::once::OnceSource;
+    /// use proconio::source::Source;
+    ///
+    /// let mut source = OnceSource::from("2 1 2\n3 1 2 3\n0\n");
+    /// let mut sums = Vec::new();
+    /// while source.next_if(|token| token == "0").is_none() {
+    ///     input! {
+    ///         from &mut source,
+    ///         n: usize,
+    ///         a: [u32; n],
+    ///     }
+    ///     sums.push(a.iter().sum::<u32>());
+    /// }
+    /// assert_eq!(sums, [3, 6]);
+    /// ```
+    ///
+    /// If the sentinel has more than one token, like `0 0`, whose first token may start a dataset,
+    /// read the tokens as usual and break the loop after checking them.
+    fn next_if<F: FnOnce(&str) -> bool>(&mut self, f: F) -> Option<&str>
+    where
+        Self: Sized,
+    {
+        match self.peek_token() {
+            Some(token) if f(token) => self.next_token(),
+            _ => None,
+        }
+    }
+
+    /// Skips the next `n` tokens.
+    ///
+    /// The tokens are dropped as raw bytes, so nothing is allocated for them.
+    fn skip_tokens(&mut self, n: usize) {
+        for _ in 0..n {
+            self.next_token_bytes_unwrap();
+        }
+    }
+
+    /// Takes the next `k` tokens out as another source.
+    ///
+    /// The returned source owns the tokens, so it is independent of this source and can be sent to
+    /// another thread.  See `tokens::TokenSource` for details.
+    #[cfg(feature = "std")]
+    fn take_tokens(&mut self, k: usize) -> tokens::TokenSource {
+        let mut res = tokens::TokenSource::new();
+        for _ in 0..k {
+            res.push(self.next_token_unwrap());
+        }
+        res
+    }
+
+    /// Skips the next token and the rest of the line containing it.
+    ///
+    /// If the previous token ended a line, this skips the whole next line.  Blank lines have no
+    /// tokens, so they are skipped before the line.  Sources not aware of lines, such as
+    /// `json::JsonSource`, don't support this and panic.
+    fn skip_line(&mut self) {
+        panic!("this source does not support skipping a line");
+    }
+
+    /// Checks that the tokens read since the previous call came from exactly one line, and that
+    /// no token is left in the line.
+    ///
+    /// This is for `_: end_line` of `input!`, which catches the values spilling over the lines
+    /// because of the wrong format.  Only `line::LineSource` checks this; other sources do
+    /// nothing, so `AutoSource` checks it only in debug build.
+    fn end_line(&mut self) {}
+
+    /// Gets the next line as is, without the line terminator.
+    ///
+    /// If a token in the current line is already read, the rest of the line is skipped and the
+    /// next line is returned; this reads the lines of a grid after its size `h w`.  Unlike the
+    /// tokens, the spaces in the line are kept and an empty line is returned as `""`.  Returns
+    /// `None` at the end of the input.
+    ///
+    /// This is for `marker::Line` and `marker::LineBytes`.  Only the sources keeping the lines
+    /// support this; the default implementation panics.
+    fn next_line(&mut self) -> Option<&str> {
+        panic!("this source does not support reading a line");
+    }
+
+    /// Gets the position of the last token or line read, to tell where the program is in the
+    /// input when it panics.
+    ///
+    /// Only the sources keeping the lines know this; the default implementation returns `None`.
+    fn position(&self) -> Option<Position> {
+        None
+    }
+
+    /// Saves the current point of the input, to read the input again from there by `rewind()`.
+    ///
+    /// Only the sources keeping the entire input support this, such as `OnceSource` (that is,
+    /// `AutoSource` in release build), `ByteSource` and `SliceSource`.  The sources reading the
+    /// input as a stream, such as `LineSource`, have already dropped the input read; the default
+    /// implementation panics.
+    fn checkpoint(&mut self) -> Checkpoint {
+        panic!(
+            "failed to save a checkpoint: this source reads the input as a stream and can't rewind"
+        );
+    }
+
+    /// Goes back to the point saved by `checkpoint()` of this source, so that the tokens after it
+    /// are read again.
+    fn rewind(&mut self, _checkpoint: Checkpoint) {
+        panic!("failed to rewind: this source reads the input as a stream and can't rewind");
+    }
+
+    /// Gets the radix of the integers, set by `with { radix = ... }` of `input!`.
+    ///
+    /// The primitive integers given by their names to `input!`, including the elements of arrays
+    /// and tuples, and `Usize1` and `Isize1` are read in this radix, while the other values such
+    /// as strings are read as written.  Only `config::ConfiguredSource` sets this; the default
+    /// implementation returns `None`.
+    fn radix(&self) -> Option<u32> {
+        None
     }
 }
 
+/// The position of the last token or line read from a source.  See `Source::position()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The 1-indexed line.
+    pub line: usize,
+    /// The 1-indexed token in the line, or 0 if the entire line is read by `next_line()`.
+    pub token: usize,
+}
+
+/// A point of the input saved by `Source::checkpoint()`, to go back by `Source::rewind()`.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::source::once::OnceSource;
+/// use proconio::source::Source;
+///
+/// let mut source = OnceSource::from("3\n1 2 3\n");
+/// let checkpoint = source.checkpoint();
+/// input! {
+///     from &mut source,
+///     n: usize,
+///     a: [u32; n],
+/// }
+/// assert_eq!(a.iter().sum::<u32>(), 6);
+///
+/// // read the same input again.
+/// source.rewind(checkpoint);
+/// input! {
+///     from &mut source,
+///     n: usize,
+///     b: [u32; n],
+/// }
+/// assert_eq!(a, b);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    // the start of the rest of the tokens.
+    rest: usize,
+    // the end of the last token or line consumed, and whether it is in the middle of a line.
+    consumed: usize,
+    in_line: bool,
+}
+
+impl Checkpoint {
+    // checks that the checkpoint is in the input of `len` bytes.
+    fn check(&self, len: usize) {
+        assert!(
+            self.rest <= len,
+            "failed to rewind: the checkpoint is not saved by this source"
+        );
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.token == 0 {
+            write!(f, "the line {}", self.line)
+        } else {
+            write!(f, "the token {} of the line {}", self.token, self.line)
+        }
+    }
+}
+
+// computes the position in the entire input `context`, of the token ending at `consumed` if
+// `in_line`, or of the line ending just before `consumed` otherwise.
+fn position_in(
+    context: &[u8],
+    consumed: usize,
+    in_line: bool,
+    is_delimiter: impl Fn(u8) -> bool,
+) -> Option<Position> {
+    let count_lines = |bytes: &[u8]| bytes.iter().filter(|&&b| b == b'\n').count() + 1;
+    if !in_line {
+        return match consumed {
+            0 => None,
+            _ => Some(Position {
+                line: count_lines(&context[..consumed - 1]),
+                token: 0,
+            }),
+        };
+    }
+
+    let before = &context[..consumed];
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |pos| pos + 1);
+    let token = before[line_start..]
+        .split(|&b| is_delimiter(b))
+        .filter(|token| !token.is_empty())
+        .count();
+    Some(Position {
+        line: count_lines(&before[..line_start]),
+        token,
+    })
+}
+
+// removes `\n` or `\r\n` at the end of the line.
+#[cfg(feature = "std")]
+fn trim_line_end(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+const NO_TOKEN_MESSAGE: &str = concat!(
+    "failed to get the next token; ",
+    "maybe reader reached an end of input. ",
+    "ensure that arguments for `input!` macro is correctly ",
+    "specified to match the problem input."
+);
+
 // &mut S where S: Source is also source.
-impl<R: BufRead, S: Source<R>> Source<R> for &'_ mut S {
+impl<R: BufRead, S: Source<R> + ?Sized> Source<R> for &'_ mut S {
     fn next_token(&mut self) -> Option<&str> {
         (*self).next_token()
     }
 
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        (*self).next_token_bytes()
+    }
+
     fn is_empty(&mut self) -> bool {
         (*self).is_empty()
     }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        (*self).peek_token()
+    }
+
+    fn skip_tokens(&mut self, n: usize) {
+        (*self).skip_tokens(n)
+    }
+
+    fn skip_line(&mut self) {
+        (*self).skip_line()
+    }
+
+    fn end_line(&mut self) {
+        (*self).end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        (*self).next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        (**self).position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        (*self).checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        (*self).rewind(checkpoint)
+    }
+
+    fn radix(&self) -> Option<u32> {
+        (**self).radix()
+    }
+}
+
+// Box<S> where S: Source is also source, including `Box<dyn Source<R>>`.
+impl<R: BufRead, S: Source<R> + ?Sized> Source<R> for alloc::boxed::Box<S> {
+    fn next_token(&mut self) -> Option<&str> {
+        (**self).next_token()
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        (**self).next_token_bytes()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        (**self).is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        (**self).peek_token()
+    }
+
+    fn skip_tokens(&mut self, n: usize) {
+        (**self).skip_tokens(n)
+    }
+
+    fn skip_line(&mut self) {
+        (**self).skip_line()
+    }
+
+    fn end_line(&mut self) {
+        (**self).end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        (**self).next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        (**self).position()
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        (**self).checkpoint()
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        (**self).rewind(checkpoint)
+    }
+
+    fn radix(&self) -> Option<u32> {
+        (**self).radix()
+    }
 }
 
 /// A trait representing which type can be read from `Source`.
@@ -111,29 +538,207 @@ impl<R: BufRead, S: Source<R>> Source<R> for &'_ mut S {
 pub trait Readable {
     type Output;
     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> Self::Output;
+
+    /// Reads the value into `output`, overwriting the previous value.
+    ///
+    /// The markers reading a token or a line as a string or a vector, such as `marker::Bytes`,
+    /// reuse the allocation of `output`, so that reading many values in a loop doesn't allocate
+    /// for each of them.  The default implementation assigns the value returned by `read()`.
+    ///
+    /// ```
+    /// # extern crate proconio;
+    /// use proconio::marker::Bytes;
+    /// use proconio::source::auto::AutoSource;
+    /// use proconio::source::Readable;
+    ///
+    /// let mut source = AutoSource::from("abc de f");
+    /// let mut token = Vec::new();
+    /// let mut lengths = Vec::new();
+    /// for _ in 0..3 {
+    ///     Bytes::read_into(&mut source, &mut token);
+    ///     lengths.push(token.len());
+    /// }
+    /// assert_eq!(lengths, [3, 2, 1]);
+    /// assert_eq!(token, b"f");
+    /// ```
+    fn read_into<R: BufRead, S: Source<R>>(source: &mut S, output: &mut Self::Output) {
+        *output = Self::read(source);
+    }
+}
+
+/// A trait for the types read by `try_input!`, returning the error instead of panicking.
+///
+/// This is implemented for the `FromStr` types and the markers `Usize1`, `Isize1`, `Chars` and
+/// `Bytes`.  Unlike `Readable`, neither the end of the input nor an invalid token panics, so that
+/// any input, such as the bytes given by a fuzzer, can be read.
+pub trait TryReadable: Readable {
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<Self::Output, ReadError>;
+}
+
+/// The error of `TryReadable`, with the token and the type which failed to be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadError {
+    /// The position of the last token read, if the source knows it.
+    pub position: Option<Position>,
+    /// The token failed to be read, or `None` at the end of the input.
+    pub token: Option<String>,
+    /// The name of the type to which the token is read.
+    pub type_name: &'static str,
+    /// The reason of the failure.
+    pub reason: String,
 }
 
-// implementations of Readable for any `FromStr` types including primitives.
+impl ReadError {
+    pub(crate) fn new<T: ?Sized>(
+        position: Option<Position>,
+        token: Option<&str>,
+        reason: impl Display,
+    ) -> ReadError {
+        ReadError {
+            position,
+            token: token.map(ToString::to_string),
+            type_name: type_name::<T>(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(position) = self.position {
+            write!(f, "line {}, token {}: ", position.line, position.token)?;
+        }
+        match &self.token {
+            Some(token) => write!(
+                f,
+                "failed to parse the input `{}` to the value of type `{}`: {}",
+                token, self.type_name, self.reason
+            ),
+            None => write!(
+                f,
+                "failed to read the value of type `{}`: {}",
+                self.type_name, self.reason
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {}
+
+/// A trait for the types parsed from a fixed number of tokens, like `FromStr` for a single token.
+///
+/// This does not depend on `Source`, so a library can implement this without `proconio_derive`.
+/// Because of the implementation of `Readable` for `FromStr` types, this can't be `Readable` by
+/// itself; read it as `marker::Tokens<T>`.
+///
+/// ```
+/// # extern crate proconio;
+/// use proconio::input;
+/// use proconio::marker::Tokens;
+/// use proconio::source::FromTokens;
+/// use proconio::source::auto::AutoSource;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Range {
+///     start: u32,
+///     end: u32,
+/// }
+///
+/// impl FromTokens for Range {
+///     const TOKENS: usize = 2;
+///     type Err = &'static str;
+///     fn from_tokens(tokens: &[&str]) -> Result<Range, &'static str> {
+///         let start = tokens[0].parse().map_err(|_| "invalid start")?;
+///         let end = tokens[1].parse().map_err(|_| "invalid end")?;
+///         if start <= end {
+///             Ok(Range { start, end })
+///         } else {
+///             Err("start > end")
+///         }
+///     }
+/// }
+///
+/// let source = AutoSource::from("2 5");
+/// input! {
+///     from source,
+///     range: Tokens<Range>,
+/// }
+/// assert_eq!(range, Range { start: 2, end: 5 });
+/// ```
+pub trait FromTokens: Sized {
+    /// The number of tokens.
+    const TOKENS: usize;
+    type Err: Debug;
+    /// Parses the value from `Self::TOKENS` tokens.
+    fn from_tokens(tokens: &[&str]) -> Result<Self, Self::Err>;
+}
+
+// implementations of Readable for any `FromStr` types including primitives.  `input!` parses the
+// primitive integers by `bulk` instead, which falls back to this to report the invalid tokens.
 impl<T: FromStr> Readable for T
 where
     T::Err: Debug,
 {
     type Output = T;
     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> T {
-        let token = source.next_token_unwrap();
-        match token.parse() {
-            Ok(v) => v,
-            Err(e) => panic!(
-                concat!(
-                    "failed to parse the input `{input}` ",
-                    "to the value of type `{ty}`: {err:?}; ",
-                    "ensure that the input format is collectly specified ",
-                    "and that the input value must handle specified type.",
-                ),
-                input = token,
-                ty = type_name::<T>(),
-                err = e,
+        parse_token(source.next_token_unwrap())
+    }
+}
+
+fn parse_token<T: FromStr>(token: &str) -> T
+where
+    T::Err: Debug,
+{
+    match token.parse() {
+        Ok(v) => v,
+        Err(e) => panic!(
+            concat!(
+                "failed to parse the input `{input}` ",
+                "to the value of type `{ty}`: {err:?}; ",
+                "ensure that the input format is collectly specified ",
+                "and that the input value must handle specified type.",
             ),
+            input = token,
+            ty = type_name::<T>(),
+            err = e,
+        ),
+    }
+}
+
+// the error of `TryReadable` at the end of the input.
+pub(crate) fn end_of_input<T: ?Sized, R: BufRead, S: Source<R>>(source: &S) -> ReadError {
+    ReadError::new::<T>(source.position(), None, "no token is left")
+}
+
+// reads the next token for `TryReadable`, or returns the error at the end of the input.
+pub(crate) fn try_next_token<T: ?Sized, R: BufRead, S: Source<R>>(
+    source: &mut S,
+) -> Result<&str, ReadError> {
+    // `is_empty()` first, since the error needs the source after `next_token()` returns `None`.
+    if source.is_empty() {
+        return Err(end_of_input::<T, R, S>(source));
+    }
+    Ok(source.next_token_unwrap())
+}
+
+impl<T: FromStr> TryReadable for T
+where
+    T::Err: Debug,
+{
+    fn try_read<R: BufRead, S: Source<R>>(source: &mut S) -> Result<T, ReadError> {
+        let token = try_next_token::<T, R, S>(source)?;
+        match token.parse() {
+            Ok(value) => Ok(value),
+            Err(e) => {
+                let token = token.to_string();
+                let position = source.position();
+                Err(ReadError::new::<T>(
+                    position,
+                    Some(&token),
+                    format_args!("{:?}", e),
+                ))
+            }
         }
     }
 }
@@ -0,0 +1,151 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines sources reading from sockets.
+//!
+//! Some training platforms serve interactive problems over a network connection.  `connect()`
+//! connects to the judge and returns a pair of a source and a sink, which can be used by `from`
+//! of `input!` and `to` of `output!` respectively.
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use proconio::source::net;
+//! use proconio::{input, outputln};
+//!
+//! let (mut source, mut sink) = net::connect("127.0.0.1:8000").unwrap();
+//! input! {
+//!     from &mut source,
+//!     n: u32,
+//! }
+//! outputln!(to sink, "? {}", n / 2); // flushed at the newline.
+//! ```
+//!
+//! The source reads the socket line by line like `LineSource`, so it never waits for more lines
+//! than needed.  The sink flushes the output at every newline.
+
+use super::line::LineSource;
+use super::{Position, Source};
+use crate::sink::net::{SocketSink, TcpSink};
+use std::io::{self, BufReader, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+#[cfg(unix)]
+use std::path::Path;
+
+/// Source reading a socket line by line.
+pub struct SocketSource<S: Read> {
+    inner: LineSource<BufReader<S>>,
+}
+
+/// Source reading a TCP connection.
+pub type TcpSource = SocketSource<TcpStream>;
+
+/// Source reading a Unix domain socket.
+#[cfg(unix)]
+pub type UnixSource = SocketSource<UnixStream>;
+
+impl<S: Read> SocketSource<S> {
+    /// Creates a source reading the socket.
+    pub fn new(socket: S) -> SocketSource<S> {
+        SocketSource {
+            inner: LineSource::new(BufReader::new(socket)),
+        }
+    }
+}
+
+impl<S: Read> Source<BufReader<S>> for SocketSource<S> {
+    fn next_token(&mut self) -> Option<&str> {
+        self.inner.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line()
+    }
+
+    fn end_line(&mut self) {
+        self.inner.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        self.inner.next_line()
+    }
+
+    fn position(&self) -> Option<Position> {
+        self.inner.position()
+    }
+}
+
+/// Connects to the judge over TCP and returns the source and the sink of the connection.
+pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<(TcpSource, TcpSink)> {
+    let stream = TcpStream::connect(addr)?;
+    // disable Nagle's algorithm; interactive protocols send many small lines.
+    stream.set_nodelay(true)?;
+    let writer = stream.try_clone()?;
+    Ok((SocketSource::new(stream), SocketSink::new(writer)))
+}
+
+/// Connects to the judge over a Unix domain socket and returns the source and the sink of the
+/// connection.
+#[cfg(unix)]
+pub fn connect_unix<P: AsRef<Path>>(
+    path: P,
+) -> io::Result<(UnixSource, crate::sink::net::UnixSink)> {
+    let stream = UnixStream::connect(path)?;
+    let writer = stream.try_clone()?;
+    Ok((SocketSource::new(stream), SocketSink::new(writer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::connect;
+    use crate::{input, outputln};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn interact_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        // the judge: sends a number, and replies whether the answer is its double.
+        let judge = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            writeln!(writer, "21").unwrap();
+
+            let mut answer = String::new();
+            reader.read_line(&mut answer).unwrap();
+            let verdict = if answer.trim() == "42" { "AC" } else { "WA" };
+            writeln!(writer, "{}", verdict).unwrap();
+        });
+
+        let (mut source, mut sink) = connect(addr).unwrap();
+        input! {
+            from &mut source,
+            n: u32,
+        }
+        outputln!(to sink, "{}", n * 2);
+        input! {
+            from &mut source,
+            verdict: String,
+        }
+
+        assert_eq!(verdict, "AC");
+        judge.join().unwrap();
+    }
+}
@@ -5,7 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
 // distributed except according to those terms.
 
-use super::Source;
+use super::{position_in, trim_line_end, Checkpoint, Position, Source, NO_TOKEN_MESSAGE};
 use std::io::BufRead;
 use std::iter::Peekable;
 use std::marker::PhantomData;
@@ -28,6 +28,10 @@ pub struct OnceSource<R: BufRead> {
     // context `tokens` is reffering to
     context: Box<str>,
 
+    // the end of the last token or line consumed, and whether it is in the middle of a line.
+    consumed: usize,
+    in_line: bool,
+
     // to consume `R`.  Actually `OnceSource` is not need to have `R`, since reading is done in its
     // constructor.  This is for the consistency with `LineSource` (To use smoothly through `AutoSource`).
     _read: PhantomData<R>,
@@ -51,6 +55,8 @@ impl<R: BufRead> OnceSource<R> {
         let mut res = OnceSource {
             context,
             tokens: "".split_whitespace().peekable(),
+            consumed: 0,
+            in_line: false,
             _read: PhantomData,
         };
 
@@ -60,18 +66,97 @@ impl<R: BufRead> OnceSource<R> {
 
         res
     }
+
+    // the position of the end of the token, which is a part of the context.
+    fn end_of(&self, token: &str) -> usize {
+        token.as_ptr() as usize - self.context.as_ptr() as usize + token.len()
+    }
 }
 
 impl<R: BufRead> Source<R> for OnceSource<R> {
     /// Gets a next token.
     fn next_token(&mut self) -> Option<&str> {
-        self.tokens.next()
+        let token = self.tokens.next()?;
+        self.consumed = self.end_of(token);
+        self.in_line = true;
+        Some(token)
     }
 
     /// Check if tokens are empty
     fn is_empty(&mut self) -> bool {
         self.tokens.peek().is_none()
     }
+
+    /// Gets a next token without consuming it.
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.peek().copied()
+    }
+
+    /// Skips the next token and the rest of the line.
+    fn skip_line(&mut self) {
+        let token = self.tokens.next().expect(NO_TOKEN_MESSAGE);
+        let end = self.end_of(token);
+        let next = match self.context[end..].find('\n') {
+            Some(len) => end + len,
+            None => self.context.len(),
+        };
+
+        let context: &'static str = unsafe { std::mem::transmute(&*self.context) };
+        self.tokens = context[next..].split_whitespace().peekable();
+        // the rest of the line is skipped by `next_line()` from the end of the token.
+        self.consumed = end;
+        self.in_line = true;
+    }
+
+    /// Gets the next line as is.
+    fn next_line(&mut self) -> Option<&str> {
+        let context: &'static str = unsafe { std::mem::transmute(&*self.context) };
+        let find_newline = |from: usize| context[from..].find('\n').map(|len| from + len);
+
+        let mut start = self.consumed;
+        if self.in_line {
+            start = find_newline(start).map_or(context.len(), |end| end + 1);
+        }
+        let end = find_newline(start).map_or(context.len(), |end| end + 1);
+        self.consumed = end;
+        self.in_line = false;
+        self.tokens = context[end..].split_whitespace().peekable();
+
+        if start == context.len() {
+            None
+        } else {
+            Some(trim_line_end(&context[start..end]))
+        }
+    }
+
+    /// Gets the position of the last token or line read.
+    fn position(&self) -> Option<Position> {
+        position_in(self.context.as_bytes(), self.consumed, self.in_line, |b| {
+            b.is_ascii_whitespace()
+        })
+    }
+
+    /// Saves the current point of the input.
+    fn checkpoint(&mut self) -> Checkpoint {
+        let rest = match self.tokens.peek().copied() {
+            Some(token) => self.end_of(token) - token.len(),
+            None => self.context.len(),
+        };
+        Checkpoint {
+            rest,
+            consumed: self.consumed,
+            in_line: self.in_line,
+        }
+    }
+
+    /// Goes back to the saved point of the input.
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        checkpoint.check(self.context.len());
+        let context: &'static str = unsafe { std::mem::transmute(&*self.context) };
+        self.tokens = context[checkpoint.rest..].split_whitespace().peekable();
+        self.consumed = checkpoint.consumed;
+        self.in_line = checkpoint.in_line;
+    }
 }
 
 use std::io::BufReader;
@@ -83,3 +168,9 @@ impl<'a> From<&'a str> for OnceSource<BufReader<&'a [u8]>> {
         OnceSource::new(BufReader::new(s.as_bytes()))
     }
 }
+
+impl<'a> From<&'a [u8]> for OnceSource<BufReader<&'a [u8]>> {
+    fn from(s: &'a [u8]) -> OnceSource<BufReader<&'a [u8]>> {
+        OnceSource::new(BufReader::new(s))
+    }
+}
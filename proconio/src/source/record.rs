@@ -0,0 +1,119 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a reader recording the input read through it.
+//!
+//! `RecordReader` wraps a reader and writes every byte consumed from it into another writer, such
+//! as a file.  Unlike `tee::TeeSource`, which logs the tokens, the record is the input itself, so
+//! it can be given as the input again to replay the run exactly.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::line::LineSource;
+//! use proconio::source::record::RecordReader;
+//! use std::io::BufReader;
+//!
+//! let mut record = Vec::new();
+//! let mut source = LineSource::new(RecordReader::new(
+//!     BufReader::new(&b"2\n1  2\n3\n"[..]),
+//!     &mut record,
+//! ));
+//! input! {
+//!     from &mut source,
+//!     n: usize,
+//!     a: [u32; n],
+//! }
+//! drop(source);
+//!
+//! assert_eq!(a, [1, 2]);
+//! // `LineSource` reads the input line by line.
+//! assert_eq!(record, b"2\n1  2\n");
+//! ```
+//!
+//! The global stdin source records the input in this way when `PROCONIO_RECORD` is set; see
+//! `proconio::record_stdin()`.
+
+use std::io::{self, BufRead, Read, Write};
+
+/// Reader writing the consumed bytes into a writer.
+pub struct RecordReader<R, W: Write> {
+    inner: R,
+    record: W,
+}
+
+impl<R, W: Write> RecordReader<R, W> {
+    /// Creates `RecordReader` writing the bytes consumed from `reader` into `record`.
+    pub fn new(reader: R, record: W) -> RecordReader<R, W> {
+        RecordReader {
+            inner: reader,
+            record,
+        }
+    }
+
+    /// Gets the record.
+    pub fn record(&self) -> &W {
+        &self.record
+    }
+
+    /// Splits into the wrapped reader and the record.
+    pub fn into_parts(self) -> (R, W) {
+        (self.inner, self.record)
+    }
+}
+
+impl<R: Read, W: Write> Read for RecordReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.record.write_all(&buf[..len])?;
+        Ok(len)
+    }
+}
+
+impl<R: BufRead, W: Write> BufRead for RecordReader<R, W> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt > 0 {
+            // the buffer is already filled, so this doesn't read the input again.
+            let buf = self
+                .inner
+                .fill_buf()
+                .unwrap_or_else(|e| panic!("failed to record the input: {}", e));
+            self.record
+                .write_all(&buf[..amt])
+                .unwrap_or_else(|e| panic!("failed to record the input: {}", e));
+        }
+        self.inner.consume(amt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::source::once::OnceSource;
+
+    #[test]
+    fn record_whole_input() {
+        let mut record = Vec::new();
+        {
+            let reader = RecordReader::new(&b"3 1 2 3\nrest"[..], &mut record);
+            let source = OnceSource::new(io::BufReader::new(reader));
+            input! {
+                from source,
+                n: usize,
+                a: [u32; n],
+            }
+            assert_eq!(a, [1, 2, 3]);
+        }
+
+        assert_eq!(record, b"3 1 2 3\nrest");
+    }
+}
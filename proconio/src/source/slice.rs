@@ -0,0 +1,325 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source reading the tokens from a byte slice.
+//!
+//! `SliceSource` borrows the input and never copies it.  This is the source available without
+//! `std` feature, where the input is typically given as a byte slice by the host.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::slice::SliceSource;
+//!
+//! let input: &[u8] = b"3\n1 2 3\n";
+//! let source = SliceSource::new(input);
+//! input! {
+//!     from source,
+//!     n: usize,
+//!     a: [u32; n],
+//! }
+//! assert_eq!(a, [1, 2, 3]);
+//! ```
+
+use super::{position_in, Checkpoint, Position, Source, NO_TOKEN_MESSAGE};
+use core::str;
+
+/// Source reading the tokens from a byte slice.
+///
+/// As `bytes::ByteSource`, tokens are splitted by ASCII whitespaces and only the tokens requested
+/// as `&str` are validated as UTF-8.
+pub struct SliceSource<'a> {
+    tokens: ByteTokens<&'a [u8]>,
+}
+
+impl<'a> SliceSource<'a> {
+    /// Creates `SliceSource` reading the specified slice.
+    pub fn new(context: &'a [u8]) -> SliceSource<'a> {
+        SliceSource {
+            tokens: ByteTokens::new(context),
+        }
+    }
+
+    /// Creates `SliceSource` splitting tokens by the specified characters instead of whitespaces.
+    ///
+    /// See `bytes::ByteSource::with_delimiters()` for details.
+    pub fn with_delimiters(context: &'a [u8], delimiters: &str) -> SliceSource<'a> {
+        let mut res = SliceSource::new(context);
+        res.tokens.delimiters = Delimiters::new(delimiters);
+        res
+    }
+}
+
+impl<'a> Source<&'a [u8]> for SliceSource<'a> {
+    /// Gets a next token.
+    fn next_token(&mut self) -> Option<&str> {
+        self.tokens.next_str()
+    }
+
+    /// Gets a next token as raw bytes.
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        self.tokens.next()
+    }
+
+    /// Check if tokens are empty
+    fn is_empty(&mut self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Gets a next token without consuming it.
+    fn peek_token(&mut self) -> Option<&str> {
+        self.tokens.peek_str()
+    }
+
+    /// Skips the next token and the rest of the line.
+    fn skip_line(&mut self) {
+        self.tokens.skip_line()
+    }
+
+    /// Gets the next line as is.
+    fn next_line(&mut self) -> Option<&str> {
+        self.tokens.next_line()
+    }
+
+    /// Gets the position of the last token or line read.
+    fn position(&self) -> Option<Position> {
+        self.tokens.position()
+    }
+
+    /// Saves the current point of the input.
+    fn checkpoint(&mut self) -> Checkpoint {
+        self.tokens.checkpoint()
+    }
+
+    /// Goes back to the saved point of the input.
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.tokens.rewind(checkpoint)
+    }
+}
+
+impl<'a> From<&'a [u8]> for SliceSource<'a> {
+    fn from(s: &'a [u8]) -> SliceSource<'a> {
+        SliceSource::new(s)
+    }
+}
+
+impl<'a> From<&'a str> for SliceSource<'a> {
+    fn from(s: &'a str) -> SliceSource<'a> {
+        SliceSource::new(s.as_bytes())
+    }
+}
+
+/// Tokenizer over bytes, shared with the other byte-oriented sources.
+pub(super) struct ByteTokens<C: AsRef<[u8]>> {
+    context: C,
+    pos: usize,
+    // the end of the last token or line consumed, and whether it is in the middle of a line.
+    consumed: usize,
+    in_line: bool,
+    pub(super) delimiters: Delimiters,
+}
+
+impl<C: AsRef<[u8]>> ByteTokens<C> {
+    pub(super) fn new(context: C) -> ByteTokens<C> {
+        ByteTokens {
+            context,
+            pos: 0,
+            consumed: 0,
+            in_line: false,
+            delimiters: Delimiters::whitespace(),
+        }
+    }
+
+    #[cfg(any(feature = "mmap", feature = "arbitrary"))]
+    pub(super) fn context(&self) -> &C {
+        &self.context
+    }
+
+    pub(super) fn next(&mut self) -> Option<&[u8]> {
+        let start = self.skip_whitespace();
+        self.pos = self.token_end();
+        if start == self.pos {
+            None
+        } else {
+            self.consumed = self.pos;
+            self.in_line = true;
+            Some(&self.context.as_ref()[start..self.pos])
+        }
+    }
+
+    pub(super) fn next_str(&mut self) -> Option<&str> {
+        self.next().map(to_str)
+    }
+
+    pub(super) fn peek(&mut self) -> Option<&[u8]> {
+        let start = self.skip_whitespace();
+        let end = self.token_end();
+        if start == end {
+            None
+        } else {
+            Some(&self.context.as_ref()[start..end])
+        }
+    }
+
+    pub(super) fn peek_str(&mut self) -> Option<&str> {
+        self.peek().map(to_str)
+    }
+
+    pub(super) fn skip_line(&mut self) {
+        self.next().expect(NO_TOKEN_MESSAGE);
+        let context = self.context.as_ref();
+        while self.pos < context.len() && context[self.pos] != b'\n' {
+            self.pos += 1;
+        }
+    }
+
+    pub(super) fn next_line(&mut self) -> Option<&str> {
+        let context = self.context.as_ref();
+        let find_newline = |from: usize| {
+            context[from..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(context.len(), |len| from + len)
+        };
+
+        // the peeked tokens may have moved `pos`, so the line starts from the consumed position.
+        let mut start = self.consumed;
+        if self.in_line {
+            start = (find_newline(start) + 1).min(context.len());
+        }
+        self.in_line = false;
+        if start == context.len() {
+            self.pos = start;
+            self.consumed = start;
+            return None;
+        }
+
+        let end = find_newline(start);
+        self.pos = (end + 1).min(context.len());
+        self.consumed = self.pos;
+        let line = &context[start..end];
+        Some(to_str(line.strip_suffix(b"\r").unwrap_or(line)))
+    }
+
+    pub(super) fn position(&self) -> Option<Position> {
+        position_in(self.context.as_ref(), self.consumed, self.in_line, |b| {
+            self.delimiters.contains(b)
+        })
+    }
+
+    pub(super) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            rest: self.pos,
+            consumed: self.consumed,
+            in_line: self.in_line,
+        }
+    }
+
+    pub(super) fn rewind(&mut self, checkpoint: Checkpoint) {
+        checkpoint.check(self.context.as_ref().len());
+        self.pos = checkpoint.rest;
+        self.consumed = checkpoint.consumed;
+        self.in_line = checkpoint.in_line;
+    }
+
+    pub(super) fn is_empty(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos == self.context.as_ref().len()
+    }
+
+    // skips the delimiters and returns the start of the next token.
+    fn skip_whitespace(&mut self) -> usize {
+        let context = self.context.as_ref();
+        while self.pos < context.len() && self.delimiters.contains(context[self.pos]) {
+            self.pos += 1;
+        }
+        self.pos
+    }
+
+    // the end of the token starting at the current position.
+    fn token_end(&self) -> usize {
+        let context = self.context.as_ref();
+        let mut end = self.pos;
+        while end < context.len() && !self.delimiters.contains(context[end]) {
+            end += 1;
+        }
+        end
+    }
+}
+
+fn to_str(token: &[u8]) -> &str {
+    str::from_utf8(token).unwrap_or_else(|e| {
+        panic!(
+            "the token `{}` is not a valid UTF-8 string: {}",
+            alloc::string::String::from_utf8_lossy(token),
+            e
+        )
+    })
+}
+
+/// Set of the bytes separating tokens.
+pub(super) struct Delimiters([bool; 256]);
+
+impl Delimiters {
+    fn whitespace() -> Delimiters {
+        let mut table = [false; 256];
+        for b in 0..=255u8 {
+            table[usize::from(b)] = b.is_ascii_whitespace();
+        }
+        Delimiters(table)
+    }
+
+    pub(super) fn new(delimiters: &str) -> Delimiters {
+        let mut table = [false; 256];
+        for c in delimiters.chars() {
+            assert!(
+                c.is_ascii(),
+                "the delimiter `{}` is not an ASCII character",
+                c
+            );
+            table[c as usize] = true;
+        }
+        Delimiters(table)
+    }
+
+    fn contains(&self, b: u8) -> bool {
+        self.0[usize::from(b)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Bytes;
+
+    #[test]
+    fn borrowed_tokens() {
+        let input = b"ab\xff 12\n\t-3".to_vec();
+        let mut source = SliceSource::new(&input);
+        input! {
+            from &mut source,
+            raw: Bytes,
+            a: u8,
+            b: i8,
+        }
+
+        assert_eq!(raw, b"ab\xff");
+        assert_eq!((a, b), (12, -3));
+        assert!(source.is_empty());
+    }
+
+    #[test]
+    fn delimiters() {
+        let source = SliceSource::with_delimiters(b"1,2;3", ",;");
+        input! {
+            from source,
+            a: [u8; 3],
+        }
+        assert_eq!(a, [1, 2, 3]);
+    }
+}
@@ -0,0 +1,351 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source recording the consumed tokens.
+//!
+//! `TeeSource` wraps another source and mirrors every token read through it into a log, along
+//! with its 0-indexed number.  When your solution crashes in the middle of reading, the log
+//! shows exactly which tokens were consumed so far.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::auto::AutoSource;
+//! use proconio::source::tee::TeeSource;
+//!
+//! let mut source = TeeSource::recorded(AutoSource::from("3 1 2 3"));
+//! input! {
+//!     from &mut source,
+//!     n: usize,
+//!     a: [u32; n - 1],
+//! }
+//!
+//! assert_eq!(source.count(), 3);
+//! assert_eq!(source.log(), b"#0 3\n#1 1\n#2 2\n");
+//! assert_eq!(source.consumed_input(), "3\n1\n2\n");
+//! ```
+//!
+//! Any writer can be used as the log, such as stderr or a file.  Each token is written as soon as
+//! it is read.
+//!
+//! The lines read by `Source::next_line()`, such as `marker::Line`, are recorded as one entry each.
+//! The tokens skipped by `Source::skip_tokens()` are recorded as well.  `Source::skip_line()`
+//! drops the line in the wrapped source without the tokens, so it is recorded as one entry
+//! `(skipped a line)` instead; it stays in `consumed_input()`, so that the replay skips it again.
+//!
+//! `Source::rewind()` removes the entries after the checkpoint from the log created by
+//! `recorded()`, since they are read again.  Other writers, such as stderr, can't remove the
+//! entries, so the entry `(rewound)` is written instead and the numbering goes back.
+
+use super::{Checkpoint, Position, Source};
+use std::io::{BufRead, Write};
+
+/// Source mirroring the consumed tokens into a writer.
+pub struct TeeSource<S, W: Write> {
+    inner: S,
+    log: W,
+    count: usize,
+    // the bytes written into the log, and how to remove the bytes after a length if it can.
+    written: usize,
+    truncate: Option<fn(&mut W, usize)>,
+    // the checkpoints saved with `count` and `written` at the time.
+    checkpoints: Vec<(Checkpoint, usize, usize)>,
+}
+
+impl<S, W: Write> TeeSource<S, W> {
+    /// Creates `TeeSource` writing the tokens read from `source` into `log`.
+    pub fn new(source: S, log: W) -> TeeSource<S, W> {
+        TeeSource {
+            inner: source,
+            log,
+            count: 0,
+            written: 0,
+            truncate: None,
+            checkpoints: Vec::new(),
+        }
+    }
+
+    /// Gets the number of tokens consumed so far, counting a line read or skipped as one.
+    ///
+    /// This is the number in the log.  `Source::position()` gives the line and the token in the
+    /// input, as the wrapped source does.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Gets the log.
+    pub fn log(&self) -> &W {
+        &self.log
+    }
+
+    /// Splits into the wrapped source and the log.
+    pub fn into_parts(self) -> (S, W) {
+        (self.inner, self.log)
+    }
+}
+
+impl<S> TeeSource<S, Vec<u8>> {
+    /// Creates `TeeSource` recording the tokens in memory.
+    pub fn recorded(source: S) -> TeeSource<S, Vec<u8>> {
+        TeeSource {
+            truncate: Some(Vec::truncate),
+            ..TeeSource::new(source, Vec::new())
+        }
+    }
+
+    /// Gets the consumed tokens, one token per line.
+    ///
+    /// This can be used as the input to replay the run up to this point.
+    pub fn consumed_input(&self) -> String {
+        let log = String::from_utf8_lossy(&self.log);
+        log.lines()
+            .map(|line| match line.find(' ') {
+                Some(space) => &line[space + 1..],
+                None => line,
+            })
+            .fold(String::new(), |mut input, token| {
+                input.push_str(token);
+                input.push('\n');
+                input
+            })
+    }
+}
+
+// the entries written for `skip_line()` and `rewind()`.
+const SKIPPED_LINE: &[u8] = b"(skipped a line)";
+const REWOUND: &[u8] = b"(rewound)";
+
+// writes the entry and returns its length in bytes.
+fn record<W: Write>(log: &mut W, count: &mut usize, token: &[u8]) -> usize {
+    let header = format!("#{} ", count);
+    log.write_all(header.as_bytes())
+        .and_then(|_| log.write_all(token))
+        .and_then(|_| log.write_all(b"\n"))
+        .unwrap_or_else(|e| panic!("failed to write the token to the log: {}", e));
+    *count += 1;
+    header.len() + token.len() + 1
+}
+
+impl<R: BufRead, S: Source<R>, W: Write> Source<R> for TeeSource<S, W> {
+    fn next_token(&mut self) -> Option<&str> {
+        let token = self.inner.next_token()?;
+        self.written += record(&mut self.log, &mut self.count, token.as_bytes());
+        Some(token)
+    }
+
+    fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        let token = self.inner.next_token_bytes()?;
+        self.written += record(&mut self.log, &mut self.count, token);
+        Some(token)
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.inner.is_empty()
+    }
+
+    // peeked tokens are recorded when they are actually read.
+    fn peek_token(&mut self) -> Option<&str> {
+        self.inner.peek_token()
+    }
+
+    fn skip_tokens(&mut self, n: usize) {
+        for _ in 0..n {
+            let token = self.inner.next_token_bytes_unwrap();
+            self.written += record(&mut self.log, &mut self.count, token);
+        }
+    }
+
+    fn skip_line(&mut self) {
+        self.inner.skip_line();
+        self.written += record(&mut self.log, &mut self.count, SKIPPED_LINE);
+    }
+
+    fn end_line(&mut self) {
+        self.inner.end_line()
+    }
+
+    fn next_line(&mut self) -> Option<&str> {
+        let line = self.inner.next_line()?;
+        self.written += record(&mut self.log, &mut self.count, line.as_bytes());
+        Some(line)
+    }
+
+    fn position(&self) -> Option<Position> {
+        Source::<R>::position(&self.inner)
+    }
+
+    fn checkpoint(&mut self) -> Checkpoint {
+        let checkpoint = self.inner.checkpoint();
+        self.checkpoints
+            .push((checkpoint, self.count, self.written));
+        checkpoint
+    }
+
+    fn rewind(&mut self, checkpoint: Checkpoint) {
+        let idx = self
+            .checkpoints
+            .iter()
+            .rposition(|&(saved, _, _)| saved == checkpoint)
+            .expect("failed to rewind: the checkpoint is not saved by this source");
+        let (_, count, written) = self.checkpoints[idx];
+        // the checkpoints after it refer to the entries removed below.
+        self.checkpoints.truncate(idx + 1);
+        self.inner.rewind(checkpoint);
+
+        self.count = count;
+        match self.truncate {
+            Some(truncate) => {
+                truncate(&mut self.log, written);
+                self.written = written;
+            }
+            // numbered by the count rewound to, which the next token has again.
+            None => {
+                let mut number = count;
+                self.written += record(&mut self.log, &mut number, REWOUND);
+            }
+        }
+    }
+
+    fn radix(&self) -> Option<u32> {
+        self.inner.radix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::marker::Bytes;
+    use crate::source::auto::AutoSource;
+    use crate::source::bytes::ByteSource;
+    use crate::source::line::LineSource;
+    use crate::source::once::OnceSource;
+
+    #[test]
+    fn record_until_panic() {
+        let mut source = TeeSource::recorded(AutoSource::from("2 10 x"));
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            input! {
+                from &mut source,
+                n: usize,
+                a: [u32; n],
+            }
+            a
+        }));
+
+        assert!(res.is_err());
+        assert_eq!(source.count(), 3);
+        assert_eq!(source.log(), b"#0 2\n#1 10\n#2 x\n");
+    }
+
+    #[test]
+    fn record_bytes() {
+        let mut source = TeeSource::recorded(ByteSource::from(&b"ab \xff"[..]));
+        input! {
+            from &mut source,
+            s: [Bytes; 2],
+        }
+
+        assert_eq!(s, [b"ab".to_vec(), vec![0xff]]);
+        assert_eq!(source.into_parts().1, b"#0 ab\n#1 \xff\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to end the line 1: the line has more tokens than expected")]
+    fn forward_end_line() {
+        let mut source = TeeSource::recorded(LineSource::from("1 2\n"));
+        input! {
+            from &mut source,
+            _: u8,
+            _: end_line,
+        }
+    }
+
+    #[test]
+    fn record_lines() {
+        use crate::marker::Line;
+
+        let mut source = TeeSource::recorded(LineSource::from("2\n#. .#\n\n"));
+        input! {
+            from &mut source,
+            n: usize,
+            grid: [Line; n],
+        }
+
+        assert_eq!(grid, ["#. .#", ""]);
+        assert_eq!(source.log(), b"#0 2\n#1 #. .#\n#2 \n");
+
+        let input = source.consumed_input();
+        input! {
+            from LineSource::from(&*input),
+            n: usize,
+            replayed: [Line; n],
+        }
+        assert_eq!(replayed, grid);
+    }
+
+    #[test]
+    fn forward_position() {
+        let mut source = TeeSource::recorded(LineSource::from("1\n2 3\n"));
+        source.skip_tokens(2);
+
+        assert_eq!(source.count(), 2);
+        assert_eq!(source.position(), Some(Position { line: 2, token: 1 }));
+    }
+
+    #[test]
+    fn rewind_log() {
+        let mut source = TeeSource::recorded(OnceSource::from("1 2 3"));
+        source.skip_tokens(1);
+        let checkpoint = source.checkpoint();
+        source.skip_tokens(2);
+        source.rewind(checkpoint);
+
+        assert_eq!(source.count(), 1);
+        assert_eq!(source.log(), b"#0 1\n");
+        input! {
+            from &mut source,
+            rest: [u8; 2],
+        }
+        assert_eq!(rest, [2, 3]);
+        assert_eq!(source.consumed_input(), "1\n2\n3\n");
+
+        let mut source = TeeSource::new(OnceSource::from("1 2"), Vec::new());
+        let checkpoint = source.checkpoint();
+        source.skip_tokens(1);
+        source.rewind(checkpoint);
+        source.skip_tokens(2);
+        assert_eq!(source.log(), b"#0 1\n#0 (rewound)\n#0 1\n#1 2\n");
+    }
+
+    #[test]
+    fn record_skipped() {
+        let mut source = TeeSource::recorded(OnceSource::from("1 2 3\n4 5\n6\n"));
+        source.skip_tokens(2);
+        source.skip_line();
+        source.skip_line();
+        input! {
+            from &mut source,
+            x: u32,
+        }
+
+        assert_eq!(x, 6);
+        assert_eq!(source.count(), 5);
+        assert_eq!(
+            source.log(),
+            b"#0 1\n#1 2\n#2 (skipped a line)\n#3 (skipped a line)\n#4 6\n"
+        );
+
+        // the replay skips the same lines.
+        let input = source.consumed_input();
+        let mut replay = OnceSource::from(&*input);
+        replay.skip_tokens(2);
+        replay.skip_line();
+        replay.skip_line();
+        assert_eq!(replay.next_token(), Some("6"));
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines a source owning a sequence of tokens.
+//!
+//! `TokenSource` is returned by `Source::take_tokens()`, which takes the next tokens out of
+//! another source.  Since it owns the tokens, it can be read independently of the original
+//! source, even by another thread.  When the size of each test case is given in advance, the cases
+//! can be split and read in parallel:
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input;
+//! use proconio::source::auto::AutoSource;
+//! use proconio::source::Source;
+//! use std::thread;
+//!
+//! let mut source = AutoSource::from("2\n3\n1 2 3\n2\n4 5\n");
+//! input! {
+//!     from &mut source,
+//!     t: usize,
+//! }
+//!
+//! let mut handles = Vec::new();
+//! for _ in 0..t {
+//!     input! {
+//!         from &mut source,
+//!         n: usize,
+//!     }
+//!     let case = source.take_tokens(n);
+//!     handles.push(thread::spawn(move || {
+//!         input! {
+//!             from case,
+//!             a: [u64; n],
+//!         }
+//!         a.iter().sum::<u64>()
+//!     }));
+//! }
+//!
+//! let sums: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+//! assert_eq!(sums, [6, 9]);
+//! ```
+//!
+//! The tokens don't keep the line breaks, so `Source::skip_line()` is not supported.
+
+use super::Source;
+use std::io;
+use std::iter::FromIterator;
+
+/// Source owning a sequence of tokens.
+///
+/// The tokens are stored in a single buffer, so taking many small tokens doesn't allocate for
+/// each of them.
+#[derive(Debug, Clone, Default)]
+pub struct TokenSource {
+    text: String,
+    ends: Vec<usize>,
+    next: usize,
+}
+
+impl TokenSource {
+    /// Creates `TokenSource` with no tokens.
+    pub fn new() -> TokenSource {
+        TokenSource::default()
+    }
+
+    /// Appends a token to the end.
+    ///
+    /// The token is taken as is, so it should not contain whitespaces.
+    pub fn push(&mut self, token: &str) {
+        self.text.push_str(token);
+        self.ends.push(self.text.len());
+    }
+
+    fn token(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.ends[i - 1] };
+        &self.text[start..self.ends[i]]
+    }
+}
+
+impl Source<io::Empty> for TokenSource {
+    fn next_token(&mut self) -> Option<&str> {
+        if self.next == self.ends.len() {
+            return None;
+        }
+
+        self.next += 1;
+        Some(self.token(self.next - 1))
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.next == self.ends.len()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        if self.next == self.ends.len() {
+            return None;
+        }
+
+        Some(self.token(self.next))
+    }
+}
+
+impl<'a> Extend<&'a str> for TokenSource {
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for token in iter {
+            self.push(token);
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for TokenSource {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> TokenSource {
+        let mut res = TokenSource::new();
+        res.extend(iter);
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+    use crate::source::line::LineSource;
+
+    #[test]
+    fn take_tokens() {
+        let mut source = LineSource::from("a 1\n\n2 b\nc");
+        let mut taken = source.take_tokens(3);
+        assert_eq!(source.next_token(), Some("b"));
+
+        input! {
+            from &mut taken,
+            s: String,
+            x: [u8; 2],
+        }
+        assert_eq!(s, "a");
+        assert_eq!(x, [1, 2]);
+        assert!(taken.is_empty());
+        assert_eq!(taken.next_token(), None);
+
+        let mut empty = source.take_tokens(0);
+        assert!(empty.is_empty());
+        assert_eq!(source.next_token(), Some("c"));
+    }
+
+    #[test]
+    fn collect_tokens() {
+        let mut source: TokenSource = "3 1 4".split(' ').collect();
+        assert_eq!(source.peek_token(), Some("3"));
+        source.skip_tokens(1);
+        assert_eq!(source.clone().next_token(), Some("1"));
+        input! {
+            from source,
+            a: [u8; 2],
+        }
+        assert_eq!(a, [1, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to get the next token")]
+    fn take_too_many() {
+        LineSource::from("1 2").take_tokens(3);
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Stress testing: compares your solution with a brute-force one over random inputs.
+//!
+//! `run()` repeatedly generates an input by the generator, runs both solutions on it and compares
+//! their outputs.  As in `test_samples!`, `input!` without `from` reads the generated input and the
+//! output of `output!` and `outputln!` is captured during each run.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::{gen_input, input, outputln, stress};
+//!
+//! // the maximum sum of two adjacent elements.
+//! fn brute() {
+//!     input! {
+//!         n: usize,
+//!         a: [i64; n],
+//!     }
+//!     let mut best = i64::min_value();
+//!     for i in 0..n - 1 {
+//!         best = best.max(a[i] + a[i + 1]);
+//!     }
+//!     outputln!("{}", best);
+//! }
+//!
+//! fn fast() {
+//!     input! {
+//!         n: usize,
+//!         a: [i64; n],
+//!     }
+//!     outputln!("{}", a.windows(2).map(|w| w[0] + w[1]).max().unwrap());
+//! }
+//!
+//! stress::run(
+//!     |generator| {
+//!         gen_input! {
+//!             from generator,
+//!             n: usize in 2..=10,
+//!             a: [i64 in -100..=100; n],
+//!         }
+//!     },
+//!     brute,
+//!     fast,
+//!     100,
+//! );
+//! ```
+//!
+//! The outputs are compared token-wise by `memo::diff_tokens()`.  A panic in either solution is
+//! also a failure.  If some of the inputs fail, `run()` panics with the smallest failing input
+//! along with its seed, so that you can reproduce it by `GenSource::new(seed)`.
+
+use crate::gen::GenSource;
+use crate::harness;
+use crate::memo;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Runs `brute` and `fast` over `iterations` inputs generated by `gen`, and compares the outputs.
+///
+/// `gen` generates an input into the given `GenSource`, usually by `gen_input!`.  See the document
+/// of [`stress`](index.html) module for details.
+pub fn run<G, B, F>(mut gen: G, mut brute: B, mut fast: F, iterations: usize)
+where
+    G: FnMut(&mut GenSource),
+    B: FnMut(),
+    F: FnMut(),
+{
+    let base_seed = GenSource::random().seed();
+    let mut failures = 0;
+    let mut smallest: Option<Failure> = None;
+
+    for iteration in 0..iterations {
+        let seed = base_seed.wrapping_add(iteration as u64);
+        let mut generator = GenSource::new(seed);
+        gen(&mut generator);
+        let input = generator.take_text();
+
+        let expected = run_solution(&mut brute, &input);
+        let actual = run_solution(&mut fast, &input);
+        let reason = match (&expected, &actual) {
+            (Ok(expected), Ok(actual)) => match memo::diff_tokens(expected, actual) {
+                Some(mismatch) => format!("{} (brute -> fast)", mismatch),
+                None => continue,
+            },
+            (Err(message), _) => format!("the brute-force solution panicked: {}", message),
+            (_, Err(message)) => format!("the fast solution panicked: {}", message),
+        };
+
+        failures += 1;
+        let failure = Failure {
+            seed,
+            input,
+            reason,
+            expected: expected.unwrap_or_default(),
+            actual: actual.unwrap_or_default(),
+        };
+        let is_smaller = match &smallest {
+            Some(smallest) => failure.size() < smallest.size(),
+            None => true,
+        };
+        if is_smaller {
+            smallest = Some(failure);
+        }
+    }
+
+    if let Some(failure) = smallest {
+        panic!(
+            concat!(
+                "{} of {} inputs failed; the smallest one is:\n\n",
+                "seed {} failed: {}\n",
+                "[input]\n{}\n[brute]\n{}\n[fast]\n{}"
+            ),
+            failures,
+            iterations,
+            failure.seed,
+            failure.reason,
+            failure.input.trim_end(),
+            failure.expected.trim_end(),
+            failure.actual.trim_end(),
+        );
+    }
+}
+
+struct Failure {
+    seed: u64,
+    input: String,
+    reason: String,
+    expected: String,
+    actual: String,
+}
+
+impl Failure {
+    // the number of tokens first, then the length of the text.
+    fn size(&self) -> (usize, usize) {
+        (self.input.split_whitespace().count(), self.input.len())
+    }
+}
+
+// Runs the solution, turning its panic into the message.
+pub(crate) fn run_solution<F: FnMut()>(solve: &mut F, input: &str) -> Result<String, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| harness::run_with_input(solve, input)))
+        .map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gen_input, input, outputln};
+
+    fn generate(generator: &mut GenSource) {
+        gen_input! {
+            from generator,
+            n: usize in 1..=6,
+            a: [u32 in 0..=20; n],
+        }
+    }
+
+    fn brute() {
+        input! {
+            n: usize,
+            a: [u32; n],
+        }
+        outputln!("{}", a.iter().sum::<u32>());
+    }
+
+    #[test]
+    fn same_outputs() {
+        run(generate, brute, brute, 50);
+    }
+
+    #[test]
+    fn smallest_failing_input() {
+        let res = panic::catch_unwind(|| {
+            run(
+                generate,
+                brute,
+                || {
+                    input! {
+                        n: usize,
+                        a: [u32; n],
+                    }
+                    // wrong if the sum exceeds 20.
+                    outputln!("{}", a.iter().sum::<u32>().min(20));
+                },
+                200,
+            )
+        });
+
+        let message = panic_message(&*res.unwrap_err());
+        assert!(message.contains("inputs failed; the smallest one is"));
+        assert!(message.contains("token #0 differs"));
+        // two elements are enough to exceed 20, and 200 iterations surely find such an input.
+        assert!(message.contains("[input]\n2\n"), "{}", message);
+    }
+
+    #[test]
+    fn panicking_solution() {
+        let res = panic::catch_unwind(|| {
+            run(generate, brute, || panic!("oops"), 1);
+        });
+
+        let message = panic_message(&*res.unwrap_err());
+        assert!(message.starts_with("1 of 1 inputs failed"));
+        assert!(message.contains("the fast solution panicked: oops"));
+    }
+}
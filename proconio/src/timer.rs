@@ -0,0 +1,118 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Measures the time to stop the work before the time limit.
+//!
+//! In optimization problems, the answer is improved until the time is up.  `Timer` is started with
+//! the time allowed for the work, and tells whether the time is left.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::timer::Timer;
+//! use std::time::Duration;
+//!
+//! let timer = Timer::new(Duration::from_millis(10));
+//! let mut iterations = 0;
+//! while timer.ok() {
+//!     // improve the answer.
+//!     iterations += 1;
+//! }
+//! assert!(iterations > 0);
+//! assert!(timer.should_stop());
+//! ```
+//!
+//! Leave some margin from the actual time limit for reading the input and writing the output, and
+//! for the difference of the judge machine: 1.9 seconds for the limit of 2 seconds, for example.
+//! Simulated annealing can use `progress()` for its temperature.
+
+use std::time::{Duration, Instant};
+
+/// Timer measuring the time from its creation, with the time limit.
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    start: Instant,
+    limit: Duration,
+}
+
+impl Timer {
+    /// Creates `Timer` starting now, with the specified time limit.
+    pub fn new(limit: Duration) -> Timer {
+        Timer {
+            start: Instant::now(),
+            limit,
+        }
+    }
+
+    /// Creates `Timer` starting now, with the time limit in seconds.
+    pub fn from_secs_f64(limit: f64) -> Timer {
+        Timer::new(Duration::from_secs_f64(limit))
+    }
+
+    /// Gets the time limit.
+    pub fn limit(&self) -> Duration {
+        self.limit
+    }
+
+    /// Gets the time passed since the timer started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Gets the time left until the limit, or zero if the limit has passed.
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.elapsed())
+    }
+
+    /// Gets the ratio of the elapsed time to the limit, which is in `0.0..=1.0`.
+    ///
+    /// This is `1.0` after the limit has passed, or if the limit is zero.
+    pub fn progress(&self) -> f64 {
+        if self.limit == Duration::ZERO {
+            return 1.0;
+        }
+
+        (self.elapsed().as_secs_f64() / self.limit.as_secs_f64()).min(1.0)
+    }
+
+    /// Check if the limit has passed.
+    pub fn should_stop(&self) -> bool {
+        self.elapsed() >= self.limit
+    }
+
+    /// Check if the time is left.  This is the opposite of `should_stop()`, for `while timer.ok()`.
+    pub fn ok(&self) -> bool {
+        !self.should_stop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn limit_passes() {
+        let timer = Timer::new(Duration::from_millis(20));
+        assert!(timer.ok());
+        assert!(timer.progress() < 1.0);
+        assert!(timer.remaining() <= timer.limit());
+
+        thread::sleep(Duration::from_millis(25));
+        assert!(timer.should_stop());
+        assert!(!timer.ok());
+        assert!(timer.elapsed() >= Duration::from_millis(20));
+        assert_eq!(timer.remaining(), Duration::ZERO);
+        assert_eq!(timer.progress(), 1.0);
+    }
+
+    #[test]
+    fn zero_limit() {
+        let timer = Timer::from_secs_f64(0.0);
+        assert!(timer.should_stop());
+        assert_eq!(timer.progress(), 1.0);
+    }
+}
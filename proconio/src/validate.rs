@@ -0,0 +1,407 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Validates the test cases against the input format declared by `input_spec!`.
+//!
+//! Unlike `input!`, which accepts any whitespaces between the tokens, `run()` checks the input
+//! strictly, as the validators of the problem setters do:
+//!
+//! - each value is in its spec,
+//! - the tokens in a line are separated by exactly one space, and each line ends with `\n`, with
+//!   no leading or trailing spaces and no `\r`, and
+//! - nothing follows the last line.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::input_spec;
+//! use proconio::validate;
+//!
+//! input_spec! {
+//!     struct Input {
+//!         n: usize in 1..=100_000,
+//!         a: [i64 in -1_000_000_000..=1_000_000_000; n],
+//!     }
+//! }
+//!
+//! assert!(validate::run::<Input, _>(&b"3\n1 -2 3\n"[..]).is_ok());
+//!
+//! let error = validate::run::<Input, _>(&b"3\n1  -2 3\n"[..]).unwrap_err();
+//! assert_eq!((error.line, error.column), (2, 3));
+//! assert_eq!(error.to_string(), "line 2, column 3: expected a token, found a space");
+//!
+//! let error = validate::run::<Input, _>(&b"0\n\n"[..]).unwrap_err();
+//! assert_eq!(error.to_string(), "line 1, column 1: `0` is not in 1..=100000");
+//! ```
+//!
+//! The expected layout is the one generated by `generate()` of the struct: each field is on its
+//! own line, and the elements of an array are separated by a space, except that each element of
+//! an array of arrays or tuples is on its own line.  An empty array takes no line.
+//!
+//! The values are read through `Readable` as in `input!`, so an invalid token such as `x` for an
+//! integer is also reported, at the last token read.  The sources reading lines, `Line` and
+//! `LineBytes`, are not supported.
+
+use crate::gen::Spec;
+use crate::source::Source;
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+
+/// The error found by `run()`, with its position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The 1-indexed line of the error.
+    pub line: usize,
+    /// The 1-indexed column of the error, in bytes.
+    pub column: usize,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ValidationError {}
+
+/// The input format validated by `run()`.
+///
+/// This is implemented by the structs declared by `input_spec!`.
+pub trait Validate {
+    /// Reads the values from `validator` with their specs.
+    fn validate(validator: &mut Validator);
+}
+
+/// Validates the input read from `reader` against the format `T`.
+///
+/// The errors unwind out of `T::validate()`, and this catches them by `catch_unwind()`.  With
+/// `panic = "abort"` in the profile, the program aborts at the first error instead.
+pub fn run<T: Validate + ?Sized, R: Read>(mut reader: R) -> Result<(), ValidationError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|e| ValidationError {
+            line: 1,
+            column: 1,
+            message: format!("failed to read the input: {}", e),
+        })?;
+    let text = match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => {
+            let valid = e.utf8_error().valid_up_to();
+            let before = &e.as_bytes()[..valid];
+            let line_start = before
+                .iter()
+                .rposition(|&b| b == b'\n')
+                .map_or(0, |i| i + 1);
+            return Err(ValidationError {
+                line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+                column: valid - line_start + 1,
+                message: "invalid UTF-8 sequence".to_string(),
+            });
+        }
+    };
+
+    let mut validator = Validator::new(text);
+    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+        T::validate(&mut validator);
+        validator.finish();
+    }));
+    res.map_err(|payload| match payload.downcast::<ValidationError>() {
+        Ok(error) => *error,
+        // a panic in `Readable`, such as a token which can't be parsed.
+        Err(payload) => {
+            let (line, line_start, start) = validator.last;
+            ValidationError {
+                line,
+                column: start - line_start + 1,
+                message: panic_message(&*payload),
+            }
+        }
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Source reading the tokens strictly for `run()`.
+///
+/// This has `push()` and `new_line()` as `gen::GenSource` does, so that `gen_input!` can read the
+/// input in its layout.  An error unwinds with `ValidationError`, which `run()` returns.
+pub struct Validator {
+    text: String,
+    pos: usize,
+    line: usize,
+    line_start: usize,
+    // whether a token is read in the current line.
+    in_line: bool,
+    // the token checked by `push()`, which is read next.
+    pending: Option<(usize, usize)>,
+    // the line, the start of the line and the start of the last token read.  `new_line()` may
+    // move on to the next line after it.
+    last: (usize, usize, usize),
+}
+
+impl Validator {
+    fn new(text: String) -> Validator {
+        Validator {
+            text,
+            pos: 0,
+            line: 1,
+            line_start: 0,
+            in_line: false,
+            pending: None,
+            last: (1, 0, 0),
+        }
+    }
+
+    /// Checks that the next token satisfies the spec.
+    pub fn push<P: Spec + ?Sized>(&mut self, spec: &P) {
+        let (start, end) = self.token();
+        if let Err(reason) = spec.check(&self.text[start..end]) {
+            self.fail(start, &reason);
+        }
+        self.pending = Some((start, end));
+    }
+
+    /// Checks that the current line ends here, unless no token is read in the line.
+    pub fn new_line(&mut self) {
+        if !self.in_line {
+            return;
+        }
+        match self.text.as_bytes().get(self.pos) {
+            Some(b'\n') => {}
+            found => self.fail(
+                self.pos,
+                &format!("expected a newline, found {}", describe(found)),
+            ),
+        }
+
+        self.pos += 1;
+        self.line += 1;
+        self.line_start = self.pos;
+        self.in_line = false;
+    }
+
+    fn finish(&mut self) {
+        self.new_line();
+        if self.pos < self.text.len() {
+            let found = describe(self.text.as_bytes().get(self.pos));
+            self.fail(self.pos, &format!("expected end of file, found {}", found));
+        }
+    }
+
+    // the range of the next token, checking the space before it.
+    fn token(&mut self) -> (usize, usize) {
+        if let Some(token) = self.pending {
+            return token;
+        }
+
+        let bytes = self.text.as_bytes();
+        if self.in_line {
+            match bytes.get(self.pos) {
+                Some(b' ') => self.pos += 1,
+                Some(b'\n') => self.fail(self.pos, "expected a token, found end of line"),
+                found => {
+                    let found = describe(found);
+                    self.fail(self.pos, &format!("expected a space, found {}", found));
+                }
+            }
+        }
+
+        let start = self.pos;
+        match bytes.get(start) {
+            Some(b) if !b.is_ascii_whitespace() => {}
+            found => {
+                let found = describe(found);
+                self.fail(start, &format!("expected a token, found {}", found));
+            }
+        }
+        let len = bytes[start..]
+            .iter()
+            .position(|b| b.is_ascii_whitespace())
+            .unwrap_or(bytes.len() - start);
+
+        self.pos = start + len;
+        self.in_line = true;
+        (start, self.pos)
+    }
+
+    fn error_at(&self, pos: usize, message: &str) -> ValidationError {
+        ValidationError {
+            line: self.line,
+            column: pos - self.line_start + 1,
+            message: message.to_string(),
+        }
+    }
+
+    // unwinds without the panic hook, so that nothing is printed for the invalid input.
+    fn fail(&self, pos: usize, message: &str) -> ! {
+        panic::resume_unwind(Box::new(self.error_at(pos, message)))
+    }
+}
+
+fn describe(found: Option<&u8>) -> String {
+    match found {
+        None => "end of file".to_string(),
+        Some(b' ') => "a space".to_string(),
+        Some(b'\n') => "end of line".to_string(),
+        Some(&b) if b.is_ascii_whitespace() => format!("`{}`", char::from(b).escape_default()),
+        // the rest of the token is not needed; the position tells it.
+        Some(_) => "a token".to_string(),
+    }
+}
+
+impl Source<io::Empty> for Validator {
+    fn next_token(&mut self) -> Option<&str> {
+        let (start, end) = self.token();
+        self.pending = None;
+        self.last = (self.line, self.line_start, start);
+        Some(&self.text[start..end])
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.pending.is_none() && self.pos == self.text.len()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        let (start, end) = self.token();
+        self.pending = Some((start, end));
+        Some(&self.text[start..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::word;
+    use crate::input_spec;
+    use crate::marker::{Chars, Usize1};
+
+    input_spec! {
+        #[allow(dead_code)]
+        struct Graph {
+            n: usize in 2..=4,
+            name: Chars in word("ab", 1..=2),
+            edges: [(Usize1 in 1..=n, Usize1 in 1..=n); n - 1],
+            weights: [u32 in 0..10; n - 1],
+        }
+    }
+
+    fn validate(input: &str) -> Result<(), String> {
+        run::<Graph, _>(input.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(validate("3\nab\n1 2\n2 3\n5 9\n"), Ok(()));
+    }
+
+    #[test]
+    fn generated() {
+        for seed in 0..20 {
+            let mut generator = crate::gen::GenSource::new(seed);
+            Graph::generate(&mut generator);
+            let text = generator.take_text();
+            assert_eq!(validate(&text), Ok(()), "{}", text);
+        }
+    }
+
+    #[test]
+    fn whitespaces() {
+        assert_eq!(
+            validate(" 3\n"),
+            Err("line 1, column 1: expected a token, found a space".into())
+        );
+        assert_eq!(
+            validate("3 \n"),
+            Err("line 1, column 2: expected a newline, found a space".into())
+        );
+        assert_eq!(
+            validate("3\r\nab\n"),
+            Err("line 1, column 2: expected a newline, found `\\r`".into())
+        );
+        assert_eq!(
+            validate("3\nab\n1 2 2 3\n"),
+            Err("line 3, column 4: expected a newline, found a space".into())
+        );
+        assert_eq!(
+            validate("3\nab\n1\n2\n"),
+            Err("line 3, column 2: expected a token, found end of line".into())
+        );
+        assert_eq!(
+            validate("3\nab\n1 2\n2 3\n5 9"),
+            Err("line 5, column 4: expected a newline, found end of file".into())
+        );
+        assert_eq!(
+            validate("3\nab\n1 2\n2 3\n5 9\n\n"),
+            Err("line 6, column 1: expected end of file, found end of line".into())
+        );
+    }
+
+    #[test]
+    fn specs() {
+        assert_eq!(
+            validate("5\n"),
+            Err("line 1, column 1: `5` is not in 2..=4".into())
+        );
+        assert_eq!(
+            validate("2\nabc\n"),
+            Err("line 2, column 1: the length of `abc` is not in 1..=2".into())
+        );
+        assert_eq!(
+            validate("2\nab\n1 3\n"),
+            Err("line 3, column 3: `3` is not in 1..=2".into())
+        );
+        assert_eq!(
+            validate("2\nab\n1 2\n10\n"),
+            Err("line 4, column 1: `10` is not in 0..10".into())
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(
+            run::<Graph, _>(&b"2\na\xff\n"[..]).map_err(|e| e.to_string()),
+            Err("line 2, column 2: invalid UTF-8 sequence".into())
+        );
+    }
+
+    #[test]
+    fn panic_after_new_line() {
+        use crate::source::Readable;
+
+        // a `Readable` panicking after the line of its token ends.
+        struct Late;
+        impl Validate for Late {
+            fn validate(validator: &mut Validator) {
+                u32::read(validator);
+                u32::read(validator);
+                validator.new_line();
+                panic!("broken");
+            }
+        }
+
+        let error = run::<Late, _>(&b"1 23\n"[..]).unwrap_err();
+        assert_eq!(error.to_string(), "line 1, column 3: broken");
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Defines the source and the sink backed by JavaScript callbacks.
+//!
+//! This module requires `wasm` feature enabled.  In `wasm32-unknown-unknown`, there is no stdin or
+//! stdout; the input is supplied by JavaScript and the output is shown by it.  `JsSource` pulls
+//! the input chunk by chunk from a JavaScript function, and `JsSink` passes the output to another
+//! one.  With `harness::with_source()` and `harness::with_sink()`, the solutions using `input!` and
+//! `output!` run as they are:
+//!
+//! ```no_run
+//! # extern crate proconio;
+//! use js_sys::Function;
+//! use proconio::harness;
+//! use proconio::wasm::{JsSink, JsSource};
+//! use proconio::{input, outputln};
+//! use wasm_bindgen::prelude::*;
+//!
+//! fn solve() {
+//!     input! {
+//!         n: usize,
+//!         a: [i64; n],
+//!     }
+//!     outputln!("{}", a.iter().sum::<i64>());
+//! }
+//!
+//! // `read` returns the next chunk of the input as a string, or `null` at the end.
+//! // `write` receives the output.
+//! # #[cfg(target_arch = "wasm32")]
+//! #[wasm_bindgen]
+//! pub fn run(read: Function, write: Function) {
+//!     harness::with_source(JsSource::new(read), || {
+//!         harness::with_sink(JsSink::new(write), solve);
+//!     });
+//! }
+//! ```
+//!
+//! The callbacks are called on the thread running the solution.  `JsSource` and `JsSink` are
+//! `Send` only on `wasm32` without threads, where there is no other thread.
+
+use crate::sink::callback::CallbackSink;
+use crate::source::chunk::ChunkSource;
+use crate::source::Source;
+use js_sys::Function;
+use std::io::{self, Write};
+use wasm_bindgen::JsValue;
+
+type ReadChunk = Box<dyn FnMut() -> Option<String>>;
+type WriteText = Box<dyn FnMut(&str)>;
+
+/// Source pulling the input from a JavaScript function.
+///
+/// The function is called with no arguments, and returns the next chunk as a string, or `null` or
+/// `undefined` at the end of the input.  A token may be split across chunks.
+pub struct JsSource(ChunkSource<ReadChunk>);
+
+impl JsSource {
+    /// Creates `JsSource` pulling the chunks from the function.
+    pub fn new(read: Function) -> JsSource {
+        JsSource(ChunkSource::new(Box::new(move || {
+            let chunk = read
+                .call0(&JsValue::NULL)
+                .unwrap_or_else(|e| panic!("failed to read the input from JavaScript: {:?}", e));
+            if chunk.is_null() || chunk.is_undefined() {
+                return None;
+            }
+
+            match chunk.as_string() {
+                Some(chunk) => Some(chunk),
+                None => panic!(
+                    "failed to read the input from JavaScript: `{:?}` is not a string",
+                    chunk
+                ),
+            }
+        })))
+    }
+}
+
+impl Source<io::Empty> for JsSource {
+    fn next_token(&mut self) -> Option<&str> {
+        self.0.next_token()
+    }
+
+    fn is_empty(&mut self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn peek_token(&mut self) -> Option<&str> {
+        self.0.peek_token()
+    }
+
+    fn skip_line(&mut self) {
+        self.0.skip_line()
+    }
+}
+
+/// Sink passing the output to a JavaScript function.
+///
+/// The function is called with the output string as the only argument, for each complete line and
+/// for the rest on flush.
+pub struct JsSink(CallbackSink<WriteText>);
+
+impl JsSink {
+    /// Creates `JsSink` passing the output to the function.
+    pub fn new(write: Function) -> JsSink {
+        JsSink(CallbackSink::new(Box::new(move |text: &str| {
+            write
+                .call1(&JsValue::NULL, &JsValue::from_str(text))
+                .unwrap_or_else(|e| panic!("failed to write the output to JavaScript: {:?}", e));
+        })))
+    }
+}
+
+impl Write for JsSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+// JavaScript values can't be sent to other threads, but `wasm32` without `atomics` has no other
+// thread.  This is needed to store them in the globals of `harness`.
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Send for JsSource {}
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Send for JsSink {}
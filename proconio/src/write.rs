@@ -0,0 +1,645 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+//! Declares `Writable`, the counterpart of `Readable` writing a value back to the input text.
+//!
+//! `Writable` is implemented for the types read as themselves (the `FromStr` types which are also
+//! `Display`) and the markers.  The text written by `to_text()` is read back into the same value,
+//! which `assert_roundtrip()` checks.  This is useful to test your own implementation of
+//! `Readable`, or to make the input of a test from the values.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::marker::{Chars, LenPrefixed, Usize1};
+//! use proconio::write::{assert_roundtrip, to_text};
+//!
+//! assert_eq!(to_text::<Usize1>(&0), "1");
+//! assert_eq!(to_text::<LenPrefixed<Chars>>(&vec![vec!['a', 'b'], vec!['c']]), "2 ab c");
+//!
+//! assert_roundtrip::<i64>(&-42);
+//! assert_roundtrip::<LenPrefixed<Usize1>>(&vec![0, 4, 2]);
+//! ```
+//!
+//! The tokens are separated by a space, and `Line` and `LineBytes` are written on their own line.
+//! Some values can't be written to be read back, such as an empty `String` or a `Line` containing
+//! a newline; `assert_roundtrip()` catches them.  `Tokens<T>` is written by `Display` of `T`, which
+//! must write its `T::TOKENS` tokens separated by spaces.
+//!
+//! To implement `Writable` for your type, write the tokens in the order of `Readable::read()` by
+//! `write_token()`, or by `Writable::write()` of the types read in it.
+//!
+//! ```
+//! # extern crate proconio;
+//! use proconio::marker::Usize1;
+//! use proconio::source::{BufRead, Readable, Source};
+//! use proconio::write::{assert_roundtrip, Writable};
+//!
+//! // a 1-indexed edge with its weight.
+//! struct Edge;
+//!
+//! impl Readable for Edge {
+//!     type Output = (usize, usize, u64);
+//!     fn read<R: BufRead, S: Source<R>>(source: &mut S) -> (usize, usize, u64) {
+//!         (Usize1::read(source), Usize1::read(source), u64::read(source))
+//!     }
+//! }
+//!
+//! impl Writable for Edge {
+//!     fn write(&(u, v, w): &(usize, usize, u64), out: &mut String) {
+//!         Usize1::write(&u, out);
+//!         Usize1::write(&v, out);
+//!         u64::write(&w, out);
+//!     }
+//! }
+//!
+//! assert_roundtrip::<Edge>(&(0, 2, 10));
+//! ```
+
+use crate::marker::{
+    ArcOf, ArrayOf, BTreeMapOf, BTreeSetOf, Bin, BinaryHeapOf, BoxOf, Bytes, CharClass, Chars,
+    Checked, DebugBounded, Digits, DigitsRev, FromStrReadable, Hex, HhMm, HhMmSs, Isize1,
+    LenPrefixed, Line, LineBytes, LooseF64, Matches, Mod, Oct, RcOf, Saturate, Saturating, Tokens,
+    Usize1, VecDequeOf, Ymd,
+};
+use crate::marker::{Bounded, ParseError};
+use crate::source::slice::SliceSource;
+use crate::source::{FromTokens, Readable, Source};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::convert::TryInto;
+use core::fmt::{Binary, Debug, Display, LowerHex, Octal, Write};
+use core::str::FromStr;
+
+/// A trait representing which value of a `Readable` type can be written back as the input.
+pub trait Writable: Readable {
+    /// Writes `value` to `out` as the tokens read back into the same value by `Self::read()`.
+    fn write(value: &Self::Output, out: &mut String);
+}
+
+/// Writes a token to `out`, separated from the previous token by a space.
+pub fn write_token<D: Display + ?Sized>(out: &mut String, token: &D) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push(' ');
+    }
+    // writing to `String` never fails.
+    let _ = write!(out, "{}", token);
+}
+
+/// Writes a line to `out`, after ending the current line.
+pub fn write_line(out: &mut String, line: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(line);
+    out.push('\n');
+}
+
+/// Writes `value` as the input text of `T`.
+pub fn to_text<T: Writable + ?Sized>(value: &T::Output) -> String {
+    let mut out = String::new();
+    T::write(value, &mut out);
+    out
+}
+
+/// Asserts that `value` is read back from the text written by `T::write()`.
+///
+/// This panics if the value read is different, or some tokens are left unread.
+pub fn assert_roundtrip<T: Writable + ?Sized>(value: &T::Output)
+where
+    T::Output: PartialEq + Debug,
+{
+    let text = to_text::<T>(value);
+    let mut source = SliceSource::from(text.as_str());
+    let read = T::read(&mut source);
+    assert!(
+        read == *value,
+        "failed to roundtrip the value of `{}`: {:?} is written as `{}`, but read as {:?}",
+        type_name::<T>(),
+        value,
+        text.trim_end(),
+        read
+    );
+    if let Some(token) = source.next_token() {
+        panic!(
+            "failed to roundtrip the value of `{}`: {:?} is written as `{}`, but `{}` is left",
+            type_name::<T>(),
+            value,
+            text.trim_end(),
+            token
+        );
+    }
+}
+
+// the types read as themselves.
+impl<T: FromStr + Display> Writable for T
+where
+    T::Err: Debug,
+{
+    fn write(value: &T, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+impl Writable for Chars {
+    fn write(value: &Vec<char>, out: &mut String) {
+        write_token(out, &value.iter().collect::<String>())
+    }
+}
+
+// invalid UTF-8 sequences are replaced since the text is `String`.
+impl Writable for Bytes {
+    fn write(value: &Vec<u8>, out: &mut String) {
+        write_token(out, &String::from_utf8_lossy(value))
+    }
+}
+
+impl Writable for Line {
+    fn write(value: &String, out: &mut String) {
+        write_line(out, value)
+    }
+}
+
+impl Writable for LineBytes {
+    fn write(value: &Vec<u8>, out: &mut String) {
+        write_line(out, &String::from_utf8_lossy(value))
+    }
+}
+
+impl Writable for Usize1 {
+    fn write(value: &usize, out: &mut String) {
+        let value = value.checked_add(1).unwrap_or_else(|| {
+            panic!(
+                concat!(
+                    "attempted to write the value {} as a Usize1:",
+                    " the value is usize::MAX and cannot be incremented"
+                ),
+                value,
+            )
+        });
+        write_token(out, &value)
+    }
+}
+
+impl Writable for Isize1 {
+    fn write(value: &isize, out: &mut String) {
+        let value = value.checked_add(1).unwrap_or_else(|| {
+            panic!(
+                concat!(
+                    "attempted to write the value {} as a Isize1:",
+                    " the value is isize::MAX and cannot be incremented"
+                ),
+                value,
+            )
+        });
+        write_token(out, &value)
+    }
+}
+
+fn write_digits<'a>(digits: impl Iterator<Item = &'a u8>, out: &mut String) {
+    let token: String = digits.map(|&d| char::from(b'0' + d)).collect();
+    write_token(out, &token)
+}
+
+impl Writable for Digits {
+    fn write(value: &Vec<u8>, out: &mut String) {
+        write_digits(value.iter(), out)
+    }
+}
+
+impl Writable for DigitsRev {
+    fn write(value: &Vec<u8>, out: &mut String) {
+        write_digits(value.iter().rev(), out)
+    }
+}
+
+impl<const P: u64> Writable for Mod<P> {
+    fn write(value: &u64, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+impl<T: Writable, const MIN: i128, const MAX: i128> Writable for Bounded<T, MIN, MAX>
+where
+    T::Output: TryInto<i128> + Copy + Debug,
+{
+    fn write(value: &T::Output, out: &mut String) {
+        T::write(value, out)
+    }
+}
+
+impl<T: Writable, const MIN: i128, const MAX: i128> Writable for DebugBounded<T, MIN, MAX>
+where
+    T::Output: TryInto<i128> + Copy + Debug,
+{
+    fn write(value: &T::Output, out: &mut String) {
+        T::write(value, out)
+    }
+}
+
+impl<C: CharClass> Writable for Matches<C> {
+    fn write(value: &String, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+#[cfg(feature = "regex")]
+impl<P: crate::marker::Pattern> Writable for crate::marker::MatchesRegex<P> {
+    fn write(value: &String, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T: Writable> Writable for crate::marker::ComplexPair<T> {
+    fn write(value: &num_complex::Complex<T::Output>, out: &mut String) {
+        T::write(&value.re, out);
+        T::write(&value.im, out);
+    }
+}
+
+#[cfg(feature = "complex")]
+impl<T> Writable for crate::marker::ComplexToken<T>
+where
+    num_complex::Complex<T>: FromStr + Display,
+    <num_complex::Complex<T> as FromStr>::Err: Display,
+{
+    fn write(value: &num_complex::Complex<T>, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+fn write_clock(seconds: u32, fields: usize, out: &mut String) {
+    let clock = [seconds / 3600, seconds / 60 % 60, seconds % 60];
+    let token: Vec<String> = clock[..fields]
+        .iter()
+        .map(|v| format!("{:02}", v))
+        .collect();
+    write_token(out, &token.join(":"))
+}
+
+impl Writable for HhMm {
+    fn write(value: &u32, out: &mut String) {
+        write_clock(*value, 2, out)
+    }
+}
+
+impl Writable for HhMmSs {
+    fn write(value: &u32, out: &mut String) {
+        write_clock(*value, 3, out)
+    }
+}
+
+impl Writable for Ymd {
+    fn write(&(year, month, day): &(u32, u32, u32), out: &mut String) {
+        write_token(out, &format!("{}/{:02}/{:02}", year, month, day))
+    }
+}
+
+impl Writable for LooseF64 {
+    fn write(value: &f64, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+impl<T: Saturate + Display> Writable for Saturating<T> {
+    fn write(value: &T, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+// the invalid token is written back as is.
+impl<T: FromStr + Display> Writable for Checked<T>
+where
+    T::Err: Display,
+{
+    fn write(value: &Result<T, ParseError>, out: &mut String) {
+        match value {
+            Ok(value) => write_token(out, value),
+            Err(e) => write_token(out, &e.token),
+        }
+    }
+}
+
+fn write_radix<T: LowerHex + Octal + Binary>(
+    out: &mut String,
+    negative: bool,
+    magnitude: T,
+    radix: u32,
+) {
+    let sign = if negative { "-" } else { "" };
+    let token = match radix {
+        16 => format!("{}{:x}", sign, magnitude),
+        8 => format!("{}{:o}", sign, magnitude),
+        _ => format!("{}{:b}", sign, magnitude),
+    };
+    write_token(out, &token)
+}
+
+macro_rules! impl_writable_for_radix {
+    (signed: $($ty:ty)*; unsigned: $($uty:ty)*) => {
+        $(
+            impl_writable_for_radix!(@impl $ty, v => (v < 0, v.unsigned_abs()));
+        )*
+        $(
+            impl_writable_for_radix!(@impl $uty, v => (false, v));
+        )*
+    };
+    (@impl $ty:ty, $v:ident => $split:expr) => {
+        impl Writable for Hex<$ty> {
+            fn write(value: &$ty, out: &mut String) {
+                let $v = *value;
+                let (negative, magnitude) = $split;
+                write_radix(out, negative, magnitude, 16)
+            }
+        }
+
+        impl Writable for Oct<$ty> {
+            fn write(value: &$ty, out: &mut String) {
+                let $v = *value;
+                let (negative, magnitude) = $split;
+                write_radix(out, negative, magnitude, 8)
+            }
+        }
+
+        impl Writable for Bin<$ty> {
+            fn write(value: &$ty, out: &mut String) {
+                let $v = *value;
+                let (negative, magnitude) = $split;
+                write_radix(out, negative, magnitude, 2)
+            }
+        }
+    };
+}
+
+impl_writable_for_radix!(
+    signed: i8 i16 i32 i64 i128 isize;
+    unsigned: u8 u16 u32 u64 u128 usize
+);
+
+// `Display` must write the `T::TOKENS` tokens separated by spaces.
+impl<T: FromTokens + Display> Writable for Tokens<T> {
+    fn write(value: &T, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+impl<T: FromStr + Display> Writable for FromStrReadable<T>
+where
+    T::Err: Display,
+{
+    fn write(value: &T, out: &mut String) {
+        write_token(out, value)
+    }
+}
+
+impl<T: Writable, const N: usize> Writable for ArrayOf<T, N> {
+    fn write(value: &[T::Output; N], out: &mut String) {
+        for item in value {
+            T::write(item, out);
+        }
+    }
+}
+
+impl<T: Writable> Writable for BoxOf<T> {
+    fn write(value: &alloc::boxed::Box<T::Output>, out: &mut String) {
+        T::write(value, out)
+    }
+}
+
+impl<T: Writable> Writable for RcOf<T> {
+    fn write(value: &alloc::rc::Rc<T::Output>, out: &mut String) {
+        T::write(value, out)
+    }
+}
+
+impl<T: Writable> Writable for ArcOf<T> {
+    fn write(value: &alloc::sync::Arc<T::Output>, out: &mut String) {
+        T::write(value, out)
+    }
+}
+
+fn write_collection<'a, T, I>(items: I, out: &mut String)
+where
+    T: Writable,
+    T::Output: 'a,
+    I: ExactSizeIterator<Item = &'a T::Output>,
+{
+    write_token(out, &items.len());
+    for item in items {
+        T::write(item, out);
+    }
+}
+
+impl<T: Writable> Writable for LenPrefixed<T> {
+    fn write(value: &Vec<T::Output>, out: &mut String) {
+        write_collection::<T, _>(value.iter(), out)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Writable> Writable for crate::marker::HashSetOf<T>
+where
+    T::Output: Eq + std::hash::Hash,
+{
+    fn write(value: &std::collections::HashSet<T::Output>, out: &mut String) {
+        write_collection::<T, _>(value.iter(), out)
+    }
+}
+
+impl<T: Writable> Writable for BTreeSetOf<T>
+where
+    T::Output: Ord,
+{
+    fn write(value: &alloc::collections::BTreeSet<T::Output>, out: &mut String) {
+        write_collection::<T, _>(value.iter(), out)
+    }
+}
+
+impl<T: Writable> Writable for VecDequeOf<T> {
+    fn write(value: &alloc::collections::VecDeque<T::Output>, out: &mut String) {
+        write_collection::<T, _>(value.iter(), out)
+    }
+}
+
+impl<T: Writable> Writable for BinaryHeapOf<T>
+where
+    T::Output: Ord,
+{
+    fn write(value: &alloc::collections::BinaryHeap<T::Output>, out: &mut String) {
+        write_collection::<T, _>(value.iter(), out)
+    }
+}
+
+fn write_map<'a, K, V, I>(entries: I, out: &mut String)
+where
+    K: Writable,
+    V: Writable,
+    K::Output: 'a,
+    V::Output: 'a,
+    I: ExactSizeIterator<Item = (&'a K::Output, &'a V::Output)>,
+{
+    write_token(out, &entries.len());
+    for (key, value) in entries {
+        K::write(key, out);
+        V::write(value, out);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Writable, V: Writable> Writable for crate::marker::HashMapOf<K, V>
+where
+    K::Output: Eq + std::hash::Hash,
+{
+    fn write(value: &std::collections::HashMap<K::Output, V::Output>, out: &mut String) {
+        write_map::<K, V, _>(value.iter(), out)
+    }
+}
+
+impl<K: Writable, V: Writable> Writable for BTreeMapOf<K, V>
+where
+    K::Output: Ord,
+{
+    fn write(value: &alloc::collections::BTreeMap<K::Output, V::Output>, out: &mut String) {
+        write_map::<K, V, _>(value.iter(), out)
+    }
+}
+
+#[cfg(feature = "geometry")]
+impl<T: Writable> Writable for crate::geometry::Point<T> {
+    fn write(value: &crate::geometry::Point<T::Output>, out: &mut String) {
+        T::write(&value.x, out);
+        T::write(&value.y, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::marker::{Mod998244353, Saturating};
+    use alloc::collections::BTreeMap;
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn primitives() {
+        assert_roundtrip::<u64>(&u64::MAX);
+        assert_roundtrip::<i128>(&i128::MIN);
+        assert_roundtrip::<f64>(&0.1);
+        assert_roundtrip::<char>(&'x');
+        assert_roundtrip::<String>(&"hello".to_string());
+        assert_roundtrip::<bool>(&true);
+    }
+
+    #[test]
+    fn markers() {
+        assert_roundtrip::<Usize1>(&0);
+        assert_roundtrip::<Isize1>(&-5);
+        assert_roundtrip::<Chars>(&vec!['a', 'b']);
+        assert_roundtrip::<Bytes>(&b"xyz".to_vec());
+        assert_roundtrip::<Digits>(&vec![1, 0, 3]);
+        assert_roundtrip::<DigitsRev>(&vec![3, 0, 1]);
+        assert_roundtrip::<Mod998244353>(&5);
+        assert_roundtrip::<Bounded<u32, 1, 10>>(&10);
+        assert_roundtrip::<HhMm>(&32700);
+        assert_roundtrip::<HhMmSs>(&86399);
+        assert_roundtrip::<Ymd>(&(2024, 2, 29));
+        assert_roundtrip::<Saturating<u8>>(&255);
+        assert_roundtrip::<Checked<u8>>(&Ok(3));
+        assert_roundtrip::<ArrayOf<Usize1, 2>>(&[3, 1]);
+        assert_roundtrip::<BTreeMapOf<String, Usize1>>(&BTreeMap::from([
+            ("a".to_string(), 0),
+            ("b".to_string(), 1),
+        ]));
+    }
+
+    #[test]
+    fn radix() {
+        assert_eq!(to_text::<Hex<i32>>(&-255), "-ff");
+        assert_eq!(to_text::<Bin<u8>>(&5), "101");
+        assert_roundtrip::<Hex<i8>>(&i8::MIN);
+        assert_roundtrip::<Oct<u128>>(&u128::MAX);
+        assert_roundtrip::<Bin<isize>>(&-6);
+    }
+
+    #[test]
+    fn lines() {
+        let mut out = String::new();
+        usize::write(&2, &mut out);
+        Line::write(&" a b ".to_string(), &mut out);
+        LineBytes::write(&b"#.".to_vec(), &mut out);
+        u32::write(&7, &mut out);
+        assert_eq!(out, "2\n a b \n#.\n7");
+
+        assert_roundtrip::<Line>(&" x  y ".to_string());
+        assert_roundtrip::<LenPrefixed<Line>>(&vec!["a".to_string(), "".to_string()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "is written as `1 1`, but `1` is left")]
+    fn token_left() {
+        use crate::source::BufRead;
+
+        // writes the value twice.
+        enum Twice {}
+        impl Readable for Twice {
+            type Output = u32;
+            fn read<R: BufRead, S: Source<R>>(source: &mut S) -> u32 {
+                u32::read(source)
+            }
+        }
+        impl Writable for Twice {
+            fn write(value: &u32, out: &mut String) {
+                u32::write(value, out);
+                u32::write(value, out);
+            }
+        }
+
+        assert_roundtrip::<Twice>(&1);
+    }
+
+    #[test]
+    fn tokens() {
+        use core::fmt::{self, Formatter};
+
+        #[derive(Debug, PartialEq)]
+        struct Range(u32, u32);
+        impl FromTokens for Range {
+            const TOKENS: usize = 2;
+            type Err = core::num::ParseIntError;
+            fn from_tokens(tokens: &[&str]) -> Result<Range, Self::Err> {
+                Ok(Range(tokens[0].parse()?, tokens[1].parse()?))
+            }
+        }
+        impl Display for Range {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, self.1)
+            }
+        }
+
+        assert_roundtrip::<LenPrefixed<Tokens<Range>>>(&vec![Range(1, 2), Range(3, 4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value is usize::MAX and cannot be incremented")]
+    fn usize1_overflow() {
+        to_text::<Usize1>(&usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "the value is isize::MAX and cannot be incremented")]
+    fn isize1_overflow() {
+        to_text::<Isize1>(&isize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "is written as ``, but read as \"\"")]
+    fn line_mismatch() {
+        assert_roundtrip::<Line>(&"\n".to_string());
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{input, is_stdin_empty};
+
+fn test_args() {
+    proconio::from_args();
+    input! {
+        n: usize,
+        a: [i32; n],
+        s: String,
+    }
+    assert!(is_stdin_empty());
+
+    println!("{} {}", a.iter().sum::<i32>(), s);
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "2", "10 -3", "ok"])
+            .stdout()
+            .is("7 ok")
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        return;
+    }
+
+    test_args();
+}
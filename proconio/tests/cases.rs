@@ -0,0 +1,117 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{caseln, input, input_cases, outputln, test_samples};
+
+fn solve() {
+    input_cases! {
+        |case: usize| {
+            input! {
+                n: usize,
+                a: [i64; n],
+            }
+            if n == 0 {
+                outputln!("empty");
+                continue;
+            }
+            outputln!("{} {}", case, a.iter().sum::<i64>());
+        }
+    }
+}
+
+fn solve_google() {
+    input_cases! {
+        prefix,
+        |_| {
+            input!(a: i64, b: i64);
+            outputln!("{}", a + b);
+        }
+    }
+}
+
+fn solve_google_lines() {
+    input_cases! {
+        prefix("Case #{}:\n"),
+        |_| {
+            input!(s: String);
+            for c in s.chars() {
+                outputln!("{}", c);
+            }
+        }
+    }
+}
+
+#[test]
+fn cases() {
+    test_samples! {
+        solve,
+        "3\n2\n1 2\n0\n3\n4 5 6\n" => "0 3\nempty\n2 15\n",
+        "0\n" => "",
+    }
+}
+
+#[test]
+fn cases_prefix() {
+    test_samples! {
+        solve_google,
+        "2\n1 2\n-3 4\n" => "Case #1: 3\nCase #2: 1\n",
+    }
+    test_samples! {
+        solve_google_lines,
+        "2\nab\nc\n" => "Case #1:\na\nb\nCase #2:\nc\n",
+    }
+}
+
+#[test]
+fn cases_caseln() {
+    test_samples! {
+        || {
+            input_cases! {
+                |case| {
+                    input!(x: f64);
+                    caseln!(case, fixed(2), x / 2.0);
+                }
+            }
+        },
+        "2\n3\n1\n" => "Case #1: 1.50\nCase #2: 0.50\n",
+    }
+
+    let mut sink = Vec::new();
+    caseln!(to sink, 9, "{} {}", "Yes", 1);
+    assert_eq!(sink, b"Case #10: Yes 1\n");
+}
+
+#[test]
+fn case_buffer() {
+    use proconio::out::CaseBuffer;
+    use proconio::{capture_output, output};
+    use std::thread;
+
+    let mut buf = CaseBuffer::new(3);
+    thread::scope(|s| {
+        for i in (0..3).rev() {
+            let buf = &buf;
+            s.spawn(move || {
+                output!(to buf.case(i), "{}", i);
+                outputln!(to buf.case(i), " {}", i * i);
+            });
+        }
+    });
+    let output = capture_output(|| {
+        outputln!("header");
+        buf.flush();
+        buf.flush(); // already cleared.
+    });
+    assert_eq!(output, "header\n0 0\n1 1\n2 4\n");
+    assert_eq!(buf.len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "failed to get the case #3: there are only 3 cases")]
+fn case_buffer_out_of_range() {
+    proconio::out::CaseBuffer::new(3).case(3);
+}
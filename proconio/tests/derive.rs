@@ -26,6 +26,62 @@ struct Edge {
     pub(crate) cost: Cost,
 }
 
+#[derive_readable]
+#[derive(PartialEq, Debug)]
+struct Query(u8, proconio::marker::Usize1, proconio::marker::Usize1);
+
+// `Pair<Usize1>` has `usize` fields, while `Usize1` itself is not `Debug` nor `PartialEq`.
+#[derive_readable]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+#[derive_readable]
+struct Named {
+    name: proconio::marker::Chars,
+    pairs: Pair<Pair<proconio::marker::Usize1>>,
+}
+
+#[derive_readable]
+#[derive(PartialEq, Debug)]
+struct Triangle {
+    sides: [i64; 3],
+    label: [proconio::marker::Usize1; 2],
+    grid: [[u8; 2]; 2],
+}
+
+#[derive_readable]
+#[derive(PartialEq, Debug)]
+struct Shared {
+    boxed: Box<proconio::marker::Usize1>,
+    rc: std::rc::Rc<[Cost; 2]>,
+    arc: std::sync::Arc<Box<proconio::marker::Chars>>,
+}
+
+#[derive_readable]
+#[derive(PartialEq, Debug)]
+#[readable(order(to, from, weight))]
+struct ReversedEdge {
+    from: proconio::marker::Usize1,
+    to: proconio::marker::Usize1,
+    weight: i64,
+}
+
+#[derive_readable(soa)]
+#[readable(order(to, from, w))]
+struct WeightedEdge {
+    from: proconio::marker::Usize1,
+    to: proconio::marker::Usize1,
+    w: i64,
+}
+
+#[derive_readable(soa)]
+struct Labeled<T> {
+    label: proconio::marker::Chars,
+    value: T,
+}
+
 #[test]
 fn derive() {
     let source = AutoSource::from("  12 32 35");
@@ -39,3 +95,96 @@ fn derive() {
     assert_eq!(edge.weight, Weight);
     assert_eq!(edge.cost, Cost(35));
 }
+
+#[test]
+fn derive_tuple_and_generics() {
+    let source = AutoSource::from("2 1 3  abc 1 2 3 4");
+    input! {
+        from source,
+        query: Query,
+        named: Named,
+    }
+
+    assert_eq!(query, Query(2, 0, 2));
+    assert_eq!(named.name, ['a', 'b', 'c']);
+    assert_eq!(named.pairs.first.first, 0);
+    assert_eq!(named.pairs.first.second, 1);
+    assert_eq!(named.pairs.second.first, 2);
+    assert_eq!(named.pairs.second.second, 3);
+}
+
+#[test]
+fn derive_fixed_size_arrays() {
+    let source = AutoSource::from("3 4 5  1 2  1 2 3 4");
+    input! {
+        from source,
+        triangle: Triangle,
+    }
+
+    assert_eq!(
+        triangle,
+        Triangle {
+            sides: [3, 4, 5],
+            label: [0, 1],
+            grid: [[1, 2], [3, 4]],
+        }
+    );
+}
+
+#[test]
+fn derive_reading_order() {
+    let source = AutoSource::from("3 1 -5");
+    input! {
+        from source,
+        edge: ReversedEdge,
+    }
+
+    assert_eq!(
+        edge,
+        ReversedEdge {
+            from: 0,
+            to: 2,
+            weight: -5,
+        }
+    );
+}
+
+#[test]
+fn derive_smart_pointers() {
+    let source = AutoSource::from("1 2 3 ab");
+    input! {
+        from source,
+        shared: Shared,
+    }
+
+    assert_eq!(
+        shared,
+        Shared {
+            boxed: Box::new(0),
+            rc: std::rc::Rc::new([Cost(2), Cost(3)]),
+            arc: std::sync::Arc::new(Box::new(vec!['a', 'b'])),
+        }
+    );
+}
+
+#[test]
+fn derive_struct_of_arrays() {
+    let mut source = AutoSource::from("2 1 -5  3 2 7  ab 1  2  c 3 d 4");
+    let edges = WeightedEdgeSoA::read_n(&mut source, 2);
+    assert_eq!(edges.len(), 2);
+    assert_eq!(edges.from, [0, 1]);
+    assert_eq!(edges.to, [1, 2]);
+    assert_eq!(edges.w, [-5, 7]);
+
+    input! {
+        from &mut source,
+        first: [Labeled<proconio::marker::Usize1>; 1] as proconio::modifier::collect::<LabeledSoA<proconio::marker::Usize1>>(),
+        n: usize,
+        rest: [Labeled<u8>; n] as proconio::modifier::collect::<LabeledSoA<u8>>(),
+    }
+    assert_eq!(first.label, [vec!['a', 'b']]);
+    assert_eq!(first.value, [0]);
+    assert_eq!(rest.label, [vec!['c'], vec!['d']]);
+    assert_eq!(rest.value, [3, 4]);
+    assert!(!rest.is_empty());
+}
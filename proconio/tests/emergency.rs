@@ -0,0 +1,97 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{emergency, outputln};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// improves the answer forever, printing the best one in an emergency.
+fn improve_forever() {
+    let best = Arc::new(AtomicU64::new(0));
+    {
+        let best = Arc::clone(&best);
+        emergency::register(move || outputln!("best {}", best.load(Ordering::SeqCst)));
+    }
+    outputln!("started");
+    proconio::flush_output();
+
+    loop {
+        best.fetch_max(42, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+fn test_deadline() {
+    emergency::set_deadline(Duration::from_millis(100));
+    improve_forever();
+}
+
+fn test_cancel() {
+    emergency::register(|| outputln!("emergency"));
+    emergency::set_deadline(Duration::from_millis(10));
+    emergency::cancel();
+    thread::sleep(Duration::from_millis(100));
+    outputln!("finished");
+}
+
+#[cfg(unix)]
+fn assert_sigterm(program: &str) {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .arg("signal")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    assert_eq!(line, "started\n");
+
+    let status = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let mut rest = String::new();
+    stdout.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "best 42\n");
+    assert!(child.wait().unwrap().success());
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard output
+    if args().len() == 1 {
+        let program = args().next().unwrap();
+        Assert::command(&[&*program, "deadline"])
+            .stdout()
+            .is("started\nbest 42\n")
+            .unwrap();
+        Assert::command(&[&*program, "cancel"])
+            .stdout()
+            .is("finished\n")
+            .unwrap();
+        #[cfg(unix)]
+        assert_sigterm(&program);
+        return;
+    }
+
+    match &*args().nth(1).unwrap() {
+        "deadline" => test_deadline(),
+        "cancel" => test_cancel(),
+        "signal" => improve_forever(),
+        _ => unreachable!(),
+    }
+    proconio::flush_output();
+}
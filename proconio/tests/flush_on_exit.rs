@@ -0,0 +1,103 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::out::{self, Newline};
+use proconio::{caseln, flush_on_exit, input, output, output_lines, outputln};
+
+fn test_return() {
+    let _guard = flush_on_exit();
+    output!("hello, ");
+    outputln!("world");
+}
+
+fn test_panic() {
+    let _guard = flush_on_exit();
+    outputln!("before panic");
+    panic!("intended panic");
+}
+
+fn test_final_newline() {
+    let _guard = flush_on_exit();
+    out::set_newline(Newline::CrLf);
+    out::set_final_newline(true);
+    outputln!("a");
+    output_lines!(&[1, 2], " ");
+    caseln!(0, "x");
+    output!("no newline");
+}
+
+fn test_parse_error() {
+    let _guard = flush_on_exit();
+    input! {
+        _n: u32,
+        _a: (u32, u32),
+    }
+}
+
+fn test_panic_after_input() {
+    let _guard = flush_on_exit();
+    input! {
+        n: u32,
+    }
+    panic!("intended panic after reading {}", n);
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard output
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "return"])
+            .stdout()
+            .is("hello, world\n")
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "panic"])
+            .fails()
+            .and()
+            .stdout()
+            .is("before panic\n")
+            .and()
+            .stderr()
+            .contains("intended panic")
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "final_newline"])
+            .stdout()
+            .satisfies(
+                |stdout| stdout == "a\r\n1 2\r\nCase #1: x\r\nno newline\r\n",
+                "the newlines are not `\\r\\n` or the final newline is missing",
+            )
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "parse_error"])
+            .stdin("1\n2 x\n")
+            .fails()
+            .and()
+            .stderr()
+            .contains("note: the input was read up to the token 2 of the line 2")
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "panic_after_input"])
+            .stdin("3\n")
+            .fails()
+            .and()
+            .stderr()
+            .contains("note: the input was read up to the token 1 of the line 1")
+            .unwrap();
+        return;
+    }
+
+    match &*args().nth(1).unwrap() {
+        "return" => test_return(),
+        "panic" => test_panic(),
+        "final_newline" => test_final_newline(),
+        "parse_error" => test_parse_error(),
+        "panic_after_input" => test_panic_after_input(),
+        _ => unreachable!(),
+    }
+}
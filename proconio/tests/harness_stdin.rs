@@ -0,0 +1,46 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::harness::with_source;
+use proconio::input;
+use proconio::marker::Bytes;
+use proconio::source::auto::AutoSource;
+
+fn test_harness_stdin() {
+    // the harness doesn't open stdin, so it can still be read as bytes afterwards.
+    let x = with_source(AutoSource::from("7"), || {
+        input! {
+            x: u32,
+        }
+        x
+    });
+    proconio::read_stdin_as_bytes();
+    input! {
+        n: usize,
+        a: [i64; n],
+        raw: Bytes,
+    }
+
+    println!("{}", x);
+    println!("{}", a.iter().sum::<i64>());
+    println!("{:?}", raw);
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .stdin("3\n1 -2 30\n\u{ff}\n")
+            .stdout()
+            .is("7\n29\n[195, 191]")
+            .unwrap();
+        return;
+    }
+
+    test_harness_stdin();
+}
@@ -0,0 +1,73 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::input_checked;
+use proconio::marker::{Chars, Usize1};
+use proconio::source::auto::AutoSource;
+use std::collections::HashMap;
+
+#[test]
+fn input_checked() {
+    let n = 1;
+    let source = AutoSource::from("5 2 1 1 2 ab 3 4 1 1 x");
+    input_checked! {
+        from source,
+        m: usize,
+        (k, mut l): (usize, Usize1),
+        a: [[u32; k]; n],
+        b: [Chars; n],
+        c: [u64; k] as proconio::modifier::prefix_sum::<i64>(),
+        d: {proconio::marker::HashMapOf<u8, String>},
+    }
+
+    l += 1;
+    assert_eq!((m, k, l), (5, 2, 1));
+    assert_eq!(a, [[1, 2]]);
+    assert_eq!(b, [['a', 'b']]);
+    assert_eq!(c, [0, 3, 7]);
+    let _: HashMap<u8, String> = d;
+}
+
+#[test]
+fn input_checked_length_from_input() {
+    let source = AutoSource::from("2 1 2 0");
+    input_checked! {
+        from source,
+        a: [u8],
+        b: [[u8]; 1],
+    }
+
+    assert_eq!(a, [1, 2]);
+    assert_eq!(b, [Vec::<u8>::new()]);
+}
+
+#[test]
+fn input_checked_with_settings() {
+    let source = AutoSource::from("1,2");
+    input_checked! {
+        from source,
+        with { sep = "," },
+        a: [u8; 2],
+    }
+
+    assert_eq!(a, [1, 2]);
+}
+
+#[test]
+fn input_checked_in_source() {
+    let mut source = AutoSource::from("1 2");
+    input_checked! {
+        in source,
+        a: u8,
+    }
+    input_checked! {
+        in source,
+        b: u8,
+    }
+
+    assert_eq!((a, b), (1, 2));
+}
@@ -0,0 +1,57 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{input, is_stdin_empty};
+
+fn test_input_env() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+    assert!(is_stdin_empty());
+
+    println!("{}", a.iter().sum::<u32>());
+}
+
+fn main() {
+    use assert_cli::{Assert, Environment};
+    use std::env::{args, temp_dir};
+    use std::fs;
+
+    // relaunch the app with the environment variables
+    if args().len() == 1 {
+        let path = temp_dir().join(format!("proconio-input-env-{}.txt", std::process::id()));
+        fs::write(&path, "3\n1 2 3\n").unwrap();
+
+        let run = |env: Environment, expected: &str| {
+            Assert::command(&[&*args().next().unwrap(), "foo"])
+                .with_env(env)
+                .stdout()
+                .is(expected)
+                .unwrap();
+        };
+        run(
+            Environment::inherit().insert("PROCONIO_INPUT", path.to_str().unwrap()),
+            "6",
+        );
+        run(
+            Environment::inherit().insert("PROCONIO_INPUT_TEXT", "2 10 20"),
+            "30",
+        );
+        run(
+            Environment::inherit()
+                .insert("PROCONIO_INPUT", path.to_str().unwrap())
+                .insert("PROCONIO_INPUT_TEXT", "2 10 20"),
+            "6",
+        );
+
+        fs::remove_file(&path).unwrap();
+        return;
+    }
+
+    test_input_env();
+}
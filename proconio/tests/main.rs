@@ -0,0 +1,81 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{input, output, outputln};
+
+fn depth(n: usize) -> usize {
+    let padding = [0u8; 256];
+    if n == 0 {
+        padding.len() - 256
+    } else {
+        depth(n - 1) + 1 + std::hint::black_box(&padding)[0] as usize
+    }
+}
+
+#[proconio::main(stack = "512MB")]
+fn test_batch() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+
+    output!("{} ", depth(1_000_000));
+    println!("{}", a.iter().sum::<u32>());
+    outputln!("done");
+}
+
+#[proconio::main(interactive)]
+fn test_interactive() {
+    input! {
+        n: usize,
+    }
+
+    for _ in 0..n {
+        input! {
+            x: i32,
+        }
+        outputln!("? {}", x);
+    }
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard output
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "batch"])
+            .stdin("3\n1 2 3\n")
+            .stdout()
+            .contains("1000000 ")
+            .and()
+            .stdout()
+            .contains("6\n")
+            .and()
+            .stdout()
+            .contains("done\n")
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "interactive"])
+            .stdin("2\n5\n7\n")
+            .stdout()
+            .is("? 5\n? 7\n")
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        return;
+    }
+
+    match &*args().nth(1).unwrap() {
+        "batch" => test_batch(),
+        "interactive" => test_interactive(),
+        _ => unreachable!(),
+    }
+}
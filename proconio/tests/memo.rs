@@ -0,0 +1,61 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::memo::MemoDir;
+use proconio::{flush_output, input, outputln};
+
+fn test_memo() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+
+    outputln!("{}", a.iter().sum::<u32>());
+    flush_output();
+}
+
+fn main() {
+    use assert_cli::{Assert, Environment};
+    use std::env::{args, temp_dir};
+    use std::fs;
+
+    let args: Vec<_> = args().collect();
+    // relaunch the app to record its input and output
+    if args.len() == 1 {
+        let root = temp_dir().join(format!("proconio-memo-{}", std::process::id()));
+        let run = |input: &str, output: &str| {
+            Assert::command(&[&*args[0], "run"])
+                .with_env(
+                    Environment::inherit()
+                        .insert("PROCONIO_MEMO", "sample1")
+                        .insert("PROCONIO_MEMO_DIR", root.to_str().unwrap()),
+                )
+                .stdin(input)
+                .stdout()
+                .is(output)
+                .unwrap();
+        };
+
+        let memos = MemoDir::new(&root);
+        run("3\n1 2 3\n", "6");
+        let memo = memos.load("sample1").unwrap();
+        assert_eq!(memo.input, "3\n1 2 3\n");
+        assert_eq!(memo.output, "6\n");
+        assert_eq!(memo.previous_output, None);
+
+        run("2\n1 2\n", "3");
+        let memo = memos.load("sample1").unwrap();
+        assert_eq!(memo.input, "2\n1 2\n");
+        assert_eq!(memo.previous_output.as_deref(), Some("6\n"));
+        assert_eq!(memos.diff_last("sample1").unwrap().unwrap().position, 0);
+
+        fs::remove_dir_all(root).unwrap();
+        return;
+    }
+
+    test_memo();
+}
@@ -0,0 +1,61 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{flush_output, out, output, outputln, stdout_writer};
+use std::io::Write;
+use std::thread;
+
+fn test_output() {
+    output!("{} ", 1);
+    outputln!("{}", 2);
+    outputln!();
+    outputln!(fixed(3), 1.0 / 3.0, 2.0);
+    out::set_default_precision(1);
+    output!(fixed, 0.25);
+    outputln!(fixed, 1.0, 2.0,);
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                for _ in 0..100 {
+                    outputln!(fixed(2), 0.5, 1.5);
+
+                    let mut writer = stdout_writer();
+                    write!(writer, "0.50").unwrap();
+                    writeln!(writer, " 1.50").unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    flush_output();
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard output
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .stdout()
+            .is(&*format!(
+                "1 2\n\n0.333 2.000\n0.21.0 2.0\n{}",
+                "0.50 1.50\n".repeat(800)
+            ))
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        return;
+    }
+
+    test_output();
+}
@@ -0,0 +1,29 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::derive_readable;
+use proconio::input;
+use proconio::marker::Usize1;
+use proconio::source::auto::AutoSource;
+
+#[derive_readable]
+struct Polygon<T> {
+    vertices: [T; 3],
+    ids: [[Usize1; 2]; 1],
+}
+
+fn main() {
+    input! {
+        from AutoSource::from("1 2 3 1 2 5 6"),
+        polygon: Polygon<i32>,
+        fixed: {[u8; 2]},
+    }
+
+    let _: [i32; 3] = polygon.vertices;
+    let _: [[usize; 2]; 1] = polygon.ids;
+    let _: [u8; 2] = fixed;
+}
@@ -0,0 +1,33 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::derive_readable;
+use proconio::input;
+use proconio::marker::{Bytes, Usize1};
+use proconio::source::auto::AutoSource;
+
+#[derive_readable]
+struct Pair<T, U> {
+    first: T,
+    second: U,
+}
+
+#[derive_readable]
+struct Wrapped<T>(Pair<T, Bytes>);
+
+fn main() {
+    input! {
+        from AutoSource::from("1 ab 2 cd"),
+        pair: {Pair<Usize1, Bytes>},
+        wrapped: Wrapped<i64>,
+    }
+
+    let _: usize = pair.first;
+    let _: Vec<u8> = pair.second;
+    let _: i64 = (wrapped.0).first;
+    let _: Vec<u8> = (wrapped.0).second;
+}
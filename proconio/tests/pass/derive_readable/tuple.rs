@@ -0,0 +1,28 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::derive_readable;
+use proconio::input;
+use proconio::marker::{Chars, Usize1};
+use proconio::source::auto::AutoSource;
+
+#[derive_readable]
+struct Query(u8, Usize1, Usize1);
+
+#[derive_readable]
+struct Word(Chars, usize);
+
+fn main() {
+    input! {
+        from AutoSource::from("1 2 3 abc 4"),
+        query: Query,
+        word: Word,
+    }
+
+    let _: (u8, usize, usize) = (query.0, query.1, query.2);
+    let _: (Vec<char>, usize) = (word.0, word.1);
+}
@@ -0,0 +1,31 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::derive_readable;
+use proconio::input;
+use proconio::source::auto::AutoSource;
+use proconio::source::Readable;
+
+#[derive_readable]
+struct Edge<T>
+where
+    T: Readable,
+    T::Output: Copy,
+{
+    from: usize,
+    to: usize,
+    cost: T,
+}
+
+fn main() {
+    input! {
+        from AutoSource::from("1 2 3"),
+        edge: Edge<u64>,
+    }
+
+    let _: (usize, usize, u64) = (edge.from, edge.to, edge.cost);
+}
@@ -0,0 +1,61 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::input;
+
+fn test_record() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+
+    println!("{}", a.iter().sum::<u32>());
+}
+
+fn main() {
+    use assert_cli::{Assert, Environment};
+    use std::env::{args, temp_dir};
+    use std::fs;
+
+    let args: Vec<_> = args().collect();
+    // relaunch the app to record its stdin
+    if args.len() == 1 {
+        let input = "3\n1  2\t3\n";
+        let path = temp_dir().join(format!("proconio-record-{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        Assert::command(&[&*args[0], "env"])
+            .with_env(Environment::inherit().insert("PROCONIO_RECORD", path))
+            .stdin(input)
+            .stdout()
+            .is("6")
+            .unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), input);
+
+        Assert::command(&[&*args[0], "api", path])
+            .stdin("1 10\n")
+            .stdout()
+            .is("10")
+            .unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "1 10\n");
+
+        // replay the recorded input.
+        Assert::command(&[&*args[0], "env"])
+            .with_env(Environment::inherit().insert("PROCONIO_INPUT", path))
+            .stdout()
+            .is("10")
+            .unwrap();
+
+        fs::remove_file(path).unwrap();
+        return;
+    }
+
+    if args[1] == "api" {
+        proconio::record_stdin(&args[2]).unwrap();
+    }
+    test_record();
+}
@@ -0,0 +1,101 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::harness::with_source;
+use proconio::source::auto::AutoSource;
+use proconio::{capture_output, input, output, outputln, test_samples};
+use std::panic;
+use std::thread;
+
+fn solve() {
+    input! {
+        n: usize,
+        a: [u64; n],
+    }
+    let sum: u64 = a.iter().sum();
+    output!("{}", sum);
+    outputln!(" {}", a.iter().max().unwrap());
+}
+
+#[test]
+fn samples() {
+    test_samples! {
+        solve,
+        "3\n1 2 3\n" => "6 3\n",
+        "1\n100\n" => "100\n100\n", // whitespaces are ignored
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "1 of 2 samples failed\n\nsample #2 failed: token #1 differs: `5` -> `2`"
+)]
+fn samples_wrong_answer() {
+    test_samples! {
+        solve,
+        "3\n1 2 3\n" => "6 3\n",
+        "2\n1 2\n" => "3 5\n",
+    }
+}
+
+#[test]
+fn samples_with_closure() {
+    let mut runs = 0;
+    test_samples! {
+        || {
+            runs += 1;
+            input!(s: String);
+            outputln!("{}", s.len());
+        },
+        "abc" => "3",
+        "de" => "2",
+    }
+    assert_eq!(runs, 2);
+}
+
+#[test]
+fn nested_sources() {
+    let res = with_source(AutoSource::from("1 2"), || {
+        input!(a: u32);
+        let inner = with_source(AutoSource::from("10"), || {
+            input!(x: u32);
+            x
+        });
+        input!(b: u32);
+        (a, inner, b)
+    });
+
+    assert_eq!(res, (1, 10, 2));
+}
+
+#[test]
+fn capture() {
+    let outer = capture_output(|| {
+        outputln!("outer");
+        let inner = capture_output(|| output!("inner"));
+        assert_eq!(inner, "inner");
+
+        // the output of other threads is also captured.
+        thread::spawn(|| outputln!("thread")).join().unwrap();
+    });
+
+    assert_eq!(outer, "outer\nthread\n");
+}
+
+#[test]
+fn capture_restored_after_panic() {
+    let res = panic::catch_unwind(|| {
+        capture_output(|| {
+            outputln!("lost");
+            panic!("wrong answer");
+        })
+    });
+    assert!(res.is_err());
+
+    let output = capture_output(|| outputln!("{}", 42));
+    assert_eq!(output, "42\n");
+}
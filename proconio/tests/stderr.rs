@@ -0,0 +1,45 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{eoutput, eoutputln, flush_stderr, outputln};
+
+fn test_stderr() {
+    eoutput!("{} ", 1);
+    eoutputln!("{}", 2);
+    eoutputln!();
+    eoutputln!(fixed(2), 0.5, 1.0);
+    flush_stderr();
+
+    // not flushed explicitly, but printed by the panic hook.
+    eoutputln!("before panic");
+    outputln!("lost");
+    panic!("wrong answer");
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard error
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .fails()
+            .and()
+            .stdout()
+            .is("")
+            .and()
+            .stderr()
+            .contains("1 2\n\n0.50 1.00\nbefore panic\n")
+            .and()
+            .stderr()
+            .contains("wrong answer")
+            .unwrap();
+        return;
+    }
+
+    test_stderr();
+}
@@ -5,7 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
 // distributed except according to those terms.
 
-use proconio::{input, is_stdin_empty};
+use proconio::{input, is_stdin_empty, peek_stdin_token};
 
 fn test_stdin() {
     assert!(!is_stdin_empty());
@@ -13,6 +13,7 @@ fn test_stdin() {
         n: usize,
     }
     assert!(!is_stdin_empty());
+    assert_eq!(peek_stdin_token().as_deref(), Some("1"));
     println!("{}", n);
 
     for c in 0..n {
@@ -26,6 +27,7 @@ fn test_stdin() {
         println!("{} {}", i, j);
     }
     assert!(is_stdin_empty());
+    assert_eq!(peek_stdin_token(), None);
 }
 
 fn test_for(input: &str, expected_stdout: &str) {
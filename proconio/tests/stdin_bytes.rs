@@ -0,0 +1,38 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::input;
+use proconio::marker::Bytes;
+
+fn test_stdin_bytes() {
+    proconio::read_stdin_as_bytes();
+    input! {
+        n: usize,
+        a: [i64; n],
+        raw: Bytes,
+        s: String,
+    }
+
+    println!("{}", a.iter().sum::<i64>());
+    println!("{:?}", raw);
+    println!("{}", s);
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .stdin("3\n1 -2 30\n\u{ff} abc\n")
+            .stdout()
+            .is("29\n[195, 191]\nabc")
+            .unwrap();
+        return;
+    }
+
+    test_stdin_bytes();
+}
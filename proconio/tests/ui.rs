@@ -1,5 +1,21 @@
-#[rustversion::stable(1.42.0)]
+#[rustversion::stable(1.67.0)]
 #[test]
 fn ui() {
-    trybuild::TestCases::new().compile_fail("./tests/ui/**/*.rs");
+    trybuild::TestCases::new().compile_fail("./tests/ui/fastout/*.rs");
+}
+
+// the errors of `proconio-derive` itself, which don't depend on the version of rustc.
+#[test]
+fn derive_readable() {
+    trybuild::TestCases::new().compile_fail("./tests/ui/derive_readable/*.rs");
+}
+
+#[test]
+fn input_checked() {
+    trybuild::TestCases::new().compile_fail("./tests/ui/input_checked/*.rs");
+}
+
+#[test]
+fn pass() {
+    trybuild::TestCases::new().pass("./tests/pass/**/*.rs");
 }
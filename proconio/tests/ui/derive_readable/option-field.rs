@@ -0,0 +1,9 @@
+use proconio::derive_readable;
+
+#[derive_readable]
+struct Query {
+    kind: u8,
+    value: Option<u32>,
+}
+
+fn main() {}
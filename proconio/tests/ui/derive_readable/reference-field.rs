@@ -0,0 +1,8 @@
+use proconio::derive_readable;
+
+#[derive_readable]
+struct Name {
+    name: &'static str,
+}
+
+fn main() {}
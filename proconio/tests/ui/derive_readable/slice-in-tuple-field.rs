@@ -0,0 +1,6 @@
+use proconio::derive_readable;
+
+#[derive_readable]
+struct Entry(u32, (u8, Box<[u8]>));
+
+fn main() {}
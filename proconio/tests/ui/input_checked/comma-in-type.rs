@@ -0,0 +1,8 @@
+use proconio::input_checked;
+
+fn main() {
+    input_checked! {
+        n: usize,
+        m: proconio::marker::HashMapOf<u32, String>,
+    }
+}
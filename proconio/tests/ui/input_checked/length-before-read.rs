@@ -0,0 +1,8 @@
+use proconio::input_checked;
+
+fn main() {
+    input_checked! {
+        a: [i64; n],
+        n: usize,
+    }
+}
@@ -0,0 +1,9 @@
+use proconio::input_checked;
+
+fn main() {
+    input_checked! {
+        n: usize,
+        a:,
+        b,
+    }
+}
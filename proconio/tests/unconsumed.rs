@@ -0,0 +1,47 @@
+// Copyright 2019 statiolake <statiolake@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be copied, modified, or
+// distributed except according to those terms.
+
+use proconio::{flush_on_exit, input, outputln};
+
+fn test_unconsumed() {
+    let _guard = flush_on_exit();
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+    outputln!("{}", a.iter().sum::<u32>());
+}
+
+fn main() {
+    use assert_cli::Assert;
+    use std::env::args;
+
+    // relaunch the app to capture standard error
+    if args().len() == 1 {
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .with_env(assert_cli::Environment::inherit().insert("PROCONIO_DEBUG", "1"))
+            .stdin("2 1 2 3\n")
+            .stdout()
+            .is("3\n")
+            .and()
+            .stderr()
+            .is("warning: the input is not consumed; the next token is `3`\n")
+            .unwrap();
+        Assert::command(&[&*args().next().unwrap(), "foo"])
+            .with_env(assert_cli::Environment::inherit().insert("PROCONIO_DEBUG", "1"))
+            .stdin("2 1 2\n")
+            .stdout()
+            .is("3\n")
+            .and()
+            .stderr()
+            .is("")
+            .unwrap();
+        return;
+    }
+
+    test_unconsumed();
+}